@@ -2,7 +2,7 @@
 //! tarball in Unix operating systems.
 
 use std::fs::File;
-use std::io::{Read, Seek, SeekFrom};
+use std::io::{copy, sink, Read, Seek, SeekFrom};
 use std::path::Path;
 
 use super::{Archive, ArchiveError, Origin};
@@ -95,6 +95,10 @@ impl Archive for Tarball {
         let decoded = GzDecoder::new(self.data);
         let mut tarball = tar::Archive::new(ProgressRead::new(decoded, (), progress));
         tarball.unpack(dest)?;
+
+        // Read any data remaining after the end of the tar entries, so that a remote archive is
+        // fully written to its cache file
+        copy(&mut tarball.into_inner(), &mut sink())?;
         Ok(())
     }
     fn origin(&self) -> Origin {
@@ -14,6 +14,10 @@ https://github.com/volta-cli/volta/issues with the details!";
 
 const PERMISSIONS_CTA: &str = "Please ensure you have correct permissions to the Volta directory.";
 
+const OFFLINE_CTA: &str =
+    "Please run the command again with network access, without `--offline` and with
+`VOLTA_OFFLINE` unset.";
+
 #[derive(Debug)]
 #[cfg_attr(test, derive(PartialEq))]
 pub enum ErrorKind {
@@ -45,9 +49,27 @@ pub enum ErrorKind {
         package: String,
     },
 
-    /// Thrown when a user tries to `volta pin` something other than node/yarn/npm.
-    CannotPinPackage {
-        package: String,
+    /// Thrown when a user tries to `volta pin` a package from a git URL, tarball, or directory
+    CannotPinPackageSource {
+        source: String,
+    },
+
+    /// Thrown when the checksum file for a tool could not be downloaded
+    ChecksumFetchError {
+        tool: tool::Spec,
+        from_url: String,
+    },
+
+    /// Thrown when a downloaded tool does not match its published checksum
+    ChecksumMismatch {
+        tool: tool::Spec,
+        from_url: String,
+    },
+
+    /// Thrown when the checksum file for a tool has no entry for the downloaded file
+    ChecksumNotFound {
+        tool: tool::Spec,
+        from_url: String,
     },
 
     /// Thrown when the Completions out-dir is not a directory
@@ -91,6 +113,14 @@ pub enum ErrorKind {
 
     CurrentDirError,
 
+    /// Thrown when deleting the profile that is in use
+    DeleteActiveProfile {
+        name: String,
+    },
+
+    /// Thrown when deleting the default profile, which always exists
+    DeleteDefaultProfile,
+
     /// Thrown when deleting a directory fails
     DeleteDirectoryError {
         directory: PathBuf,
@@ -143,6 +173,15 @@ pub enum ErrorKind {
         command: String,
     },
 
+    /// Thrown when `volta install --as` is used with anything other than a single package
+    InstallAliasRequiresPackage,
+
+    /// Thrown when `volta install --migrate-packages` is used without installing Node
+    InstallMigrateRequiresNode,
+
+    /// Thrown when `volta install` is given a Node or npm version without any package to install
+    InstallPlatformRequiresPackage,
+
     /// Thrown when determining the name of a newly-installed package fails
     InstalledPackageNameError,
 
@@ -163,12 +202,29 @@ pub enum ErrorKind {
         version: String,
     },
 
+    /// Thrown when the bin names for a package installed under an alias can't be determined
+    InvalidPackageAlias {
+        alias: String,
+        package: String,
+        bins: String,
+    },
+
+    /// Thrown when a profile name is not a valid directory name
+    InvalidProfileName {
+        name: String,
+    },
+
     /// Thrown when a tool name is invalid per npm's rules.
     InvalidToolName {
         name: String,
         errors: Vec<String>,
     },
 
+    /// Thrown when a package to install from a local directory or tarball doesn't exist
+    LocalPackageNotFound {
+        path: String,
+    },
+
     /// Thrown when unable to acquire a lock on the Volta directory
     LockAcquireError,
 
@@ -177,6 +233,9 @@ pub enum ErrorKind {
         command: String,
     },
 
+    /// Thrown when pnpm is not set at the command-line
+    NoCommandLinePnpm,
+
     /// Thrown when Yarn is not set at the command-line
     NoCommandLineYarn,
 
@@ -208,6 +267,9 @@ pub enum ErrorKind {
     /// Thrown when parsing the project manifest and there is a `"volta"` key without Node
     NoProjectNodeInManifest,
 
+    /// Thrown when pnpm is not set in a project
+    NoProjectPnpm,
+
     /// Thrown when Yarn is not set in a project
     NoProjectYarn,
 
@@ -220,6 +282,9 @@ pub enum ErrorKind {
     /// Thrown when the user tries to pin Node or Yarn versions outside of a package.
     NotInPackage,
 
+    /// Thrown when default pnpm is not set
+    NoDefaultPnpm,
+
     /// Thrown when default Yarn is not set
     NoDefaultYarn,
 
@@ -242,11 +307,27 @@ pub enum ErrorKind {
         version: String,
     },
 
+    /// Thrown when a tool needs to be downloaded while Volta is in offline mode
+    OfflineFetchError {
+        tool: tool::Spec,
+    },
+
+    /// Thrown when there is no local version matching the requested Semver/Tag in offline mode
+    OfflineVersionNotFound {
+        tool: String,
+        matching: String,
+    },
+
     /// Thrown when the command to install a global package is not successful
     PackageInstallFailed {
         package: String,
     },
 
+    /// Thrown when the `packageManager` field of a package.json file could not be parsed
+    PackageManagerParseError {
+        value: String,
+    },
+
     /// Thrown when parsing the package manifest fails
     PackageManifestParseError {
         package: String,
@@ -308,6 +389,16 @@ pub enum ErrorKind {
     /// Thrown when unable to parse the platform.json file
     ParsePlatformError,
 
+    /// Thrown when unable to parse the settings.json file
+    ParseSettingsError {
+        file: PathBuf,
+    },
+
+    /// Thrown when a toolfile could not be parsed
+    ParseToolfileError {
+        file: PathBuf,
+    },
+
     /// Thrown when unable to parse a tool spec (`<tool>[@<version>]`)
     ParseToolSpecError {
         tool_spec: String,
@@ -318,6 +409,21 @@ pub enum ErrorKind {
         tool: String,
     },
 
+    /// Thrown when there is no pnpm version matching a requested semver specifier.
+    PnpmVersionNotFound {
+        matching: String,
+    },
+
+    /// Thrown when creating a profile that already exists
+    ProfileAlreadyExists {
+        name: String,
+    },
+
+    /// Thrown when a profile to use or delete does not exist
+    ProfileNotFound {
+        name: String,
+    },
+
     /// Thrown when executing a project-local binary fails
     ProjectLocalBinaryExecError {
         command: String,
@@ -328,6 +434,11 @@ pub enum ErrorKind {
         command: String,
     },
 
+    /// Thrown when a directory passed to `volta prune` is not in a project
+    PruneProjectNotFound {
+        dir: PathBuf,
+    },
+
     /// Thrown when a publish hook contains both the url and bin fields
     PublishHookBothUrlAndBin,
 
@@ -369,6 +480,11 @@ pub enum ErrorKind {
         file: PathBuf,
     },
 
+    /// Thrown when there was an error reading a project's `.nvmrc` or `.node-version` file
+    ReadNodeVersionFileError {
+        file: PathBuf,
+    },
+
     /// Thrown when there was an error reading the npm manifest file
     ReadNpmManifestError,
 
@@ -382,16 +498,41 @@ pub enum ErrorKind {
         file: PathBuf,
     },
 
+    /// Thrown when unable to read the file naming the active profile
+    ReadProfileError {
+        file: PathBuf,
+    },
+
+    /// Thrown when there was an error opening the settings.json file
+    ReadSettingsError {
+        file: PathBuf,
+    },
+
+    /// Thrown when a toolfile could not be read
+    ReadToolfileError {
+        file: PathBuf,
+    },
+
     /// Thrown when unable to read the user Path environment variable from the registry
     #[cfg(windows)]
     ReadUserPathError,
 
+    /// Thrown when there was an error reading a Yarn configuration file
+    ReadYarnrcError {
+        file: PathBuf,
+    },
+
     /// Thrown when the public registry for Node or Yarn could not be downloaded.
     RegistryFetchError {
         tool: String,
         from_url: String,
     },
 
+    /// Thrown when a package to reinstall is not installed
+    ReinstallPackageNotFound {
+        package: String,
+    },
+
     /// Thrown when the shim binary is called directly, not through a symlink
     RunShimDirectly,
 
@@ -421,11 +562,25 @@ pub enum ErrorKind {
     /// Thrown when serializing the platform to JSON fails
     StringifyPlatformError,
 
+    /// Thrown when serializing a toolfile to JSON fails
+    StringifyToolfileError,
+
     /// Thrown when a given feature has not yet been implemented
     Unimplemented {
         feature: String,
     },
 
+    /// Thrown when trying to uninstall a tool without specifying an exact version
+    UninstallExactVersionRequired {
+        tool: String,
+    },
+
+    /// Thrown when trying to uninstall a tool version that is still in use
+    UninstallToolInUse {
+        tool: String,
+        dependents: Vec<String>,
+    },
+
     /// Thrown when unpacking an archive (tarball or zip) fails
     UnpackArchiveError {
         tool: String,
@@ -444,6 +599,11 @@ pub enum ErrorKind {
         manager: PackageManager,
     },
 
+    /// Thrown when a tool to upgrade is neither a default tool nor an installed package
+    UpgradeToolNotFound {
+        tool: String,
+    },
+
     VersionParseError {
         version: String,
     },
@@ -478,11 +638,26 @@ pub enum ErrorKind {
         file: PathBuf,
     },
 
+    /// Thrown when there was an error writing the package manifest of a pnpm staging directory
+    WritePackageManifestError {
+        file: PathBuf,
+    },
+
     /// Thrown when writing the platform.json file fails
     WritePlatformError {
         file: PathBuf,
     },
 
+    /// Thrown when unable to write the file naming the active profile
+    WriteProfileError {
+        file: PathBuf,
+    },
+
+    /// Thrown when a toolfile could not be written
+    WriteToolfileError {
+        file: PathBuf,
+    },
+
     /// Thrown when unable to write the user PATH environment variable
     #[cfg(windows)]
     WriteUserPathError,
@@ -545,12 +720,36 @@ VOLTA_BYPASS is enabled, please ensure that the command exists on your system or
 Use `volta install {}` to update the default version.",
                 package
             ),
-            ErrorKind::CannotPinPackage { package } => write!(
+            ErrorKind::CannotPinPackageSource { source } => write!(
                 f,
-                "Only node and yarn can be pinned in a project
+                "Cannot pin the package from '{}'.
 
-Use `npm install` or `yarn add` to select a version of {} for this project.",
-                package
+Only packages from the package registry can be pinned in a project.",
+                source
+            ),
+            ErrorKind::ChecksumFetchError { tool, from_url } => write!(
+                f,
+                "Could not download checksums for {}
+from {}
+
+Please verify your internet connection.",
+                tool, from_url
+            ),
+            ErrorKind::ChecksumMismatch { tool, from_url } => write!(
+                f,
+                "Checksum verification failed for {}
+downloaded from {}
+
+The download may have been corrupted. Please try again.",
+                tool, from_url
+            ),
+            ErrorKind::ChecksumNotFound { tool, from_url } => write!(
+                f,
+                "Could not find a checksum for {}
+in {}
+
+Please ensure that the checksum file is published alongside the download.",
+                tool, from_url
             ),
             ErrorKind::CompletionsOutFileError { path } => write!(
                 f,
@@ -625,6 +824,17 @@ in {}
 
 Please ensure that you have the correct permissions."
             ),
+            ErrorKind::DeleteActiveProfile { name } => write!(
+                f,
+                "Could not delete the profile '{}' because it is in use.
+
+Use `volta profile use` to switch to another profile first.",
+                name
+            ),
+            ErrorKind::DeleteDefaultProfile => write!(
+                f,
+                "The default profile cannot be deleted."
+            ),
             ErrorKind::DeleteDirectoryError { directory } => write!(
                 f,
                 "Could not remove directory
@@ -719,6 +929,24 @@ Please include one of 'bin', 'prefix', or 'template'"
 Please ensure that the correct command is specified.",
                 command
             ),
+            ErrorKind::InstallAliasRequiresPackage => write!(
+                f,
+                "The `--as` option can only be used when installing a single package.
+
+Please install other tools in a separate command."
+            ),
+            ErrorKind::InstallMigrateRequiresNode => write!(
+                f,
+                "The `--migrate-packages` option can only be used when installing Node.
+
+Use `volta reinstall --all` to reinstall your packages with the default Node."
+            ),
+            ErrorKind::InstallPlatformRequiresPackage => write!(
+                f,
+                "The `--node` and `--npm` options can only be used when installing packages.
+
+Use `volta install node@<version>` to change the default version of Node."
+            ),
             ErrorKind::InstalledPackageNameError => write!(
                 f,
                 "Could not determine the name of the package that was just installed.
@@ -770,6 +998,26 @@ To {action} the packages '{name}' and '{version}', please {action} them in separ
                 write!(f, "{}\n\n{}", error, wrapped_cta)
             }
 
+            ErrorKind::InvalidPackageAlias {
+                alias,
+                package,
+                bins,
+            } => write!(
+                f,
+                "Could not determine the executable names for '{}' installed as '{}'.
+
+The package provides several executables: {}
+Please use an alias that starts with the name of one of them.",
+                package, alias, bins
+            ),
+
+            ErrorKind::InvalidProfileName { name } => write!(
+                f,
+                "Invalid profile name '{}'
+
+Profile names may only contain letters, numbers, '-', '_', and '.', and may not start with '.'",
+                name
+            ),
             ErrorKind::InvalidToolName { name, errors } => {
                 let indentation = "    ";
                 let wrapped = match text_width() {
@@ -790,6 +1038,13 @@ To {action} the packages '{name}' and '{version}', please {action} them in separ
                     name, call_to_action, formatted_errs
                 )
             }
+            ErrorKind::LocalPackageNotFound { path } => write!(
+                f,
+                "Could not find the package to install at '{}'
+
+Please ensure the directory or tarball exists.",
+                path
+            ),
             // Note: No CTA as this error is purely informational and shouldn't be exposed to the user
             ErrorKind::LockAcquireError => write!(
                 f,
@@ -802,6 +1057,12 @@ To {action} the packages '{name}' and '{version}', please {action} them in separ
 Please ensure you have a Node version selected with `volta {} node` (see `volta help {0}` for more info).",
                 command
             ),
+            ErrorKind::NoCommandLinePnpm => write!(
+                f,
+                "No pnpm version specified.
+
+Use `volta run --pnpm` to select a version (see `volta help run` for more info)."
+            ),
             ErrorKind::NoCommandLineYarn => write!(
                 f,
                 "No Yarn version specified.
@@ -858,6 +1119,12 @@ To run any Node command, first set a default version using `volta install node`"
                 "No Node version found in this project.
 
 Use `volta pin node` to select a version (see `volta help pin` for more info)."
+            ),
+            ErrorKind::NoProjectPnpm => write!(
+                f,
+                "No pnpm version found in this project.
+
+Use `volta pin pnpm` to select a version (see `volta help pin` for more info)."
             ),
             ErrorKind::NoProjectYarn => write!(
                 f,
@@ -878,6 +1145,12 @@ Please create one of these and try again; or you can edit your profile manually
                 "Not in a node package.
 
 Use `volta install` to select a default version of a tool."
+            ),
+            ErrorKind::NoDefaultPnpm => write!(
+                f,
+                "pnpm is not available.
+
+Use `volta install pnpm` to select a default version (see `volta help install` for more info)."
             ),
             ErrorKind::NoDefaultYarn => write!(
                 f,
@@ -913,6 +1186,20 @@ Please verify that the version is correct."#,
 This project is configured to use version {} of npm.",
                 version
             ),
+            ErrorKind::OfflineFetchError { tool } => write!(
+                f,
+                "Could not download {} while offline.
+
+{}",
+                tool, OFFLINE_CTA
+            ),
+            ErrorKind::OfflineVersionNotFound { tool, matching } => write!(
+                f,
+                r#"Could not find a {} version matching "{}" in the local inventory while offline.
+
+{}"#,
+                tool, matching, OFFLINE_CTA
+            ),
             ErrorKind::PackageInstallFailed { package } => write!(
                 f,
                 "Could not install package '{}'
@@ -920,6 +1207,13 @@ This project is configured to use version {} of npm.",
 Please confirm the package is valid and run with `--verbose` for more diagnostics.",
                 package
             ),
+            ErrorKind::PackageManagerParseError { value } => write!(
+                f,
+                "Could not parse the `packageManager` field \"{}\" in package.json
+
+Please ensure it is in the form `name@version`, e.g. `yarn@1.22.19`, optionally followed by a hash.",
+                value
+            ),
             ErrorKind::PackageManifestParseError { package } => write!(
                 f,
                 "Could not parse package.json manifest for {}
@@ -1028,6 +1322,22 @@ Please ensure the version of Node is correct."
 {}",
                 REPORT_BUG_CTA
             ),
+            ErrorKind::ParseSettingsError { file } => write!(
+                f,
+                "Could not parse settings file.
+from {}
+
+Please ensure the file is correctly formatted, and that `packageManager` is one of npm, pnpm, or yarn.",
+                file.display()
+            ),
+            ErrorKind::ParseToolfileError { file } => write!(
+                f,
+                "Could not parse toolfile
+from {}
+
+Please ensure the file is correctly formatted, and that every `manager` is one of npm, pnpm, or yarn.",
+                file.display()
+            ),
             ErrorKind::ParseToolSpecError { tool_spec } => write!(
                 f,
                 "Could not parse tool spec `{}`
@@ -1042,6 +1352,27 @@ Please supply a spec in the format `<tool name>[@<version>]`.",
 {}",
                 tool, PERMISSIONS_CTA
             ),
+            ErrorKind::PnpmVersionNotFound { matching } => write!(
+                f,
+                r#"Could not find pnpm version matching "{}" in the version registry.
+
+Please verify that the version is correct."#,
+                matching
+            ),
+            ErrorKind::ProfileAlreadyExists { name } => write!(
+                f,
+                "The profile '{}' already exists.
+
+Use `volta profile use {}` to switch to it.",
+                name, name
+            ),
+            ErrorKind::ProfileNotFound { name } => write!(
+                f,
+                "Could not find the profile '{}'.
+
+Use `volta profile list` to see the available profiles.",
+                name
+            ),
             ErrorKind::ProjectLocalBinaryExecError { command } => write!(
                 f,
                 "Could not execute `{}`
@@ -1056,6 +1387,13 @@ Please ensure you have correct permissions to access the file.",
 Please ensure that all project dependencies are installed with `npm install` or `yarn install`",
                 command
             ),
+            ErrorKind::PruneProjectNotFound { dir } => write!(
+                f,
+                "Could not find a project in '{}'.
+
+Please ensure that the directory contains a package.json file.",
+                dir.display()
+            ),
             ErrorKind::PublishHookBothUrlAndBin => write!(
                 f,
                 "Publish hook configuration includes both hook types.
@@ -1125,6 +1463,15 @@ from {}
                 "Could not read Node index cache expiration
 from {}
 
+{}",
+                file.display(),
+                PERMISSIONS_CTA
+            ),
+            ErrorKind::ReadNodeVersionFileError { file } => write!(
+                f,
+                "Could not read Node version file
+from {}
+
 {}",
                 file.display(),
                 PERMISSIONS_CTA
@@ -1149,6 +1496,33 @@ from {}
                 "Could not read default platform file
 from {}
 
+{}",
+                file.display(),
+                PERMISSIONS_CTA
+            ),
+            ErrorKind::ReadProfileError { file } => write!(
+                f,
+                "Could not read the active profile
+from {}
+
+{}",
+                file.display(),
+                PERMISSIONS_CTA
+            ),
+            ErrorKind::ReadSettingsError { file } => write!(
+                f,
+                "Could not read settings file
+from {}
+
+{}",
+                file.display(),
+                PERMISSIONS_CTA
+            ),
+            ErrorKind::ReadToolfileError { file } => write!(
+                f,
+                "Could not read toolfile
+from {}
+
 {}",
                 file.display(),
                 PERMISSIONS_CTA
@@ -1160,6 +1534,15 @@ from {}
 
 Please ensure you have access to the your environment variables."
             ),
+            ErrorKind::ReadYarnrcError { file } => write!(
+                f,
+                "Could not read Yarn configuration file
+from {}
+
+{}",
+                file.display(),
+                PERMISSIONS_CTA
+            ),
             ErrorKind::RegistryFetchError { tool, from_url } => write!(
                 f,
                 "Could not download {} version registry
@@ -1168,6 +1551,13 @@ from {}
 Please verify your internet connection.",
                 tool, from_url
             ),
+            ErrorKind::ReinstallPackageNotFound { package } => write!(
+                f,
+                "Could not find the installed package '{}' to reinstall.
+
+Use `volta list all` to see the packages that are installed.",
+                package
+            ),
             ErrorKind::RunShimDirectly => write!(
                 f,
                 "'volta-shim' should not be called directly.
@@ -1217,12 +1607,39 @@ at {}
                 f,
                 "Could not serialize platform settings.
 
+{}",
+                REPORT_BUG_CTA
+            ),
+            ErrorKind::StringifyToolfileError => write!(
+                f,
+                "Could not serialize toolfile.
+
 {}",
                 REPORT_BUG_CTA
             ),
             ErrorKind::Unimplemented { feature } => {
                 write!(f, "{} is not supported yet.", feature)
             }
+            ErrorKind::UninstallExactVersionRequired { tool } => write!(
+                f,
+                "Could not determine which version of {} to uninstall.
+
+Please specify an exact version, e.g. `volta uninstall {0}@1.2.3`",
+                tool
+            ),
+            ErrorKind::UninstallToolInUse { tool, dependents } => {
+                let formatted_dependents = indent(&dependents.join("\n"), "    ");
+
+                write!(
+                    f,
+                    "Could not uninstall {} because it is still used by:
+
+{}
+
+To remove it anyway, use `volta uninstall --force {0}`",
+                    tool, formatted_dependents
+                )
+            }
             ErrorKind::UnpackArchiveError { tool, version } => write!(
                 f,
                 "Could not unpack {} v{}
@@ -1238,12 +1655,14 @@ Please ensure it is installed with `{} {0}`"#,
                 package,
                 match manager {
                     PackageManager::Npm => "npm i -g",
+                    PackageManager::Pnpm => "volta install --manager pnpm",
                     PackageManager::Yarn => "yarn global add",
                 }
             ),
             ErrorKind::UpgradePackageWrongManager { package, manager } => {
                 let (name, command) = match manager {
                     PackageManager::Npm => ("npm", "npm update -g"),
+                    PackageManager::Pnpm => ("pnpm", "volta install --manager pnpm"),
                     PackageManager::Yarn => ("Yarn", "yarn global upgrade"),
                 };
                 write!(
@@ -1254,6 +1673,13 @@ To upgrade it, please use the command `{} {0}`"#,
                     package, name, command
                 )
             }
+            ErrorKind::UpgradeToolNotFound { tool } => write!(
+                f,
+                "Could not find '{}' among your default tools or installed packages.
+
+Use `volta list all` to see the tools that can be upgraded.",
+                tool
+            ),
             ErrorKind::VersionParseError { version } => write!(
                 f,
                 r#"Could not parse version "{}"
@@ -1309,6 +1735,15 @@ to {}
                 "Could not write package configuration
 to {}
 
+{}",
+                file.display(),
+                PERMISSIONS_CTA
+            ),
+            ErrorKind::WritePackageManifestError { file } => write!(
+                f,
+                "Could not write package manifest
+to {}
+
 {}",
                 file.display(),
                 PERMISSIONS_CTA
@@ -1318,6 +1753,24 @@ to {}
                 "Could not save platform settings
 to {}
 
+{}",
+                file.display(),
+                PERMISSIONS_CTA
+            ),
+            ErrorKind::WriteProfileError { file } => write!(
+                f,
+                "Could not save the active profile
+to {}
+
+{}",
+                file.display(),
+                PERMISSIONS_CTA
+            ),
+            ErrorKind::WriteToolfileError { file } => write!(
+                f,
+                "Could not write toolfile
+to {}
+
 {}",
                 file.display(),
                 PERMISSIONS_CTA
@@ -1357,7 +1810,10 @@ impl ErrorKind {
             ErrorKind::BuildPathError => ExitCode::EnvironmentError,
             ErrorKind::BypassError { .. } => ExitCode::ExecutionFailure,
             ErrorKind::CannotFetchPackage { .. } => ExitCode::InvalidArguments,
-            ErrorKind::CannotPinPackage { .. } => ExitCode::InvalidArguments,
+            ErrorKind::CannotPinPackageSource { .. } => ExitCode::InvalidArguments,
+            ErrorKind::ChecksumFetchError { .. } => ExitCode::NetworkError,
+            ErrorKind::ChecksumMismatch { .. } => ExitCode::NetworkError,
+            ErrorKind::ChecksumNotFound { .. } => ExitCode::NetworkError,
             ErrorKind::CompletionsOutFileError { .. } => ExitCode::InvalidArguments,
             ErrorKind::ContainingDirError { .. } => ExitCode::FileSystemError,
             ErrorKind::CouldNotDetermineTool => ExitCode::UnknownError,
@@ -1368,6 +1824,8 @@ impl ErrorKind {
             ErrorKind::CreateTempDirError { .. } => ExitCode::FileSystemError,
             ErrorKind::CreateTempFileError { .. } => ExitCode::FileSystemError,
             ErrorKind::CurrentDirError => ExitCode::EnvironmentError,
+            ErrorKind::DeleteActiveProfile { .. } => ExitCode::InvalidArguments,
+            ErrorKind::DeleteDefaultProfile => ExitCode::InvalidArguments,
             ErrorKind::DeleteDirectoryError { .. } => ExitCode::FileSystemError,
             ErrorKind::DeleteFileError { .. } => ExitCode::FileSystemError,
             ErrorKind::DeprecatedCommandError { .. } => ExitCode::InvalidArguments,
@@ -1379,13 +1837,20 @@ impl ErrorKind {
             ErrorKind::HookMultipleFieldsSpecified => ExitCode::ConfigurationError,
             ErrorKind::HookNoFieldsSpecified => ExitCode::ConfigurationError,
             ErrorKind::HookPathError { .. } => ExitCode::ConfigurationError,
+            ErrorKind::InstallAliasRequiresPackage => ExitCode::InvalidArguments,
+            ErrorKind::InstallMigrateRequiresNode => ExitCode::InvalidArguments,
+            ErrorKind::InstallPlatformRequiresPackage => ExitCode::InvalidArguments,
             ErrorKind::InstalledPackageNameError => ExitCode::UnknownError,
             ErrorKind::InvalidHookCommand { .. } => ExitCode::ExecutableNotFound,
             ErrorKind::InvalidHookOutput { .. } => ExitCode::ExecutionFailure,
             ErrorKind::InvalidInvocation { .. } => ExitCode::InvalidArguments,
+            ErrorKind::InvalidPackageAlias { .. } => ExitCode::InvalidArguments,
+            ErrorKind::InvalidProfileName { .. } => ExitCode::InvalidArguments,
             ErrorKind::InvalidToolName { .. } => ExitCode::InvalidArguments,
+            ErrorKind::LocalPackageNotFound { .. } => ExitCode::InvalidArguments,
             ErrorKind::LockAcquireError => ExitCode::FileSystemError,
             ErrorKind::NoBundledNpm { .. } => ExitCode::ConfigurationError,
+            ErrorKind::NoCommandLinePnpm => ExitCode::ConfigurationError,
             ErrorKind::NoCommandLineYarn => ExitCode::ConfigurationError,
             ErrorKind::NoDefaultNodeVersion { .. } => ExitCode::ConfigurationError,
             ErrorKind::NodeVersionNotFound { .. } => ExitCode::NoVersionMatch,
@@ -1395,15 +1860,20 @@ impl ErrorKind {
             ErrorKind::NoPinnedNodeVersion { .. } => ExitCode::ConfigurationError,
             ErrorKind::NoPlatform => ExitCode::ConfigurationError,
             ErrorKind::NoProjectNodeInManifest => ExitCode::ConfigurationError,
+            ErrorKind::NoProjectPnpm => ExitCode::ConfigurationError,
             ErrorKind::NoProjectYarn => ExitCode::ConfigurationError,
             ErrorKind::NoShellProfile { .. } => ExitCode::EnvironmentError,
             ErrorKind::NotInPackage => ExitCode::ConfigurationError,
+            ErrorKind::NoDefaultPnpm => ExitCode::ConfigurationError,
             ErrorKind::NoDefaultYarn => ExitCode::ConfigurationError,
             ErrorKind::NpmLinkMissingPackage { .. } => ExitCode::ConfigurationError,
             ErrorKind::NpmLinkWrongManager { .. } => ExitCode::ConfigurationError,
             ErrorKind::NpmVersionNotFound { .. } => ExitCode::NoVersionMatch,
             ErrorKind::NpxNotAvailable { .. } => ExitCode::ExecutableNotFound,
+            ErrorKind::OfflineFetchError { .. } => ExitCode::NetworkError,
+            ErrorKind::OfflineVersionNotFound { .. } => ExitCode::NoVersionMatch,
             ErrorKind::PackageInstallFailed { .. } => ExitCode::UnknownError,
+            ErrorKind::PackageManagerParseError { .. } => ExitCode::ConfigurationError,
             ErrorKind::PackageManifestParseError { .. } => ExitCode::ConfigurationError,
             ErrorKind::PackageManifestReadError { .. } => ExitCode::FileSystemError,
             ErrorKind::PackageNotFound { .. } => ExitCode::InvalidArguments,
@@ -1413,6 +1883,8 @@ impl ErrorKind {
             ErrorKind::PackageWriteError { .. } => ExitCode::FileSystemError,
             ErrorKind::ParseBinConfigError => ExitCode::UnknownError,
             ErrorKind::ParseHooksError { .. } => ExitCode::ConfigurationError,
+            ErrorKind::ParseSettingsError { .. } => ExitCode::ConfigurationError,
+            ErrorKind::ParseToolfileError { .. } => ExitCode::ConfigurationError,
             ErrorKind::ParseToolSpecError { .. } => ExitCode::InvalidArguments,
             ErrorKind::ParseNodeIndexCacheError => ExitCode::UnknownError,
             ErrorKind::ParseNodeIndexError { .. } => ExitCode::NetworkError,
@@ -1421,8 +1893,12 @@ impl ErrorKind {
             ErrorKind::ParsePackageConfigError => ExitCode::UnknownError,
             ErrorKind::ParsePlatformError => ExitCode::ConfigurationError,
             ErrorKind::PersistInventoryError { .. } => ExitCode::FileSystemError,
+            ErrorKind::PnpmVersionNotFound { .. } => ExitCode::NoVersionMatch,
+            ErrorKind::ProfileAlreadyExists { .. } => ExitCode::InvalidArguments,
+            ErrorKind::ProfileNotFound { .. } => ExitCode::InvalidArguments,
             ErrorKind::ProjectLocalBinaryExecError { .. } => ExitCode::ExecutionFailure,
             ErrorKind::ProjectLocalBinaryNotFound { .. } => ExitCode::FileSystemError,
+            ErrorKind::PruneProjectNotFound { .. } => ExitCode::ConfigurationError,
             ErrorKind::PublishHookBothUrlAndBin => ExitCode::ConfigurationError,
             ErrorKind::PublishHookNeitherUrlNorBin => ExitCode::ConfigurationError,
             ErrorKind::ReadBinConfigDirError { .. } => ExitCode::FileSystemError,
@@ -1432,12 +1908,18 @@ impl ErrorKind {
             ErrorKind::ReadHooksError { .. } => ExitCode::FileSystemError,
             ErrorKind::ReadNodeIndexCacheError { .. } => ExitCode::FileSystemError,
             ErrorKind::ReadNodeIndexExpiryError { .. } => ExitCode::FileSystemError,
+            ErrorKind::ReadNodeVersionFileError { .. } => ExitCode::FileSystemError,
             ErrorKind::ReadNpmManifestError => ExitCode::UnknownError,
             ErrorKind::ReadPackageConfigError { .. } => ExitCode::FileSystemError,
             ErrorKind::ReadPlatformError { .. } => ExitCode::FileSystemError,
+            ErrorKind::ReadProfileError { .. } => ExitCode::FileSystemError,
+            ErrorKind::ReadSettingsError { .. } => ExitCode::FileSystemError,
+            ErrorKind::ReadToolfileError { .. } => ExitCode::FileSystemError,
             #[cfg(windows)]
             ErrorKind::ReadUserPathError => ExitCode::EnvironmentError,
+            ErrorKind::ReadYarnrcError { .. } => ExitCode::FileSystemError,
             ErrorKind::RegistryFetchError { .. } => ExitCode::NetworkError,
+            ErrorKind::ReinstallPackageNotFound { .. } => ExitCode::InvalidArguments,
             ErrorKind::RunShimDirectly => ExitCode::InvalidArguments,
             ErrorKind::SetupToolImageError { .. } => ExitCode::FileSystemError,
             ErrorKind::ShimCreateError { .. } => ExitCode::FileSystemError,
@@ -1445,10 +1927,14 @@ impl ErrorKind {
             ErrorKind::StringifyBinConfigError => ExitCode::UnknownError,
             ErrorKind::StringifyPackageConfigError => ExitCode::UnknownError,
             ErrorKind::StringifyPlatformError => ExitCode::UnknownError,
+            ErrorKind::StringifyToolfileError => ExitCode::UnknownError,
             ErrorKind::Unimplemented { .. } => ExitCode::UnknownError,
+            ErrorKind::UninstallExactVersionRequired { .. } => ExitCode::InvalidArguments,
+            ErrorKind::UninstallToolInUse { .. } => ExitCode::ConfigurationError,
             ErrorKind::UnpackArchiveError { .. } => ExitCode::UnknownError,
             ErrorKind::UpgradePackageNotFound { .. } => ExitCode::ConfigurationError,
             ErrorKind::UpgradePackageWrongManager { .. } => ExitCode::ConfigurationError,
+            ErrorKind::UpgradeToolNotFound { .. } => ExitCode::InvalidArguments,
             ErrorKind::VersionParseError { .. } => ExitCode::NoVersionMatch,
             ErrorKind::WriteBinConfigError { .. } => ExitCode::FileSystemError,
             ErrorKind::WriteDefaultNpmError { .. } => ExitCode::FileSystemError,
@@ -1456,7 +1942,10 @@ impl ErrorKind {
             ErrorKind::WriteNodeIndexCacheError { .. } => ExitCode::FileSystemError,
             ErrorKind::WriteNodeIndexExpiryError { .. } => ExitCode::FileSystemError,
             ErrorKind::WritePackageConfigError { .. } => ExitCode::FileSystemError,
+            ErrorKind::WritePackageManifestError { .. } => ExitCode::FileSystemError,
             ErrorKind::WritePlatformError { .. } => ExitCode::FileSystemError,
+            ErrorKind::WriteProfileError { .. } => ExitCode::FileSystemError,
+            ErrorKind::WriteToolfileError { .. } => ExitCode::FileSystemError,
             #[cfg(windows)]
             ErrorKind::WriteUserPathError => ExitCode::EnvironmentError,
             ErrorKind::YarnLatestFetchError { .. } => ExitCode::NetworkError,
@@ -1,14 +1,28 @@
 //! Events for the sessions in executables and shims and everything
 
 use std::env;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::io::Write;
+use std::process::Stdio;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+use attohttpc::Response;
+use log::debug;
 use serde::Serialize;
 
+use crate::command::create_command;
 use crate::error::{ExitCode, VoltaError};
 use crate::hook::Publish;
+use crate::layout::volta_install;
 use crate::monitor::Monitor;
 use crate::session::ActivityKind;
+use crate::tool::is_offline;
+
+/// The maximum time to spend sending events to a `publish.url` hook
+const PUBLISH_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Environment variable set for the process that sends the events of another Volta process, so
+/// that it doesn't publish its own events in turn
+const PUBLISHER_ENV_VAR: &str = "_VOLTA_EVENT_PUBLISHER";
 
 // the Event data that is serialized to JSON and sent the plugin
 #[derive(Serialize)]
@@ -129,10 +143,13 @@ impl EventLog {
     }
 
     pub fn publish(&self, plugin: Option<&Publish>) {
+        if env::var_os(PUBLISHER_ENV_VAR).is_some() {
+            return;
+        }
+
         match plugin {
-            // Note: This call to unimplemented is left in, as it's not a Fallible operation that can use ErrorKind::Unimplemented
-            Some(&Publish::Url(_)) => unimplemented!(),
-            Some(&Publish::Bin(ref command)) => {
+            Some(Publish::Url(url)) => spawn_publisher(url, &self.events),
+            Some(Publish::Bin(command)) => {
                 let mut monitor = Monitor::new(command);
                 monitor.send_events(&self.events);
             }
@@ -141,6 +158,79 @@ impl EventLog {
     }
 }
 
+/// Hand the events to a `volta publish-events` process, which sends them to the given URL
+///
+/// The process is left running without waiting for it, so that the request can't hold up the
+/// tool, and any failure is only reported in the debug output.
+fn spawn_publisher(url: &str, events: &[Event]) {
+    if is_offline() {
+        debug!("Skipping publishing events to '{}' in offline mode", url);
+        return;
+    }
+
+    let data = match serde_json::to_string(events) {
+        Ok(data) => data,
+        Err(error) => {
+            debug!("There was a problem serializing the JSON data: {:?}", error);
+            return;
+        }
+    };
+
+    let executable = match volta_install() {
+        Ok(install) => install.main_executable(),
+        Err(error) => {
+            debug!("Unable to locate Volta to publish events\n{}", error);
+            return;
+        }
+    };
+
+    let mut command = create_command(executable);
+    command
+        .arg("publish-events")
+        .arg(url)
+        .env(PUBLISHER_ENV_VAR, "1")
+        .stdin(Stdio::piped());
+
+    #[cfg(not(debug_assertions))]
+    // Hide stdout and stderr of spawned process in release mode
+    command.stdout(Stdio::null()).stderr(Stdio::null());
+
+    match command.spawn() {
+        Ok(mut child) => {
+            if let Some(stdin) = child.stdin.as_mut() {
+                if let Err(error) = stdin.write_all(data.as_bytes()) {
+                    debug!("Unable to send events to the publisher\n{}", error);
+                }
+            }
+        }
+        Err(error) => debug!("Unable to start publishing events to '{}'\n{}", url, error),
+    }
+}
+
+/// POST the events, already serialized as JSON, to the given URL
+///
+/// The request is limited by a short timeout so that an unresponsive server can't keep the
+/// publisher running, and any failure is only reported in the debug output.
+pub fn send_events(url: &str, events: &str) {
+    let events: serde_json::Value = match serde_json::from_str(events) {
+        Ok(events) => events,
+        Err(error) => {
+            debug!("Unable to parse the events to publish\n{}", error);
+            return;
+        }
+    };
+
+    let result = attohttpc::post(url)
+        .timeout(PUBLISH_TIMEOUT)
+        .json(&events)
+        .and_then(|request| request.send())
+        .and_then(Response::error_for_status);
+
+    if let Err(error) = result {
+        debug!("Unable to publish events to '{}'\n{}", url, error);
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
 
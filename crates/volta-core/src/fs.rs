@@ -1,7 +1,7 @@
 //! Provides utilities for operating on the filesystem.
 
 use std::fs::{self, create_dir_all, read_dir, DirEntry, File, Metadata};
-use std::io;
+use std::io::{self, Write};
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
 use std::path::Path;
@@ -10,6 +10,7 @@ use crate::error::{Context, ErrorKind, Fallible};
 use crate::layout::volta_home;
 use retry::delay::Fibonacci;
 use retry::{retry, Error as RetryError, OperationResult};
+use sha2::Digest;
 use tempfile::{tempdir_in, NamedTempFile, TempDir};
 
 /// Opens a file, creating it if it doesn't exist
@@ -53,6 +54,14 @@ pub fn ok_if_not_found<T: Default>(err: io::Error) -> io::Result<T> {
     }
 }
 
+/// Calculates the digest of a file's contents using the given hash algorithm
+pub fn hash_file<D: Digest + Write>(file: &Path) -> io::Result<Vec<u8>> {
+    let mut file = File::open(file)?;
+    let mut hasher = D::new();
+    io::copy(&mut file, &mut hasher)?;
+    Ok(hasher.result().to_vec())
+}
+
 /// Reads a file, if it exists.
 pub fn read_file<P: AsRef<Path>>(path: P) -> io::Result<Option<String>> {
     let result: io::Result<String> = fs::read_to_string(path);
@@ -9,7 +9,7 @@ use std::path::Path;
 use crate::error::{Context, ErrorKind, Fallible};
 use crate::layout::volta_home;
 use crate::project::Project;
-use crate::tool::{Node, Npm, Tool, Yarn};
+use crate::tool::{Node, Npm, Pnpm, Tool, Yarn};
 use lazycell::LazyCell;
 use log::debug;
 
@@ -50,6 +50,7 @@ impl LazyHookConfig {
 pub struct HookConfig {
     node: Option<ToolHooks<Node>>,
     npm: Option<ToolHooks<Npm>>,
+    pnpm: Option<ToolHooks<Pnpm>>,
     yarn: Option<ToolHooks<Yarn>>,
     events: Option<EventHooks>,
 }
@@ -97,6 +98,10 @@ impl HookConfig {
         self.npm.as_ref()
     }
 
+    pub fn pnpm(&self) -> Option<&ToolHooks<Pnpm>> {
+        self.pnpm.as_ref()
+    }
+
     pub fn yarn(&self) -> Option<&ToolHooks<Yarn>> {
         self.yarn.as_ref()
     }
@@ -107,7 +112,7 @@ impl HookConfig {
 
     /// Returns the current hooks, which are a merge between the user hooks and
     /// the project hooks (if any).
-    fn current(project: Option<&Project>) -> Fallible<Self> {
+    pub(crate) fn current(project: Option<&Project>) -> Fallible<Self> {
         let default_hooks_file = volta_home()?.default_hooks_file();
 
         // Since `from_paths` expects the paths to be sorted in descending precedence order, we
@@ -161,6 +166,7 @@ impl HookConfig {
                     Self {
                         node: None,
                         npm: None,
+                        pnpm: None,
                         yarn: None,
                         events: None,
                     }
@@ -193,6 +199,7 @@ impl HookConfig {
         Self {
             node: merge_hooks!(self, other, node),
             npm: merge_hooks!(self, other, npm),
+            pnpm: merge_hooks!(self, other, pnpm),
             yarn: merge_hooks!(self, other, yarn),
             events: merge_hooks!(self, other, events),
         }
@@ -245,6 +252,7 @@ pub mod tests {
         let bin_file = fixture_dir.join("bins.json");
         let hooks = HookConfig::from_file(&bin_file).unwrap().unwrap();
         let node = hooks.node.unwrap();
+        let pnpm = hooks.pnpm.unwrap();
         let yarn = hooks.yarn.unwrap();
 
         assert_eq!(
@@ -268,6 +276,21 @@ pub mod tests {
                 base_path: fixture_dir.clone(),
             })
         );
+        assert_eq!(
+            pnpm.distro,
+            Some(tool::DistroHook::Bin {
+                bin: "/bin/to/pnpm/distro".to_string(),
+                base_path: fixture_dir.clone(),
+            })
+        );
+        assert_eq!(pnpm.latest, None);
+        assert_eq!(
+            pnpm.index,
+            Some(tool::MetadataHook::Bin {
+                bin: "/bin/to/pnpm/index".to_string(),
+                base_path: fixture_dir.clone(),
+            })
+        );
         assert_eq!(
             yarn.distro,
             Some(tool::DistroHook::Bin {
@@ -8,6 +8,7 @@ use std::path::Path;
 use crate::error::{Context, ErrorKind, Fallible};
 use crate::fs::read_dir_eager;
 use crate::layout::volta_home;
+use crate::profile::{active_profile, profiles};
 use crate::tool::PackageConfig;
 use crate::version::parse_version;
 use log::debug;
@@ -38,6 +39,23 @@ pub fn npm_versions() -> Fallible<BTreeSet<Version>> {
     volta_home().and_then(|home| read_versions(home.npm_image_root_dir()))
 }
 
+/// Checks if a given pnpm version image is available on the local machine
+pub fn pnpm_available(version: &Version) -> Fallible<bool> {
+    volta_home().map(|home| home.pnpm_image_dir(&version.to_string()).exists())
+}
+
+/// Collects a set of all pnpm versions fetched on the local machine
+pub fn pnpm_versions() -> Fallible<BTreeSet<Version>> {
+    let dir = volta_home()?.pnpm_image_root_dir();
+
+    // Note: Volta directories created before pnpm support won't have a pnpm image directory
+    if dir.exists() {
+        read_versions(dir)
+    } else {
+        Ok(BTreeSet::new())
+    }
+}
+
 /// Checks if a given Yarn version image is available on the local machine
 pub fn yarn_available(version: &Version) -> Fallible<bool> {
     volta_home().map(|home| home.yarn_image_dir(&version.to_string()).exists())
@@ -48,11 +66,24 @@ pub fn yarn_versions() -> Fallible<BTreeSet<Version>> {
     volta_home().and_then(|home| read_versions(home.yarn_image_root_dir()))
 }
 
-/// Collects a set of all Package Configs on the local machine
+/// Collects a set of all Package Configs in the active profile
 pub fn package_configs() -> Fallible<BTreeSet<PackageConfig>> {
-    let package_dir = volta_home()?.default_package_dir();
+    read_package_configs(active_profile()?.package_dir())
+}
+
+/// Collects the Package Configs of every profile, which share the package images
+pub(crate) fn all_package_configs() -> Fallible<Vec<PackageConfig>> {
+    let mut configs = Vec::new();
+    for profile in profiles()? {
+        configs.extend(read_package_configs(profile.package_dir())?);
+    }
+
+    Ok(configs)
+}
 
-    WalkDir::new(&package_dir)
+/// Collects a set of the Package Configs in a package config directory
+pub(crate) fn read_package_configs(package_dir: &Path) -> Fallible<BTreeSet<PackageConfig>> {
+    WalkDir::new(package_dir)
         .max_depth(2)
         .into_iter()
         // Ignore any items which didn't resolve as `DirEntry` correctly.
@@ -2,7 +2,7 @@
 
 mod command;
 pub mod error;
-mod event;
+pub mod event;
 pub mod fs;
 mod hook;
 pub mod inventory;
@@ -10,13 +10,17 @@ pub mod layout;
 pub mod log;
 pub mod monitor;
 pub mod platform;
+pub mod profile;
 pub mod project;
 pub mod run;
 pub mod session;
+pub mod settings;
 pub mod shim;
 pub mod signal;
 pub mod style;
 pub mod sync;
 pub mod tool;
 pub mod toolchain;
+pub mod toolfile;
+pub mod usage;
 pub mod version;
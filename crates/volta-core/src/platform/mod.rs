@@ -1,8 +1,11 @@
 use std::fmt;
 
 use crate::error::{ErrorKind, Fallible};
+use crate::project::PackageManagerKind;
 use crate::session::Session;
-use crate::tool::{Node, Npm, Yarn};
+use crate::tool::{Node, Npm, Pnpm, Yarn};
+use crate::usage::record_use;
+use log::debug;
 use semver::Version;
 
 mod image;
@@ -25,6 +28,9 @@ pub enum Source {
     /// Represents a version from a project manifest
     Project,
 
+    /// Represents a Node version from a project's `.nvmrc` or `.node-version` file
+    VersionFile,
+
     /// Represents a version from a pinned Binary platform
     Binary,
 
@@ -37,6 +43,7 @@ impl fmt::Display for Source {
         match self {
             Source::Default => write!(f, "default"),
             Source::Project => write!(f, "project"),
+            Source::VersionFile => write!(f, "version file"),
             Source::Binary => write!(f, "binary"),
             Source::CommandLine => write!(f, "command-line"),
         }
@@ -149,6 +156,15 @@ impl<T> From<InheritOption<T>> for Option<T> {
     }
 }
 
+impl<T> From<Option<T>> for InheritOption<T> {
+    fn from(value: Option<T>) -> InheritOption<T> {
+        match value {
+            Some(value) => InheritOption::Some(value),
+            None => InheritOption::None,
+        }
+    }
+}
+
 impl<T> Default for InheritOption<T> {
     fn default() -> Self {
         InheritOption::Inherit
@@ -161,6 +177,7 @@ impl<T> Default for InheritOption<T> {
 pub struct PlatformSpec {
     pub node: Version,
     pub npm: Option<Version>,
+    pub pnpm: Option<Version>,
     pub yarn: Option<Version>,
 }
 
@@ -170,6 +187,7 @@ impl PlatformSpec {
         Platform {
             node: Sourced::with_default(self.node.clone()),
             npm: self.npm.clone().map(Sourced::with_default),
+            pnpm: self.pnpm.clone().map(Sourced::with_default),
             yarn: self.yarn.clone().map(Sourced::with_default),
         }
     }
@@ -179,6 +197,7 @@ impl PlatformSpec {
         Platform {
             node: Sourced::with_project(self.node.clone()),
             npm: self.npm.clone().map(Sourced::with_project),
+            pnpm: self.pnpm.clone().map(Sourced::with_project),
             yarn: self.yarn.clone().map(Sourced::with_project),
         }
     }
@@ -188,6 +207,7 @@ impl PlatformSpec {
         Platform {
             node: Sourced::with_binary(self.node.clone()),
             npm: self.npm.clone().map(Sourced::with_binary),
+            pnpm: self.pnpm.clone().map(Sourced::with_binary),
             yarn: self.yarn.clone().map(Sourced::with_binary),
         }
     }
@@ -198,6 +218,7 @@ impl PlatformSpec {
 pub struct CliPlatform {
     pub node: Option<Version>,
     pub npm: InheritOption<Version>,
+    pub pnpm: InheritOption<Version>,
     pub yarn: InheritOption<Version>,
 }
 
@@ -207,6 +228,7 @@ impl CliPlatform {
         Platform {
             node: self.node.map_or(base.node, Sourced::with_command_line),
             npm: self.npm.map(Sourced::with_command_line).inherit(base.npm),
+            pnpm: self.pnpm.map(Sourced::with_command_line).inherit(base.pnpm),
             yarn: self.yarn.map(Sourced::with_command_line).inherit(base.yarn),
         }
     }
@@ -220,6 +242,7 @@ impl From<CliPlatform> for Option<Platform> {
             Some(node) => Some(Platform {
                 node: Sourced::with_command_line(node),
                 npm: base.npm.map(Sourced::with_command_line).into(),
+                pnpm: base.pnpm.map(Sourced::with_command_line).into(),
                 yarn: base.yarn.map(Sourced::with_command_line).into(),
             }),
         }
@@ -231,6 +254,7 @@ impl From<CliPlatform> for Option<Platform> {
 pub struct Platform {
     pub node: Sourced<Version>,
     pub npm: Option<Sourced<Version>>,
+    pub pnpm: Option<Sourced<Version>>,
     pub yarn: Option<Sourced<Version>>,
 }
 
@@ -239,12 +263,29 @@ impl Platform {
     ///
     /// Active platform is determined by first looking at the Project Platform
     ///
-    /// - If it exists and has a Yarn version, then we use the project platform
-    /// - If it exists but doesn't have a Yarn version, then we merge the two,
-    ///   pulling Yarn from the user default platform, if available
-    /// - If there is no Project platform, then we use the user Default Platform
+    /// - If it exists and has pnpm and Yarn versions, then we use the project platform
+    /// - If it exists but doesn't have a pnpm or Yarn version, then we merge the two,
+    ///   pulling pnpm and Yarn from the user default platform, if available
+    /// - If there is no Project platform, then we use the user Default Platform, with the package
+    ///   manager from the project's `packageManager` field, if any
+    ///
+    /// If the project Node version came from a `.nvmrc` or `.node-version` file rather than the
+    /// manifest, it is marked with that source instead.
     pub fn current(session: &mut Session) -> Fallible<Option<Self>> {
         if let Some(mut platform) = session.project_platform()?.map(PlatformSpec::as_project) {
+            if let Some(project) = session.project()? {
+                if project.node_version_file().is_some() {
+                    platform.node.source = Source::VersionFile;
+                }
+            }
+
+            if platform.pnpm.is_none() {
+                platform.pnpm = session
+                    .default_platform()?
+                    .and_then(|default_platform| default_platform.pnpm.clone())
+                    .map(Sourced::with_default);
+            }
+
             if platform.yarn.is_none() {
                 platform.yarn = session
                     .default_platform()?
@@ -254,7 +295,27 @@ impl Platform {
 
             Ok(Some(platform))
         } else {
-            Ok(session.default_platform()?.map(PlatformSpec::as_default))
+            let mut platform = session.default_platform()?.map(PlatformSpec::as_default);
+
+            // Without a Node version pinned in the project, the package manager from the
+            // `packageManager` field still takes precedence over the default one
+            if let (Some(platform), Some(project)) = (&mut platform, session.project()?) {
+                if let Some(manager) = project.package_manager() {
+                    let pinned = match manager.kind {
+                        PackageManagerKind::Npm => &mut platform.npm,
+                        PackageManagerKind::Pnpm => &mut platform.pnpm,
+                        PackageManagerKind::Yarn => &mut platform.yarn,
+                    };
+
+                    debug!(
+                        "Using {}@{} from the packageManager field",
+                        manager.kind, manager.version
+                    );
+                    *pinned = Some(Sourced::with_project(manager.version.clone()));
+                }
+            }
+
+            Ok(platform)
         }
     }
 
@@ -268,13 +329,30 @@ impl Platform {
             Npm::new(version.clone()).ensure_fetched(session)?;
         }
 
+        if let Some(Sourced { value: version, .. }) = &self.pnpm {
+            Pnpm::new(version.clone()).ensure_fetched(session)?;
+        }
+
         if let Some(Sourced { value: version, .. }) = &self.yarn {
             Yarn::new(version.clone()).ensure_fetched(session)?;
         }
 
+        record_use("node", &self.node.value);
+        let package_managers = [
+            ("npm", &self.npm),
+            ("pnpm", &self.pnpm),
+            ("yarn", &self.yarn),
+        ];
+        for &(tool, version) in package_managers.iter() {
+            if let Some(Sourced { value: version, .. }) = version {
+                record_use(tool, version);
+            }
+        }
+
         Ok(Image {
             node: self.node,
             npm: self.npm,
+            pnpm: self.pnpm,
             yarn: self.yarn,
         })
     }
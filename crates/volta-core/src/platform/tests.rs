@@ -28,16 +28,21 @@ fn test_image_path() {
     let npm_bin = volta_home().unwrap().npm_image_bin_dir("6.4.3");
     let expected_npm_bin = npm_bin.to_str().unwrap();
 
+    let pnpm_bin = volta_home().unwrap().pnpm_image_bin_dir("8.2.1");
+    let expected_pnpm_bin = pnpm_bin.to_str().unwrap();
+
     let yarn_bin = volta_home().unwrap().yarn_image_bin_dir("4.5.7");
     let expected_yarn_bin = yarn_bin.to_str().unwrap();
 
     let v123 = Version::parse("1.2.3").unwrap();
     let v457 = Version::parse("4.5.7").unwrap();
     let v643 = Version::parse("6.4.3").unwrap();
+    let v821 = Version::parse("8.2.1").unwrap();
 
     let only_node = Image {
         node: Sourced::with_default(v123.clone()),
         npm: None,
+        pnpm: None,
         yarn: None,
     };
 
@@ -49,6 +54,7 @@ fn test_image_path() {
     let node_npm = Image {
         node: Sourced::with_default(v123.clone()),
         npm: Some(Sourced::with_default(v643.clone())),
+        pnpm: None,
         yarn: None,
     };
 
@@ -63,6 +69,7 @@ fn test_image_path() {
     let node_yarn = Image {
         node: Sourced::with_default(v123.clone()),
         npm: None,
+        pnpm: None,
         yarn: Some(Sourced::with_default(v457.clone())),
     };
 
@@ -74,9 +81,25 @@ fn test_image_path() {
         )
     );
 
+    let node_pnpm = Image {
+        node: Sourced::with_default(v123.clone()),
+        npm: None,
+        pnpm: Some(Sourced::with_default(v821)),
+        yarn: None,
+    };
+
+    assert_eq!(
+        node_pnpm.path().unwrap().into_string().unwrap(),
+        format!(
+            "{}:{}:{}",
+            expected_pnpm_bin, expected_node_bin, starting_path
+        )
+    );
+
     let node_npm_yarn = Image {
         node: Sourced::with_default(v123),
         npm: Some(Sourced::with_default(v643)),
+        pnpm: None,
         yarn: Some(Sourced::with_default(v457)),
     };
 
@@ -111,16 +134,21 @@ fn test_image_path() {
     let npm_bin = volta_home().unwrap().npm_image_bin_dir("6.4.3");
     let expected_npm_bin = npm_bin.to_str().unwrap();
 
+    let pnpm_bin = volta_home().unwrap().pnpm_image_bin_dir("8.2.1");
+    let expected_pnpm_bin = pnpm_bin.to_str().unwrap();
+
     let yarn_bin = volta_home().unwrap().yarn_image_bin_dir("4.5.7");
     let expected_yarn_bin = yarn_bin.to_str().unwrap();
 
     let v123 = Version::parse("1.2.3").unwrap();
     let v457 = Version::parse("4.5.7").unwrap();
     let v643 = Version::parse("6.4.3").unwrap();
+    let v821 = Version::parse("8.2.1").unwrap();
 
     let only_node = Image {
         node: Sourced::with_default(v123.clone()),
         npm: None,
+        pnpm: None,
         yarn: None,
     };
 
@@ -132,6 +160,7 @@ fn test_image_path() {
     let node_npm = Image {
         node: Sourced::with_default(v123.clone()),
         npm: Some(Sourced::with_default(v643.clone())),
+        pnpm: None,
         yarn: None,
     };
 
@@ -146,6 +175,7 @@ fn test_image_path() {
     let node_yarn = Image {
         node: Sourced::with_default(v123.clone()),
         npm: None,
+        pnpm: None,
         yarn: Some(Sourced::with_default(v457.clone())),
     };
 
@@ -157,9 +187,25 @@ fn test_image_path() {
         )
     );
 
+    let node_pnpm = Image {
+        node: Sourced::with_default(v123.clone()),
+        npm: None,
+        pnpm: Some(Sourced::with_default(v821)),
+        yarn: None,
+    };
+
+    assert_eq!(
+        node_pnpm.path().unwrap().into_string().unwrap(),
+        format!(
+            "{};{};{}",
+            expected_pnpm_bin, expected_node_bin, path_with_shims
+        )
+    );
+
     let node_npm_yarn = Image {
         node: Sourced::with_default(v123),
         npm: Some(Sourced::with_default(v643)),
+        pnpm: None,
         yarn: Some(Sourced::with_default(v457)),
     };
 
@@ -273,6 +319,7 @@ mod cli_platform {
     lazy_static! {
         static ref NODE_VERSION: Version = Version::from((12, 14, 1));
         static ref NPM_VERSION: Version = Version::from((6, 13, 2));
+        static ref PNPM_VERSION: Version = Version::from((8, 6, 0));
         static ref YARN_VERSION: Version = Version::from((1, 17, 0));
     }
 
@@ -285,12 +332,14 @@ mod cli_platform {
             let test = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::default(),
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::default(),
             };
 
             let base = Platform {
                 node: Sourced::with_default(Version::from((10, 10, 10))),
                 npm: None,
+                pnpm: None,
                 yarn: None,
             };
 
@@ -305,12 +354,14 @@ mod cli_platform {
             let test = CliPlatform {
                 node: None,
                 npm: InheritOption::default(),
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::default(),
             };
 
             let base = Platform {
                 node: Sourced::with_default(NODE_VERSION.clone()),
                 npm: None,
+                pnpm: None,
                 yarn: None,
             };
 
@@ -325,12 +376,14 @@ mod cli_platform {
             let test = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::Some(NPM_VERSION.clone()),
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::default(),
             };
 
             let base = Platform {
                 node: Sourced::with_default(Version::from((10, 10, 10))),
                 npm: Some(Sourced::with_default(Version::from((5, 6, 3)))),
+                pnpm: None,
                 yarn: None,
             };
 
@@ -346,12 +399,14 @@ mod cli_platform {
             let test = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::Inherit,
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::default(),
             };
 
             let base = Platform {
                 node: Sourced::with_default(Version::from((10, 10, 10))),
                 npm: Some(Sourced::with_default(NPM_VERSION.clone())),
+                pnpm: None,
                 yarn: None,
             };
 
@@ -367,12 +422,14 @@ mod cli_platform {
             let test = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::None,
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::default(),
             };
 
             let base = Platform {
                 node: Sourced::with_default(Version::from((10, 10, 10))),
                 npm: Some(Sourced::with_default(NPM_VERSION.clone())),
+                pnpm: None,
                 yarn: None,
             };
 
@@ -381,17 +438,65 @@ mod cli_platform {
             assert!(merged.npm.is_none());
         }
 
+        #[test]
+        fn uses_pnpm() {
+            let test = CliPlatform {
+                node: Some(NODE_VERSION.clone()),
+                npm: InheritOption::default(),
+                pnpm: InheritOption::Some(PNPM_VERSION.clone()),
+                yarn: InheritOption::default(),
+            };
+
+            let base = Platform {
+                node: Sourced::with_default(Version::from((10, 10, 10))),
+                npm: None,
+                pnpm: Some(Sourced::with_default(Version::from((7, 33, 7)))),
+                yarn: None,
+            };
+
+            let merged = test.merge(base);
+
+            let merged_pnpm = merged.pnpm.unwrap();
+            assert_eq!(merged_pnpm.value, PNPM_VERSION.clone());
+            assert_eq!(merged_pnpm.source, Source::CommandLine);
+        }
+
+        #[test]
+        fn inherits_pnpm() {
+            let test = CliPlatform {
+                node: Some(NODE_VERSION.clone()),
+                npm: InheritOption::default(),
+                pnpm: InheritOption::Inherit,
+                yarn: InheritOption::default(),
+            };
+
+            let base = Platform {
+                node: Sourced::with_default(Version::from((10, 10, 10))),
+                npm: None,
+                pnpm: Some(Sourced::with_default(PNPM_VERSION.clone())),
+                yarn: None,
+            };
+
+            let merged = test.merge(base);
+
+            let merged_pnpm = merged.pnpm.unwrap();
+            assert_eq!(merged_pnpm.value, PNPM_VERSION.clone());
+            assert_eq!(merged_pnpm.source, Source::Default);
+        }
+
         #[test]
         fn uses_yarn() {
             let test = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::default(),
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::Some(YARN_VERSION.clone()),
             };
 
             let base = Platform {
                 node: Sourced::with_default(Version::from((10, 10, 10))),
                 npm: None,
+                pnpm: None,
                 yarn: Some(Sourced::with_default(Version::from((1, 10, 3)))),
             };
 
@@ -407,12 +512,14 @@ mod cli_platform {
             let test = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::default(),
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::Inherit,
             };
 
             let base = Platform {
                 node: Sourced::with_default(Version::from((10, 10, 10))),
                 npm: None,
+                pnpm: None,
                 yarn: Some(Sourced::with_default(YARN_VERSION.clone())),
             };
 
@@ -428,12 +535,14 @@ mod cli_platform {
             let test = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::default(),
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::None,
             };
 
             let base = Platform {
                 node: Sourced::with_default(Version::from((10, 10, 10))),
                 npm: None,
+                pnpm: None,
                 yarn: Some(Sourced::with_default(YARN_VERSION.clone())),
             };
 
@@ -452,6 +561,7 @@ mod cli_platform {
             let cli = CliPlatform {
                 node: None,
                 npm: InheritOption::default(),
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::default(),
             };
 
@@ -465,6 +575,7 @@ mod cli_platform {
             let cli = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::default(),
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::default(),
             };
 
@@ -480,6 +591,7 @@ mod cli_platform {
             let cli = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::Some(NPM_VERSION.clone()),
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::default(),
             };
 
@@ -495,6 +607,7 @@ mod cli_platform {
             let cli = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::None,
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::default(),
             };
 
@@ -508,6 +621,7 @@ mod cli_platform {
             let cli = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::Inherit,
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::default(),
             };
 
@@ -521,6 +635,7 @@ mod cli_platform {
             let cli = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::default(),
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::Some(YARN_VERSION.clone()),
             };
 
@@ -536,6 +651,7 @@ mod cli_platform {
             let cli = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::default(),
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::None,
             };
 
@@ -549,6 +665,7 @@ mod cli_platform {
             let cli = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::default(),
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::Inherit,
             };
 
@@ -0,0 +1,325 @@
+//! Provides profiles, which each hold their own default platform and global packages, so that
+//! several default toolchains can be switched between
+
+use std::collections::BTreeSet;
+use std::convert::TryFrom;
+use std::fs::{create_dir_all, read_to_string, write};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::error::{Context, ErrorKind, Fallible};
+use crate::fs::{read_dir_eager, read_file, remove_dir_if_exists};
+use crate::inventory::read_package_configs;
+use crate::layout::volta_home;
+use crate::platform::PlatformSpec;
+use crate::shim;
+use crate::style::success_prefix;
+use crate::sync::VoltaLock;
+use crate::tool::package::{
+    link_package_to_shared_dir, remove_image_if_unused, remove_shared_link_dir, PackageConfig,
+};
+use crate::toolchain::serial;
+use double_checked_cell::DoubleCheckedCell;
+use fs_utils::ensure_containing_dir_exists;
+use lazy_static::lazy_static;
+use log::{debug, info};
+
+/// The profile that is used until another is selected, which keeps its default platform and
+/// global packages directly in `tools/user`
+pub const DEFAULT_PROFILE: &str = "default";
+
+lazy_static! {
+    static ref ACTIVE_PROFILE: DoubleCheckedCell<Profile> = DoubleCheckedCell::new();
+}
+
+/// A default toolchain, made up of a default platform and the global packages installed with it
+pub struct Profile {
+    name: String,
+    platform_file: PathBuf,
+    package_dir: PathBuf,
+    bin_dir: PathBuf,
+}
+
+impl Profile {
+    fn named(name: &str) -> Fallible<Self> {
+        let home = volta_home()?;
+
+        Ok(if name == DEFAULT_PROFILE {
+            Profile {
+                name: name.into(),
+                platform_file: home.default_platform_file().to_owned(),
+                package_dir: home.default_package_dir().to_owned(),
+                bin_dir: home.default_bin_dir().to_owned(),
+            }
+        } else {
+            Profile {
+                name: name.into(),
+                platform_file: home.profile_platform_file(name),
+                package_dir: home.profile_package_dir(name),
+                bin_dir: home.profile_bin_dir(name),
+            }
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The file holding the default platform of this profile
+    pub fn platform_file(&self) -> &Path {
+        &self.platform_file
+    }
+
+    /// The directory holding the configs of the global packages in this profile
+    pub fn package_dir(&self) -> &Path {
+        &self.package_dir
+    }
+
+    /// The directory holding the configs of the bins of the global packages in this profile
+    pub fn bin_dir(&self) -> &Path {
+        &self.bin_dir
+    }
+
+    /// Reads the default platform of this profile, if it has one
+    pub fn platform(&self) -> Fallible<Option<PlatformSpec>> {
+        let src = read_file(&self.platform_file).with_context(|| ErrorKind::ReadPlatformError {
+            file: self.platform_file.clone(),
+        })?;
+
+        match src {
+            Some(src) => Ok(serial::Platform::try_from(src)?.into()),
+            None => Ok(None),
+        }
+    }
+
+    pub fn package_config_file(&self, package_name: &str) -> PathBuf {
+        self.package_dir.join(format!("{}.json", package_name))
+    }
+
+    pub fn bin_config_file(&self, bin_name: &str) -> PathBuf {
+        self.bin_dir.join(format!("{}.json", bin_name))
+    }
+}
+
+/// The profile in use, whose default platform and global packages are used by the shims
+pub fn active_profile<'a>() -> Fallible<&'a Profile> {
+    ACTIVE_PROFILE.get_or_try_init(|| {
+        let file = volta_home()?.active_profile_file();
+        let name = match read_to_string(file) {
+            Ok(name) => name.trim().to_string(),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => DEFAULT_PROFILE.into(),
+            Err(error) => {
+                return Err(error).with_context(|| ErrorKind::ReadProfileError {
+                    file: file.to_owned(),
+                })
+            }
+        };
+
+        // Note: If the profile has since been removed by hand, fall back to the default profile
+        if exists(&name)? {
+            Profile::named(&name)
+        } else {
+            debug!("Profile '{}' not found, using the default profile", name);
+            Profile::named(DEFAULT_PROFILE)
+        }
+    })
+}
+
+/// Collects every profile, starting with the default profile
+pub fn profiles() -> Fallible<Vec<Profile>> {
+    let profile_root = volta_home()?.profile_root_dir();
+    let mut names = vec![DEFAULT_PROFILE.to_string()];
+
+    if profile_root.exists() {
+        let entries = read_dir_eager(profile_root).with_context(|| ErrorKind::ReadDirError {
+            dir: profile_root.to_owned(),
+        })?;
+        let mut named: Vec<String> = entries
+            .filter(|(_, metadata)| metadata.is_dir())
+            .map(|(entry, _)| entry.file_name().to_string_lossy().into_owned())
+            .collect();
+        named.sort();
+        names.extend(named);
+    }
+
+    names.iter().map(|name| Profile::named(name)).collect()
+}
+
+/// Lists the profiles, marking the one in use
+pub fn list() -> Fallible<()> {
+    let active = active_profile()?;
+
+    for profile in profiles()? {
+        if profile.name == active.name {
+            info!("{} (active)", profile.name);
+        } else {
+            info!("{}", profile.name);
+        }
+    }
+
+    Ok(())
+}
+
+/// Creates a new profile, without a default platform or any global packages
+pub fn create(name: &str) -> Fallible<()> {
+    validate_name(name)?;
+    // Acquire a lock on the Volta directory, if possible, to prevent concurrent changes
+    let _lock = VoltaLock::acquire();
+
+    if exists(name)? {
+        return Err(ErrorKind::ProfileAlreadyExists { name: name.into() }.into());
+    }
+
+    let profile = Profile::named(name)?;
+    for dir in &[&profile.package_dir, &profile.bin_dir] {
+        create_dir_all(dir).with_context(|| ErrorKind::CreateDirError {
+            dir: dir.to_path_buf(),
+        })?;
+    }
+
+    info!("{} created profile '{}'", success_prefix(), name);
+    Ok(())
+}
+
+/// Switches to another profile
+///
+/// The shims for the bins of the previous profile are replaced with the shims for the bins of the
+/// new one. The links in the shared lib directory to the packages of the previous profile are
+/// removed, and it is linked to the packages of the new profile instead, so that they can
+/// `require` each other.
+pub fn use_profile(name: &str) -> Fallible<()> {
+    let profile = find(name)?;
+    // Acquire a lock on the Volta directory, if possible, to prevent concurrent changes
+    let _lock = VoltaLock::acquire();
+
+    let previous = active_profile()?;
+    if previous.name == profile.name {
+        info!("Already using profile '{}'", name);
+        return Ok(());
+    }
+
+    let previous_configs = read_package_configs(previous.package_dir())?;
+    let configs = read_package_configs(profile.package_dir())?;
+
+    // Note: The active profile is recorded before the shims and links are changed. If changing
+    // them fails, the previous profile is restored, so that switching again starts over rather
+    // than finding the new profile already active.
+    write_active_profile(name)?;
+    if let Err(error) = switch_packages(&previous_configs, &configs) {
+        write_active_profile(&previous.name)?;
+        return Err(error);
+    }
+
+    info!("{} using profile '{}'", success_prefix(), name);
+    Ok(())
+}
+
+/// Replace the shims and shared lib links for the packages of one profile with those of another
+fn switch_packages(
+    previous_configs: &BTreeSet<PackageConfig>,
+    configs: &BTreeSet<PackageConfig>,
+) -> Fallible<()> {
+    for bin_name in previous_configs.iter().flat_map(|config| &config.bins) {
+        if !configs.iter().any(|config| config.bins.contains(bin_name)) {
+            shim::delete(bin_name)?;
+        }
+    }
+
+    for config in previous_configs {
+        if config.alias.is_none() {
+            remove_shared_link_dir(&config.name)?;
+        }
+    }
+
+    for config in configs {
+        for bin_name in &config.bins {
+            shim::create(bin_name)?;
+        }
+        if config.alias.is_none() {
+            link_package_to_shared_dir(&config.name, &config.version, config.manager)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_active_profile(name: &str) -> Fallible<()> {
+    let file = volta_home()?.active_profile_file();
+    ensure_containing_dir_exists(&file).with_context(|| ErrorKind::ContainingDirError {
+        path: file.to_owned(),
+    })?;
+    write(file, name).with_context(|| ErrorKind::WriteProfileError {
+        file: file.to_owned(),
+    })
+}
+
+/// Deletes a profile, along with the images of its packages that no other profile uses
+pub fn delete(name: &str) -> Fallible<()> {
+    if name == DEFAULT_PROFILE {
+        return Err(ErrorKind::DeleteDefaultProfile.into());
+    }
+
+    let profile = find(name)?;
+    // Acquire a lock on the Volta directory, if possible, to prevent concurrent changes
+    let _lock = VoltaLock::acquire();
+
+    if active_profile()?.name == profile.name {
+        return Err(ErrorKind::DeleteActiveProfile { name: name.into() }.into());
+    }
+
+    let configs = read_package_configs(profile.package_dir())?;
+    remove_dir_if_exists(volta_home()?.profile_dir(name))?;
+    for config in configs {
+        remove_image_if_unused(&config.name, &config.version)?;
+    }
+
+    info!("{} deleted profile '{}'", success_prefix(), name);
+    Ok(())
+}
+
+/// Find an existing profile by name
+fn find(name: &str) -> Fallible<Profile> {
+    validate_name(name)?;
+
+    if exists(name)? {
+        Profile::named(name)
+    } else {
+        Err(ErrorKind::ProfileNotFound { name: name.into() }.into())
+    }
+}
+
+fn exists(name: &str) -> Fallible<bool> {
+    Ok(name == DEFAULT_PROFILE || volta_home()?.profile_dir(name).is_dir())
+}
+
+/// Validate that a profile name can be used as a directory name on every platform
+fn validate_name(name: &str) -> Fallible<()> {
+    let valid = !name.is_empty()
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.');
+
+    if valid {
+        Ok(())
+    } else {
+        Err(ErrorKind::InvalidProfileName { name: name.into() }.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::validate_name;
+
+    #[test]
+    fn test_validate_name() {
+        assert!(validate_name("work").is_ok());
+        assert!(validate_name("oss-2.x_node18").is_ok());
+
+        assert!(validate_name("").is_err());
+        assert!(validate_name(".hidden").is_err());
+        assert!(validate_name("../work").is_err());
+        assert!(validate_name("work/oss").is_err());
+        assert!(validate_name(r"work\oss").is_err());
+    }
+}
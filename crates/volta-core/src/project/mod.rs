@@ -1,6 +1,7 @@
 //! Provides the `Project` type, which represents a Node project tree in
 //! the filesystem.
 
+use std::collections::BTreeMap;
 use std::convert::{TryFrom, TryInto};
 use std::env;
 use std::ffi::OsStr;
@@ -8,20 +9,28 @@ use std::iter::once;
 use std::path::{Path, PathBuf};
 
 use lazycell::LazyCell;
+use log::debug;
 use semver::Version;
 
 use crate::error::{Context, ErrorKind, Fallible, VoltaError};
-use crate::layout::volta_home;
 use crate::platform::PlatformSpec;
+use crate::profile::active_profile;
 use crate::tool::BinConfig;
+use crate::version::VersionSpec;
 use chain_map::ChainMap;
 use indexmap::IndexSet;
 
+mod package_manager;
 mod serial;
 #[cfg(test)]
 mod tests;
+mod version_file;
+mod yarnrc;
 
-use serial::{update_manifest, Manifest, ManifestKey};
+pub use package_manager::{PackageManager, PackageManagerHash, PackageManagerKind};
+use serial::{update_manifest, update_manifest_package, Manifest, ManifestKey};
+use version_file::{find_version_file, read_version_file, resolve_version_file};
+use yarnrc::find_yarn_path;
 
 /// A lazily loaded Project
 pub struct LazyProject {
@@ -52,7 +61,11 @@ pub struct Project {
     manifest_file: PathBuf,
     workspace_manifests: IndexSet<PathBuf>,
     dependencies: ChainMap<String, String>,
-    platform: Option<PlatformSpec>,
+    partial_platform: Option<PartialPlatform>,
+    platform: LazyCell<Option<PlatformSpec>>,
+    packages: BTreeMap<String, Version>,
+    node_version_file: Option<(PathBuf, VersionSpec)>,
+    package_manager: Option<PackageManager>,
 }
 
 impl Project {
@@ -65,7 +78,7 @@ impl Project {
     /// Creates an optional Project instance from the specified directory
     ///
     /// Will search ancestors to find a `package.json` and use that as the root of the project
-    fn for_dir(base_dir: PathBuf) -> Fallible<Option<Self>> {
+    pub fn for_dir(base_dir: PathBuf) -> Fallible<Option<Self>> {
         match find_closest_root(base_dir) {
             Some(mut project) => {
                 project.push("package.json");
@@ -81,7 +94,9 @@ impl Project {
         let mut dependencies: ChainMap<String, String> = manifest.dependency_maps.collect();
         let mut workspace_manifests = IndexSet::new();
         let mut platform = manifest.platform;
+        let mut packages: BTreeMap<String, Version> = manifest.packages.into_iter().collect();
         let mut extends = manifest.extends;
+        let mut package_manager = manifest.package_manager;
 
         // Iterate the `volta.extends` chain, parsing each file in turn
         while let Some(path) = extends {
@@ -107,17 +122,100 @@ impl Project {
                 (None, None) => None,
             };
 
+            // Packages pinned closer to the project take precedence over extended manifests
+            for (name, version) in manifest.packages {
+                packages.entry(name).or_insert(version);
+            }
+
+            package_manager = package_manager.or(manifest.package_manager);
             extends = manifest.extends;
         }
 
-        let platform = platform.map(TryInto::try_into).transpose()?;
-
-        Ok(Project {
+        let mut project = Project {
             manifest_file,
             workspace_manifests,
             dependencies,
-            platform,
-        })
+            partial_platform: platform,
+            platform: LazyCell::new(),
+            packages,
+            node_version_file: None,
+            package_manager,
+        };
+
+        // If the manifests don't pin a Node version, fall back to a `.nvmrc` or `.node-version`
+        // file, if there is one. The version in it is only resolved once the platform is needed.
+        let pins_node =
+            matches!(&project.partial_platform, Some(partial) if partial.node.is_some());
+        if !pins_node {
+            project.node_version_file = project.read_node_version_file()?;
+        }
+
+        Ok(project)
+    }
+
+    /// Reads the Node version requirement from the closest version file in the project roots
+    fn read_node_version_file(&self) -> Fallible<Option<(PathBuf, VersionSpec)>> {
+        let file = match find_version_file(self.workspace_roots()) {
+            Some(file) => file,
+            None => return Ok(None),
+        };
+
+        match read_version_file(&file)? {
+            Some(matching) => Ok(Some((file, matching))),
+            None => {
+                debug!("No Node version found in version file '{}'", file.display());
+                Ok(None)
+            }
+        }
+    }
+
+    /// Resolves the project platform from the manifests and the version file, if any
+    fn resolve_platform(&self) -> Fallible<Option<PlatformSpec>> {
+        let node = match &self.node_version_file {
+            Some((file, matching)) => {
+                let version = resolve_version_file(matching.clone(), self)?;
+                debug!(
+                    "Using node@{} from version file '{}'",
+                    version,
+                    file.display()
+                );
+                Some(version)
+            }
+            None => None,
+        };
+
+        let partial = match (&self.partial_platform, node) {
+            (Some(partial), node) => Some(partial.clone().merge(PartialPlatform {
+                node,
+                ..PartialPlatform::default()
+            })),
+            (None, Some(node)) => Some(PartialPlatform {
+                node: Some(node),
+                ..PartialPlatform::default()
+            }),
+            (None, None) => None,
+        };
+
+        let mut platform: Option<PlatformSpec> = partial.map(TryInto::try_into).transpose()?;
+
+        // The `packageManager` field pins the package manager only if the `volta` key doesn't
+        if let (Some(platform), Some(manager)) = (&mut platform, &self.package_manager) {
+            let pinned = match manager.kind {
+                PackageManagerKind::Npm => &mut platform.npm,
+                PackageManagerKind::Pnpm => &mut platform.pnpm,
+                PackageManagerKind::Yarn => &mut platform.yarn,
+            };
+
+            if pinned.is_none() {
+                debug!(
+                    "Using {}@{} from the packageManager field",
+                    manager.kind, manager.version
+                );
+                *pinned = Some(manager.version.clone());
+            }
+        }
+
+        Ok(platform)
     }
 
     /// Returns a reference to the manifest file for the current project
@@ -133,9 +231,63 @@ impl Project {
             .map(|file| file.parent().expect("File paths always have a parent"))
     }
 
+    /// Returns the Yarn release vendored with `yarnPath` in the project's `.yarnrc.yml`, if any
+    pub fn yarn_path(&self) -> Fallible<Option<PathBuf>> {
+        find_yarn_path(self.workspace_roots())
+    }
+
     /// Returns a reference to the Project's `PlatformSpec`, if available
-    pub fn platform(&self) -> Option<&PlatformSpec> {
-        self.platform.as_ref()
+    ///
+    /// A Node version from a version file is resolved the first time this is called.
+    pub fn platform(&self) -> Fallible<Option<&PlatformSpec>> {
+        self.platform
+            .try_borrow_with(|| self.resolve_platform())
+            .map(Option::as_ref)
+    }
+
+    /// Returns a mutable reference to the Project's `PlatformSpec`, if available
+    fn platform_mut(&mut self) -> Fallible<Option<&mut PlatformSpec>> {
+        self.platform()?;
+        Ok(self.platform.borrow_mut().and_then(Option::as_mut))
+    }
+
+    /// Updates the partial platform read from the manifests
+    fn update_partial_platform<F>(&mut self, update: F)
+    where
+        F: FnOnce(&mut PartialPlatform),
+    {
+        update(self.partial_platform.get_or_insert_with(Default::default));
+    }
+
+    /// Returns the names and versions of the packages pinned in the project's manifests
+    pub fn pinned_packages(&self) -> impl Iterator<Item = (&String, &Version)> {
+        self.packages.iter()
+    }
+
+    /// Returns the `.nvmrc` or `.node-version` file that the project Node version came from, if
+    /// the manifest doesn't pin a Node version itself
+    pub fn node_version_file(&self) -> Option<&Path> {
+        self.node_version_file
+            .as_ref()
+            .map(|(file, _)| file.as_path())
+    }
+
+    /// Returns the package manager declared by the `packageManager` field, if any
+    pub fn package_manager(&self) -> Option<&PackageManager> {
+        self.package_manager.as_ref()
+    }
+
+    /// Returns the hash declared by the `packageManager` field for the given package manager
+    /// version, if there is one
+    pub fn package_manager_hash(
+        &self,
+        kind: PackageManagerKind,
+        version: &Version,
+    ) -> Option<&PackageManagerHash> {
+        self.package_manager
+            .as_ref()
+            .filter(|manager| manager.kind == kind && &manager.version == version)
+            .and_then(|manager| manager.hash.as_ref())
     }
 
     /// Returns true if the project dependency map contains the specified dependency
@@ -146,11 +298,14 @@ impl Project {
     /// Returns true if the input binary name is a direct dependency of the input project
     pub fn has_direct_bin(&self, bin_name: &OsStr) -> Fallible<bool> {
         if let Some(name) = bin_name.to_str() {
-            let config_path = volta_home()?.default_tool_bin_config(name);
+            let config_path = active_profile()?.bin_config_file(name);
 
             return match BinConfig::from_file_if_exists(config_path)? {
                 None => Ok(false),
-                Some(config) => Ok(self.has_direct_dependency(&config.package)),
+                // Note: Aliased binaries never come from the project's dependencies
+                Some(config) => {
+                    Ok(config.alias.is_none() && self.has_direct_dependency(&config.package))
+                }
             };
         }
         Ok(false)
@@ -174,26 +329,31 @@ impl Project {
     /// Pins the Node version in this project's manifest file
     pub fn pin_node(&mut self, version: Version) -> Fallible<()> {
         update_manifest(&self.manifest_file, ManifestKey::Node, Some(&version))?;
+        self.node_version_file = None;
+        self.update_partial_platform(|partial| partial.node = Some(version));
 
-        if let Some(platform) = self.platform.as_mut() {
-            platform.node = version;
-        } else {
-            self.platform = Some(PlatformSpec {
-                node: version,
-                npm: None,
-                yarn: None,
-            });
-        }
+        // Note: With the Node version pinned, the platform is resolved again without the network
+        self.platform = LazyCell::new();
+
+        Ok(())
+    }
+
+    /// Pins a version of a package in this project's manifest file
+    pub fn pin_package(&mut self, name: &str, version: Version) -> Fallible<()> {
+        update_manifest_package(&self.manifest_file, name, &version)?;
+        self.packages.insert(name.into(), version);
 
         Ok(())
     }
 
     /// Pins the npm version in this project's manifest file
     pub fn pin_npm(&mut self, version: Option<Version>) -> Fallible<()> {
-        if let Some(platform) = self.platform.as_mut() {
-            update_manifest(&self.manifest_file, ManifestKey::Npm, version.as_ref())?;
+        let manifest_file = self.manifest_file.clone();
+        if let Some(platform) = self.platform_mut()? {
+            update_manifest(&manifest_file, ManifestKey::Npm, version.as_ref())?;
 
-            platform.npm = version;
+            platform.npm = version.clone();
+            self.update_partial_platform(|partial| partial.npm = version);
 
             Ok(())
         } else {
@@ -201,12 +361,32 @@ impl Project {
         }
     }
 
+    /// Pins the pnpm version in this project's manifest file
+    pub fn pin_pnpm(&mut self, version: Option<Version>) -> Fallible<()> {
+        let manifest_file = self.manifest_file.clone();
+        if let Some(platform) = self.platform_mut()? {
+            update_manifest(&manifest_file, ManifestKey::Pnpm, version.as_ref())?;
+
+            platform.pnpm = version.clone();
+            self.update_partial_platform(|partial| partial.pnpm = version);
+
+            Ok(())
+        } else {
+            Err(ErrorKind::NoPinnedNodeVersion {
+                tool: "pnpm".into(),
+            }
+            .into())
+        }
+    }
+
     /// Pins the Yarn version in this project's manifest file
     pub fn pin_yarn(&mut self, version: Option<Version>) -> Fallible<()> {
-        if let Some(platform) = self.platform.as_mut() {
-            update_manifest(&self.manifest_file, ManifestKey::Yarn, version.as_ref())?;
+        let manifest_file = self.manifest_file.clone();
+        if let Some(platform) = self.platform_mut()? {
+            update_manifest(&manifest_file, ManifestKey::Yarn, version.as_ref())?;
 
-            platform.yarn = version;
+            platform.yarn = version.clone();
+            self.update_partial_platform(|partial| partial.yarn = version);
 
             Ok(())
         } else {
@@ -245,9 +425,12 @@ pub(crate) fn find_closest_root(mut dir: PathBuf) -> Option<PathBuf> {
     Some(dir)
 }
 
+#[derive(Clone, Default)]
+#[cfg_attr(test, derive(Debug))]
 struct PartialPlatform {
     node: Option<Version>,
     npm: Option<Version>,
+    pnpm: Option<Version>,
     yarn: Option<Version>,
 }
 
@@ -256,6 +439,7 @@ impl PartialPlatform {
         PartialPlatform {
             node: self.node.or(other.node),
             npm: self.npm.or(other.npm),
+            pnpm: self.pnpm.or(other.pnpm),
             yarn: self.yarn.or(other.yarn),
         }
     }
@@ -270,6 +454,7 @@ impl TryFrom<PartialPlatform> for PlatformSpec {
         Ok(PlatformSpec {
             node,
             npm: partial.npm,
+            pnpm: partial.pnpm,
             yarn: partial.yarn,
         })
     }
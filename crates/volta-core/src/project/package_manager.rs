@@ -0,0 +1,178 @@
+//! Provides support for the `packageManager` field in `package.json`, which declares the package
+//! manager for a project (following the convention used by Corepack).
+
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::error::{ErrorKind, Fallible};
+use crate::fs::hash_file;
+use crate::version::parse_version;
+use semver::Version;
+use sha1::Sha1;
+use sha2::{Sha224, Sha256, Sha512};
+
+/// The package managers that can be declared by the `packageManager` field
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(test, derive(Debug))]
+pub enum PackageManagerKind {
+    Npm,
+    Pnpm,
+    Yarn,
+}
+
+impl fmt::Display for PackageManagerKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PackageManagerKind::Npm => "npm",
+            PackageManagerKind::Pnpm => "pnpm",
+            PackageManagerKind::Yarn => "yarn",
+        })
+    }
+}
+
+impl FromStr for PackageManagerKind {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        match s {
+            "npm" => Ok(PackageManagerKind::Npm),
+            "pnpm" => Ok(PackageManagerKind::Pnpm),
+            "yarn" => Ok(PackageManagerKind::Yarn),
+            _ => Err(()),
+        }
+    }
+}
+
+/// A package manager declared by the `packageManager` field, e.g. `yarn@3.6.1+sha224.abc123`
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub struct PackageManager {
+    pub kind: PackageManagerKind,
+    pub version: Version,
+    pub hash: Option<PackageManagerHash>,
+}
+
+impl PackageManager {
+    /// Parses the value of a `packageManager` field
+    ///
+    /// Returns `None` if the field declares a package manager that Volta doesn't support.
+    pub(super) fn parse(value: &str) -> Fallible<Option<Self>> {
+        let parse_error = || ErrorKind::PackageManagerParseError {
+            value: value.into(),
+        };
+
+        let (name, spec) = value.split_once('@').ok_or_else(parse_error)?;
+        let kind = match name.parse() {
+            Ok(kind) => kind,
+            Err(()) => return Ok(None),
+        };
+
+        let (version, hash) = match spec.split_once('+') {
+            Some((version, hash)) => (version, Some(hash.parse().map_err(|_| parse_error())?)),
+            None => (spec, None),
+        };
+        let version = parse_version(version).map_err(|_| parse_error())?;
+
+        Ok(Some(PackageManager {
+            kind,
+            version,
+            hash,
+        }))
+    }
+}
+
+/// The hash of the package manager archive, embedded in a `packageManager` field
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub struct PackageManagerHash {
+    algorithm: HashAlgorithm,
+    digest: String,
+}
+
+#[derive(Clone, Copy)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+enum HashAlgorithm {
+    Sha1,
+    Sha224,
+    Sha256,
+    Sha512,
+}
+
+impl PackageManagerHash {
+    /// Check whether the given file matches this hash
+    pub fn matches(&self, file: &Path) -> bool {
+        let actual = match self.algorithm {
+            HashAlgorithm::Sha1 => hash_file::<Sha1>(file),
+            HashAlgorithm::Sha224 => hash_file::<Sha224>(file),
+            HashAlgorithm::Sha256 => hash_file::<Sha256>(file),
+            HashAlgorithm::Sha512 => hash_file::<Sha512>(file),
+        };
+
+        actual
+            .map(|actual| hex::encode(&actual).eq_ignore_ascii_case(&self.digest))
+            .unwrap_or(false)
+    }
+}
+
+impl FromStr for PackageManagerHash {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        let (algorithm, digest) = s.split_once('.').ok_or(())?;
+        let algorithm = match algorithm {
+            "sha1" => HashAlgorithm::Sha1,
+            "sha224" => HashAlgorithm::Sha224,
+            "sha256" => HashAlgorithm::Sha256,
+            "sha512" => HashAlgorithm::Sha512,
+            _ => return Err(()),
+        };
+
+        if digest.is_empty() || !digest.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(());
+        }
+
+        Ok(PackageManagerHash {
+            algorithm,
+            digest: digest.into(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_package_manager() {
+        let manager = PackageManager::parse("yarn@3.6.1").unwrap().unwrap();
+        assert_eq!(manager.kind, PackageManagerKind::Yarn);
+        assert_eq!(manager.version, "3.6.1".parse().unwrap());
+        assert_eq!(manager.hash, None);
+
+        let manager =
+            PackageManager::parse("pnpm@8.6.0+sha1.10d224f266a6fda6cc0acfc60a7ee41e68c70f64")
+                .unwrap()
+                .unwrap();
+        assert_eq!(manager.kind, PackageManagerKind::Pnpm);
+        assert_eq!(manager.version, "8.6.0".parse().unwrap());
+        assert_eq!(
+            manager.hash,
+            Some(PackageManagerHash {
+                algorithm: HashAlgorithm::Sha1,
+                digest: "10d224f266a6fda6cc0acfc60a7ee41e68c70f64".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_unsupported_package_manager() {
+        assert_eq!(PackageManager::parse("bun@1.0.0").unwrap(), None);
+    }
+
+    #[test]
+    fn test_parse_invalid_package_manager() {
+        assert!(PackageManager::parse("yarn").is_err());
+        assert!(PackageManager::parse("yarn@latest").is_err());
+        assert!(PackageManager::parse("yarn@3.6.1+md5.abc123").is_err());
+        assert!(PackageManager::parse("yarn@3.6.1+sha224.not-hex").is_err());
+    }
+}
@@ -4,10 +4,12 @@ use std::fs::{read_to_string, File};
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
+use super::package_manager::PackageManager;
 use super::PartialPlatform;
 use crate::error::{Context, ErrorKind, Fallible};
 use crate::version::parse_version;
 use dunce::canonicalize;
+use log::{debug, warn};
 use semver::Version;
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
@@ -20,7 +22,9 @@ pub type DependencyMapIterator = std::iter::Chain<
 pub(super) struct Manifest {
     pub dependency_maps: DependencyMapIterator,
     pub platform: Option<PartialPlatform>,
+    pub packages: HashMap<String, Version>,
     pub extends: Option<PathBuf>,
+    pub package_manager: Option<PackageManager>,
 }
 
 impl Manifest {
@@ -32,9 +36,9 @@ impl Manifest {
             .into_iter()
             .chain(raw.dev_dependencies.into_iter());
 
-        let (platform, extends) = match raw.volta {
+        let (platform, packages, extends) = match raw.volta {
             Some(toolchain) => {
-                let (partial, extends) = toolchain.parse_split()?;
+                let (partial, packages, extends) = toolchain.parse_split()?;
 
                 let next = extends
                     .map(|path| {
@@ -47,15 +51,33 @@ impl Manifest {
                             .with_context(|| ErrorKind::ExtensionPathError { path })
                     })
                     .transpose()?;
-                (Some(partial), next)
+                (partial, packages, next)
             }
-            None => (None, None),
+            None => (None, HashMap::new(), None),
+        };
+
+        // Note: An invalid `packageManager` field is ignored with a warning, like one declaring a
+        // package manager that Volta doesn't support, rather than failing every command run in
+        // the project
+        let package_manager = match raw.package_manager.as_deref().map(PackageManager::parse) {
+            Some(Ok(manager)) => manager,
+            Some(Err(error)) => {
+                warn!(
+                    "Ignoring the `packageManager` field in '{}'",
+                    file.display()
+                );
+                debug!("{}", error);
+                None
+            }
+            None => None,
         };
 
         Ok(Manifest {
             dependency_maps,
             platform,
+            packages,
             extends,
+            package_manager,
         })
     }
 }
@@ -63,6 +85,7 @@ impl Manifest {
 pub(super) enum ManifestKey {
     Node,
     Npm,
+    Pnpm,
     Yarn,
 }
 
@@ -71,6 +94,7 @@ impl fmt::Display for ManifestKey {
         f.write_str(match self {
             ManifestKey::Node => "node",
             ManifestKey::Npm => "npm",
+            ManifestKey::Pnpm => "pnpm",
             ManifestKey::Yarn => "yarn",
         })
     }
@@ -86,6 +110,63 @@ pub(super) fn update_manifest(
     key: ManifestKey,
     value: Option<&Version>,
 ) -> Fallible<()> {
+    edit_manifest(file, |root| {
+        let key = key.to_string();
+
+        // If the `packageManager` field declares the same tool, keep it in sync with the new
+        // version. Any hash is dropped, since it would no longer match.
+        if let Some(v) = value {
+            if let Some(Value::String(package_manager)) = root.get_mut("packageManager") {
+                if package_manager.split('@').next() == Some(key.as_str()) {
+                    *package_manager = format!("{}@{}", key, v);
+                }
+            }
+        }
+
+        match (value, root.get_mut("volta").and_then(|v| v.as_object_mut())) {
+            (Some(v), Some(hash)) => {
+                hash.insert(key, Value::String(v.to_string()));
+            }
+            (None, Some(hash)) => {
+                hash.remove(&key);
+            }
+            (Some(v), None) => {
+                let mut map = Map::new();
+                map.insert(key, Value::String(v.to_string()));
+                root.insert("volta".into(), Value::Object(map));
+            }
+            (None, None) => {}
+        }
+    })
+}
+
+/// Updates the `volta.packages` hash in the specified manifest with the given package version
+///
+/// Will create the `volta` and `packages` hashes if they aren't already present
+pub(super) fn update_manifest_package(file: &Path, name: &str, version: &Version) -> Fallible<()> {
+    edit_manifest(file, |root| {
+        let volta = root
+            .entry("volta")
+            .or_insert_with(|| Value::Object(Map::new()));
+
+        // Note: A non-object `volta` value would have failed to parse when loading the project
+        if let Some(volta) = volta.as_object_mut() {
+            let packages = volta
+                .entry("packages")
+                .or_insert_with(|| Value::Object(Map::new()));
+
+            if let Some(packages) = packages.as_object_mut() {
+                packages.insert(name.into(), Value::String(version.to_string()));
+            }
+        }
+    })
+}
+
+/// Applies an edit to the root object of the specified manifest, preserving its indentation
+fn edit_manifest<F>(file: &Path, edit: F) -> Fallible<()>
+where
+    F: FnOnce(&mut Map<String, Value>),
+{
     let contents = read_to_string(&file).with_context(|| ErrorKind::PackageReadError {
         file: file.to_owned(),
     })?;
@@ -101,22 +182,7 @@ pub(super) fn update_manifest(
             file: file.to_owned(),
         })?;
 
-    let key = key.to_string();
-
-    match (value, root.get_mut("volta").and_then(|v| v.as_object_mut())) {
-        (Some(v), Some(hash)) => {
-            hash.insert(key, Value::String(v.to_string()));
-        }
-        (None, Some(hash)) => {
-            hash.remove(&key);
-        }
-        (Some(v), None) => {
-            let mut map = Map::new();
-            map.insert(key, Value::String(v.to_string()));
-            root.insert("volta".into(), Value::Object(map));
-        }
-        (None, None) => {}
-    }
+    edit(root);
 
     let indent = detect_indent::detect_indent(&contents);
     let mut output = File::create(&file).with_context(|| ErrorKind::PackageWriteError {
@@ -147,6 +213,9 @@ struct RawManifest {
     dev_dependencies: Option<HashMap<String, String>>,
 
     volta: Option<ToolchainSpec>,
+
+    #[serde(rename = "packageManager")]
+    package_manager: Option<String>,
 }
 
 impl RawManifest {
@@ -161,6 +230,12 @@ impl RawManifest {
     }
 }
 
+type ToolchainParts = (
+    Option<PartialPlatform>,
+    HashMap<String, Version>,
+    Option<PathBuf>,
+);
+
 #[derive(Default, Deserialize, Serialize)]
 struct ToolchainSpec {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -168,20 +243,44 @@ struct ToolchainSpec {
     #[serde(skip_serializing_if = "Option::is_none")]
     npm: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pnpm: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     yarn: Option<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    packages: HashMap<String, String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     extends: Option<PathBuf>,
 }
 
 impl ToolchainSpec {
-    /// Moves the tool versions into a `PartialPlatform` and returns that along with the `extends` value
-    fn parse_split(self) -> Fallible<(PartialPlatform, Option<PathBuf>)> {
+    /// Moves the tool versions into a `PartialPlatform` and returns that along with the pinned
+    /// package versions and the `extends` value
+    ///
+    /// If no tool versions are set, for example when only packages are pinned, there is no
+    /// `PartialPlatform`.
+    fn parse_split(self) -> Fallible<ToolchainParts> {
         let node = self.node.map(parse_version).transpose()?;
         let npm = self.npm.map(parse_version).transpose()?;
+        let pnpm = self.pnpm.map(parse_version).transpose()?;
         let yarn = self.yarn.map(parse_version).transpose()?;
 
-        let platform = PartialPlatform { node, npm, yarn };
+        let platform = if node.is_none() && npm.is_none() && pnpm.is_none() && yarn.is_none() {
+            None
+        } else {
+            Some(PartialPlatform {
+                node,
+                npm,
+                pnpm,
+                yarn,
+            })
+        };
+
+        let packages = self
+            .packages
+            .into_iter()
+            .map(|(name, version)| parse_version(version).map(|version| (name, version)))
+            .collect::<Fallible<_>>()?;
 
-        Ok((platform, self.extends))
+        Ok((platform, packages, self.extends))
     }
 }
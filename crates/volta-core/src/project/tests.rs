@@ -73,10 +73,11 @@ mod project {
     fn platform_simple() {
         let project_path = fixture_path(&["basic"]);
         let test_project = Project::for_dir(project_path).unwrap().unwrap();
-        let platform = test_project.platform().unwrap();
+        let platform = test_project.platform().unwrap().unwrap();
 
         assert_eq!(platform.node, "6.11.1".parse().unwrap());
         assert_eq!(platform.npm, Some("3.10.10".parse().unwrap()));
+        assert_eq!(platform.pnpm, Some("7.33.7".parse().unwrap()));
         assert_eq!(platform.yarn, Some("1.2.0".parse().unwrap()));
     }
 
@@ -84,7 +85,7 @@ mod project {
     fn platform_workspace() {
         let project_path = fixture_path(&["nested", "subproject", "inner_project"]);
         let test_project = Project::for_dir(project_path).unwrap().unwrap();
-        let platform = test_project.platform().unwrap();
+        let platform = test_project.platform().unwrap().unwrap();
 
         // From the top level `nested/package.json`
         assert_eq!(platform.node, "12.14.0".parse().unwrap());
@@ -94,6 +95,123 @@ mod project {
         assert_eq!(platform.yarn, Some("1.22.4".parse().unwrap()));
     }
 
+    #[test]
+    fn platform_from_nvmrc() {
+        let project_path = fixture_path(&["nvmrc"]);
+        let test_project = Project::for_dir(project_path).unwrap().unwrap();
+        let platform = test_project.platform().unwrap().unwrap();
+
+        // From `nvmrc/.nvmrc`, since the manifest doesn't pin Node
+        assert_eq!(platform.node, "14.17.0".parse().unwrap());
+        // From `nvmrc/package.json`
+        assert_eq!(platform.yarn, Some("1.22.4".parse().unwrap()));
+        assert_eq!(
+            test_project.node_version_file(),
+            Some(&*fixture_path(&["nvmrc", ".nvmrc"]))
+        );
+    }
+
+    #[test]
+    fn platform_from_node_version() {
+        let project_path = fixture_path(&["node-version"]);
+        let test_project = Project::for_dir(project_path).unwrap().unwrap();
+        let platform = test_project.platform().unwrap().unwrap();
+
+        assert_eq!(platform.node, "16.3.0".parse().unwrap());
+        assert_eq!(platform.yarn, None);
+        assert_eq!(
+            test_project.node_version_file(),
+            Some(&*fixture_path(&["node-version", ".node-version"]))
+        );
+    }
+
+    #[test]
+    fn platform_prefers_manifest_over_nvmrc() {
+        let project_path = fixture_path(&["basic"]);
+        let test_project = Project::for_dir(project_path).unwrap().unwrap();
+        let platform = test_project.platform().unwrap().unwrap();
+
+        assert_eq!(platform.node, "6.11.1".parse().unwrap());
+        assert_eq!(test_project.node_version_file(), None);
+    }
+
+    #[test]
+    fn platform_ignores_unsupported_nvmrc() {
+        let project_path = fixture_path(&["nvmrc-unsupported"]);
+        let test_project = Project::for_dir(project_path).unwrap().unwrap();
+
+        // `system` can't be used by Volta, so the file is ignored rather than an error
+        assert!(test_project.platform().unwrap().is_none());
+        assert_eq!(test_project.node_version_file(), None);
+    }
+
+    #[test]
+    fn nvmrc_is_resolved_lazily() {
+        let project_path = fixture_path(&["nvmrc-lts"]);
+
+        // Resolving `lts/*` needs the Node index, which isn't fetched until the platform is used
+        let test_project = Project::for_dir(project_path).unwrap().unwrap();
+        assert_eq!(
+            test_project.node_version_file(),
+            Some(&*fixture_path(&["nvmrc-lts", ".nvmrc"]))
+        );
+    }
+
+    #[test]
+    fn platform_from_package_manager() {
+        let project_path = fixture_path(&["package-manager"]);
+        let test_project = Project::for_dir(project_path).unwrap().unwrap();
+        let platform = test_project.platform().unwrap().unwrap();
+
+        // From the `packageManager` field, since `volta.yarn` isn't set
+        assert_eq!(platform.yarn, Some("1.22.4".parse().unwrap()));
+        // From `volta.npm`
+        assert_eq!(platform.npm, Some("6.14.13".parse().unwrap()));
+
+        let yarn = "1.22.4".parse().unwrap();
+        assert!(test_project
+            .package_manager_hash(PackageManagerKind::Yarn, &yarn)
+            .is_some());
+        assert!(test_project
+            .package_manager_hash(PackageManagerKind::Npm, &yarn)
+            .is_none());
+        assert!(test_project
+            .package_manager_hash(PackageManagerKind::Yarn, &"1.22.5".parse().unwrap())
+            .is_none());
+    }
+
+    #[test]
+    fn platform_ignores_invalid_package_manager() {
+        let project_path = fixture_path(&["package-manager-invalid"]);
+        let test_project = Project::for_dir(project_path).unwrap().unwrap();
+        let platform = test_project.platform().unwrap().unwrap();
+
+        assert!(test_project.package_manager().is_none());
+        assert_eq!(platform.yarn, None);
+    }
+
+    #[test]
+    fn pinned_packages() {
+        let project_path = fixture_path(&["pinned-packages"]);
+        let test_project = Project::for_dir(project_path).unwrap().unwrap();
+
+        assert_eq!(
+            test_project.pinned_packages().collect::<Vec<_>>(),
+            vec![
+                (&"@vue/cli".to_string(), &"5.0.8".parse().unwrap()),
+                (&"typescript".to_string(), &"4.9.5".parse().unwrap()),
+            ]
+        );
+    }
+
+    #[test]
+    fn pinned_packages_none() {
+        let project_path = fixture_path(&["basic"]);
+        let test_project = Project::for_dir(project_path).unwrap().unwrap();
+
+        assert_eq!(test_project.pinned_packages().count(), 0);
+    }
+
     #[test]
     fn direct_dependencies_single() {
         let project_path = fixture_path(&["basic"]);
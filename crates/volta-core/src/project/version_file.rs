@@ -0,0 +1,150 @@
+//! Provides support for the `.nvmrc` and `.node-version` files that other Node version managers
+//! use to pin the Node version of a project.
+
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+use super::Project;
+use crate::error::{Context, ErrorKind, Fallible};
+use crate::hook::HookConfig;
+use crate::inventory::node_versions;
+use crate::tool::node;
+use crate::version::{parse_requirements, parse_version, VersionSpec, VersionTag};
+use log::{debug, warn};
+use semver::Version;
+
+/// The names of the supported version files, in order of precedence
+const VERSION_FILES: [&str; 2] = [".nvmrc", ".node-version"];
+
+/// Finds the first version file in the given project roots
+pub(super) fn find_version_file<'a, I>(roots: I) -> Option<PathBuf>
+where
+    I: IntoIterator<Item = &'a Path>,
+{
+    roots
+        .into_iter()
+        .flat_map(|root| VERSION_FILES.iter().map(move |name| root.join(name)))
+        .find(|file| file.is_file())
+}
+
+/// Reads the Node version requirement from a version file, if it contains one
+///
+/// Other version managers support values that Volta can't use, such as `system` or `iojs`, so a
+/// file that can't be parsed is ignored with a warning rather than breaking the project.
+pub(super) fn read_version_file(file: &Path) -> Fallible<Option<VersionSpec>> {
+    let contents = read_to_string(file).with_context(|| ErrorKind::ReadNodeVersionFileError {
+        file: file.to_owned(),
+    })?;
+
+    match parse_version_file(&contents) {
+        Some(Ok(matching)) => Ok(Some(matching)),
+        Some(Err(_)) => {
+            warn!(
+                "Ignoring version file '{}', as it doesn't contain a supported Node version",
+                file.display()
+            );
+            Ok(None)
+        }
+        None => Ok(None),
+    }
+}
+
+/// Parses the contents of a version file
+///
+/// Only the first line with any content is considered, ignoring comments. Along with plain
+/// versions and ranges, this supports the `node`, `lts/*`, and `lts/<codename>` aliases.
+fn parse_version_file(contents: &str) -> Option<Fallible<VersionSpec>> {
+    let version = contents
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .find(|line| !line.is_empty())?;
+
+    Some(match version {
+        "node" | "stable" => Ok(VersionSpec::Tag(VersionTag::Latest)),
+        "lts/*" => Ok(VersionSpec::Tag(VersionTag::Lts)),
+        alias if alias.starts_with("lts/") => {
+            Ok(VersionSpec::Tag(VersionTag::Custom(alias.to_lowercase())))
+        }
+        version => {
+            let version = version.strip_prefix('v').unwrap_or(version);
+            parse_version(version)
+                .map(VersionSpec::Exact)
+                .or_else(|_| parse_requirements(version).map(VersionSpec::Semver))
+        }
+    })
+}
+
+/// Resolves the Node version from a version file into an exact version
+///
+/// Partial versions are matched against the locally installed Node versions first, so that
+/// running a tool in the project doesn't need the network once a matching version is fetched.
+pub(super) fn resolve_version_file(matching: VersionSpec, project: &Project) -> Fallible<Version> {
+    match &matching {
+        VersionSpec::Exact(version) => return Ok(version.clone()),
+        VersionSpec::Semver(requirement) => {
+            let installed = node_versions()?
+                .into_iter()
+                .rev()
+                .find(|version| requirement.matches(version));
+
+            if let Some(version) = installed {
+                debug!(
+                    "Found installed node@{} matching requirement '{}'",
+                    version, requirement
+                );
+                return Ok(version);
+            }
+        }
+        _ => {}
+    }
+
+    let hooks = HookConfig::current(Some(project))?;
+    node::resolve_with_hooks(matching, hooks.node())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_version_file;
+    use crate::version::{parse_requirements, VersionSpec, VersionTag};
+
+    fn parse(contents: &str) -> VersionSpec {
+        parse_version_file(contents)
+            .expect("Contents should include a version")
+            .expect("Version should parse")
+    }
+
+    #[test]
+    fn test_parse_version_file() {
+        assert_eq!(
+            parse("v14.17.0\n"),
+            VersionSpec::Exact("14.17.0".parse().unwrap())
+        );
+        assert_eq!(
+            parse("# Node version\n\n16.3.0 # latest\n"),
+            VersionSpec::Exact("16.3.0".parse().unwrap())
+        );
+        assert_eq!(
+            parse("14"),
+            VersionSpec::Semver(parse_requirements("14").unwrap())
+        );
+        assert_eq!(parse("lts/*"), VersionSpec::Tag(VersionTag::Lts));
+        assert_eq!(
+            parse("lts/Gallium"),
+            VersionSpec::Tag(VersionTag::Custom("lts/gallium".into()))
+        );
+        assert_eq!(parse("node"), VersionSpec::Tag(VersionTag::Latest));
+    }
+
+    #[test]
+    fn test_parse_unsupported_version_file() {
+        assert!(parse_version_file("system").unwrap().is_err());
+        assert!(parse_version_file("default\n").unwrap().is_err());
+        assert!(parse_version_file("iojs").unwrap().is_err());
+    }
+
+    #[test]
+    fn test_parse_empty_version_file() {
+        assert!(parse_version_file("").is_none());
+        assert!(parse_version_file("\n  \n# comment only\n").is_none());
+    }
+}
@@ -0,0 +1,75 @@
+//! Provides support for the `yarnPath` setting in `.yarnrc.yml`, which projects use to vendor a
+//! specific release of Yarn.
+
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+use crate::error::{Context, ErrorKind, Fallible};
+
+/// The name of the Yarn 2+ configuration file
+const YARNRC_FILE: &str = ".yarnrc.yml";
+
+/// Finds the `yarnPath` setting from the first `.yarnrc.yml` in the given project roots
+///
+/// The returned path is resolved relative to the directory containing the configuration file.
+pub(super) fn find_yarn_path<'a, I>(roots: I) -> Fallible<Option<PathBuf>>
+where
+    I: IntoIterator<Item = &'a Path>,
+{
+    let file = match roots
+        .into_iter()
+        .map(|root| root.join(YARNRC_FILE))
+        .find(|file| file.is_file())
+    {
+        Some(file) => file,
+        None => return Ok(None),
+    };
+
+    let contents =
+        read_to_string(&file).with_context(|| ErrorKind::ReadYarnrcError { file: file.clone() })?;
+
+    Ok(parse_yarn_path(&contents).map(|yarn_path| {
+        file.parent()
+            .expect("File paths always have a parent")
+            .join(yarn_path)
+    }))
+}
+
+/// Parses the top-level `yarnPath` setting from the contents of a `.yarnrc.yml` file
+fn parse_yarn_path(contents: &str) -> Option<&str> {
+    contents
+        .lines()
+        .find_map(|line| line.strip_prefix("yarnPath:"))
+        .map(|value| value.split(" #").next().unwrap_or_default().trim())
+        .map(|value| value.trim_matches(|c| c == '"' || c == '\''))
+        .filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_yarn_path;
+
+    #[test]
+    fn test_parse_yarn_path() {
+        assert_eq!(
+            parse_yarn_path("yarnPath: .yarn/releases/yarn-3.6.1.cjs\n"),
+            Some(".yarn/releases/yarn-3.6.1.cjs")
+        );
+        assert_eq!(
+            parse_yarn_path(
+                "nodeLinker: node-modules\n\nyarnPath: \".yarn/releases/yarn-4.0.2.cjs\" # vendored\n"
+            ),
+            Some(".yarn/releases/yarn-4.0.2.cjs")
+        );
+    }
+
+    #[test]
+    fn test_parse_missing_yarn_path() {
+        assert_eq!(parse_yarn_path("nodeLinker: node-modules\n"), None);
+        assert_eq!(
+            parse_yarn_path("packageExtensions:\n  yarnPath: nested\n"),
+            None
+        );
+        assert_eq!(parse_yarn_path("yarnPath: ''\n"), None);
+    }
+}
@@ -7,8 +7,9 @@ use super::{debug_active_image, debug_no_platform};
 use crate::error::{Context, ErrorKind, Fallible};
 use crate::layout::volta_home;
 use crate::platform::{Platform, Sourced, System};
+use crate::profile::active_profile;
 use crate::session::Session;
-use crate::tool::package::BinConfig;
+use crate::tool::package::{find_pinned_binary, BinConfig};
 use log::debug;
 
 /// Determine the correct command to run for a 3rd-party binary
@@ -40,6 +41,24 @@ pub(super) fn command(exe: &OsStr, args: &[OsString], session: &mut Session) ->
         }
     }
 
+    // Next, try to use a package pinned by the project
+    if let Some(path_to_bin) = find_pinned_binary(&bin, session)? {
+        debug!(
+            "Found {} in pinned package at '{}'",
+            bin,
+            path_to_bin.display()
+        );
+
+        let platform = Platform::current(session)?;
+        return Ok(ToolCommand::new(
+            path_to_bin,
+            args,
+            platform,
+            ToolKind::ProjectLocalBinary(bin),
+        )
+        .into());
+    }
+
     // Try to use the default toolchain
     if let Some(default_tool) = DefaultBinary::from_name(exe, session)? {
         debug!(
@@ -124,9 +143,10 @@ pub struct DefaultBinary {
 
 impl DefaultBinary {
     pub fn from_config(bin_config: BinConfig, session: &mut Session) -> Fallible<Self> {
-        let package_dir = volta_home()?.package_image_dir(&bin_config.package);
+        let package_dir =
+            volta_home()?.package_image_dir(&bin_config.package, &bin_config.version.to_string());
         let mut bin_path = bin_config.manager.binary_dir(package_dir);
-        bin_path.push(&bin_config.name);
+        bin_path.push(bin_config.target_name());
 
         // If the user does not have yarn set in the platform for this binary, use the default
         // This is necessary because some tools (e.g. ember-cli with the `--yarn` option) invoke `yarn`
@@ -139,6 +159,7 @@ impl DefaultBinary {
         let platform = Platform {
             node: Sourced::with_binary(bin_config.platform.node),
             npm: bin_config.platform.npm.map(Sourced::with_binary),
+            pnpm: bin_config.platform.pnpm.map(Sourced::with_binary),
             yarn: yarn.map(Sourced::with_binary),
         };
 
@@ -151,7 +172,7 @@ impl DefaultBinary {
     /// name is not a valid UTF-8 string, or the tool config doesn't exist.
     pub fn from_name(tool_name: &OsStr, session: &mut Session) -> Fallible<Option<Self>> {
         let bin_config_file = match tool_name.to_str() {
-            Some(name) => volta_home()?.default_tool_bin_config(name),
+            Some(name) => active_profile()?.bin_config_file(name),
             None => return Ok(None),
         };
 
@@ -11,6 +11,7 @@ use crate::command::create_command;
 use crate::error::{Context, ErrorKind, Fallible};
 use crate::layout::volta_home;
 use crate::platform::{CliPlatform, Platform, System};
+use crate::profile::active_profile;
 use crate::session::Session;
 use crate::signal::pass_control_to_shim;
 use crate::style::{note_prefix, tool_version};
@@ -77,7 +78,7 @@ impl Executor {
             Executor::PackageLink(cmd) => cmd.execute(session),
             Executor::PackageUpgrade(cmd) => cmd.execute(session),
             Executor::InternalInstall(cmd) => cmd.execute(session),
-            Executor::Uninstall(cmd) => cmd.execute(),
+            Executor::Uninstall(cmd) => cmd.execute(session),
             Executor::Multiple(executors) => {
                 info!(
                     "{} Volta is processing each package separately",
@@ -123,6 +124,7 @@ pub enum ToolKind {
     Node,
     Npm,
     Npx,
+    Pnpm,
     Yarn,
     ProjectLocalBinary(String),
     DefaultBinary(String),
@@ -179,6 +181,7 @@ impl ToolCommand {
             ToolKind::Node => super::node::execution_context(self.platform, session)?,
             ToolKind::Npm => super::npm::execution_context(self.platform, session)?,
             ToolKind::Npx => super::npx::execution_context(self.platform, session)?,
+            ToolKind::Pnpm => super::pnpm::execution_context(self.platform, session)?,
             ToolKind::Yarn => super::yarn::execution_context(self.platform, session)?,
             ToolKind::DefaultBinary(bin) => {
                 super::binary::default_execution_context(bin, self.platform, session)?
@@ -226,6 +229,7 @@ impl PackageInstallCommand {
 
         let mut command = match manager {
             PackageManager::Npm => create_command("npm"),
+            PackageManager::Pnpm => create_command("pnpm"),
             PackageManager::Yarn => create_command("yarn"),
         };
         command.args(args);
@@ -348,14 +352,14 @@ impl PackageLinkCommand {
     ///
     /// This will also check for some common failure cases and alert the user
     pub fn execute(mut self, session: &mut Session) -> Fallible<ExitStatus> {
-        self.check_linked_package(session)?;
+        let config = self.check_linked_package(session)?;
 
         let image = self.platform.checkout(session)?;
         let path = image.path()?;
 
         self.command.env(RECURSION_ENV_VAR, "1");
         self.command.env("PATH", path);
-        let package_root = volta_home()?.package_image_dir(&self.tool);
+        let package_root = volta_home()?.package_image_dir(&self.tool, &config.version.to_string());
         PackageManager::Npm.setup_global_command(&mut self.command, package_root);
 
         self.command
@@ -367,12 +371,13 @@ impl PackageLinkCommand {
     ///     - The package is not found as a global
     ///     - The package exists, but was linked using a different package manager
     ///     - The package is using a different version of Node than the current project (warning)
-    fn check_linked_package(&self, session: &mut Session) -> Fallible<()> {
-        let config =
-            PackageConfig::from_file(volta_home()?.default_package_config_file(&self.tool))
-                .with_context(|| ErrorKind::NpmLinkMissingPackage {
-                    package: self.tool.clone(),
-                })?;
+    ///
+    /// Returns the config of the linked package
+    fn check_linked_package(&self, session: &mut Session) -> Fallible<PackageConfig> {
+        let config = PackageConfig::from_file(active_profile()?.package_config_file(&self.tool))
+            .with_context(|| ErrorKind::NpmLinkMissingPackage {
+                package: self.tool.clone(),
+            })?;
 
         if config.manager != PackageManager::Npm {
             return Err(ErrorKind::NpmLinkWrongManager {
@@ -392,7 +397,7 @@ impl PackageLinkCommand {
             }
         }
 
-        Ok(())
+        Ok(config)
     }
 }
 
@@ -430,6 +435,7 @@ impl PackageUpgradeCommand {
 
         let mut command = match manager {
             PackageManager::Npm => create_command("npm"),
+            PackageManager::Pnpm => create_command("pnpm"),
             PackageManager::Yarn => create_command("yarn"),
         };
         command.args(args);
@@ -470,7 +476,7 @@ impl PackageUpgradeCommand {
 
         self.command.env(RECURSION_ENV_VAR, "1");
         self.command.env("PATH", path);
-        self.upgrader.setup_command(&mut self.command);
+        self.upgrader.setup_command(&mut self.command)?;
 
         let status = self
             .command
@@ -539,14 +545,14 @@ impl UninstallCommand {
     }
 
     /// Runs the uninstall with Volta's internal uninstall logic
-    fn execute(self) -> Fallible<ExitStatus> {
+    fn execute(self, session: &mut Session) -> Fallible<ExitStatus> {
         info!(
             "{} using Volta to uninstall {}",
             note_prefix(),
             self.tool.name()
         );
 
-        self.tool.uninstall()?;
+        self.tool.uninstall(session, false)?;
 
         Ok(ExitStatus::from_raw(0))
     }
@@ -220,11 +220,11 @@ impl<'a> InstallArgs<'a> {
         let mut executors = Vec::with_capacity(self.tools.len());
 
         for tool in self.tools {
-            // External tool installs may be in a form that doesn't match a `Spec` (such as a git
-            // URL or path to a tarball). If parsing into a `Spec` fails, we assume that it's a
-            // 3rd-party Tool and attempt to install anyway.
+            // External tool installs may be in a form that doesn't match a `Spec`. If parsing into
+            // a `Spec` fails, or it is the source of a package (such as a git URL or path to a
+            // tarball), we assume that it's a 3rd-party Tool and attempt to install anyway.
             match Spec::try_from_str(&tool.to_string_lossy()) {
-                Ok(Spec::Package(_, _)) | Err(_) => {
+                Ok(Spec::Package(_, _)) | Ok(Spec::PackageSource(_)) | Err(_) => {
                     let platform = platform_spec.as_default();
                     // The args for an individual install command are the common args combined
                     // with the name of the tool.
@@ -293,16 +293,16 @@ impl<'a> UpgradeArgs<'a> {
                         PackageUpgradeCommand::new(args, package, platform, self.manager)?.into(),
                     );
                 }
-                Ok(internal) => {
-                    executors.push(UninstallCommand::new(internal).into());
-                }
-                Err(_) => {
+                Ok(Spec::PackageSource(_)) | Err(_) => {
                     return Err(ErrorKind::UpgradePackageNotFound {
                         package: tool.to_string_lossy().to_string(),
                         manager: self.manager,
                     }
                     .into())
                 }
+                Ok(internal) => {
+                    executors.push(UninstallCommand::new(internal).into());
+                }
             }
         }
 
@@ -1,5 +1,7 @@
 use std::env;
 use std::ffi::OsString;
+use std::iter::once;
+use std::path::PathBuf;
 
 use super::executor::{Executor, ToolCommand, ToolKind};
 use super::parser::CommandArg;
@@ -7,6 +9,7 @@ use super::{debug_active_image, debug_no_platform, RECURSION_ENV_VAR};
 use crate::error::{ErrorKind, Fallible};
 use crate::platform::{Platform, Source, System};
 use crate::session::{ActivityKind, Session};
+use log::debug;
 
 /// Build an `Executor` for Yarn
 ///
@@ -29,13 +32,33 @@ pub(super) fn command(args: &[OsString], session: &mut Session) -> Fallible<Exec
                 }
             }
 
-            Platform::current(session)?
+            let platform = Platform::current(session)?;
+
+            // A project that vendors its Yarn release with `yarnPath` can still run it without
+            // a Yarn version in the platform, by executing the release directly with Node
+            if matches!(&platform, Some(plat) if plat.yarn.is_none()) {
+                if let Some(yarn_path) = project_yarn_path(session)? {
+                    debug!("Using yarnPath '{}' from .yarnrc.yml", yarn_path.display());
+                    let args = once(yarn_path.into_os_string()).chain(args.iter().cloned());
+                    return Ok(ToolCommand::new("node", args, platform, ToolKind::Node).into());
+                }
+            }
+
+            platform
         }
     };
 
     Ok(ToolCommand::new("yarn", args, platform, ToolKind::Yarn).into())
 }
 
+/// Find the Yarn release vendored with `yarnPath` in the current project, if any
+fn project_yarn_path(session: &Session) -> Fallible<Option<PathBuf>> {
+    match session.project()? {
+        Some(project) => project.yarn_path(),
+        None => Ok(None),
+    }
+}
+
 /// Determine the execution context (PATH and failure error message) for Yarn
 pub(super) fn execution_context(
     platform: Option<Platform>,
@@ -63,7 +86,7 @@ fn validate_platform_yarn(platform: &Platform) -> Fallible<()> {
     match &platform.yarn {
         Some(_) => Ok(()),
         None => match platform.node.source {
-            Source::Project => Err(ErrorKind::NoProjectYarn.into()),
+            Source::Project | Source::VersionFile => Err(ErrorKind::NoProjectYarn.into()),
             Source::Default | Source::Binary => Err(ErrorKind::NoDefaultYarn.into()),
             Source::CommandLine => Err(ErrorKind::NoCommandLineYarn.into()),
         },
@@ -18,13 +18,22 @@ pub enum ActivityKind {
     Fetch,
     Install,
     Uninstall,
+    Reinstall,
+    Upgrade,
+    Sync,
+    Export,
+    Profile,
     List,
     Current,
     Default,
     Pin,
+    Prune,
+    Cache,
     Node,
     Npm,
     Npx,
+    Pnpm,
+    Pnpx,
     Yarn,
     Volta,
     Tool,
@@ -35,6 +44,7 @@ pub enum ActivityKind {
     Completions,
     Which,
     Setup,
+    Doctor,
     Run,
 }
 
@@ -44,13 +54,22 @@ impl Display for ActivityKind {
             ActivityKind::Fetch => "fetch",
             ActivityKind::Install => "install",
             ActivityKind::Uninstall => "uninstall",
+            ActivityKind::Reinstall => "reinstall",
+            ActivityKind::Upgrade => "upgrade",
+            ActivityKind::Sync => "sync",
+            ActivityKind::Export => "export",
+            ActivityKind::Profile => "profile",
             ActivityKind::List => "list",
             ActivityKind::Current => "current",
             ActivityKind::Default => "default",
             ActivityKind::Pin => "pin",
+            ActivityKind::Prune => "prune",
+            ActivityKind::Cache => "cache",
             ActivityKind::Node => "node",
             ActivityKind::Npm => "npm",
             ActivityKind::Npx => "npx",
+            ActivityKind::Pnpm => "pnpm",
+            ActivityKind::Pnpx => "pnpx",
             ActivityKind::Yarn => "yarn",
             ActivityKind::Volta => "volta",
             ActivityKind::Tool => "tool",
@@ -58,6 +77,7 @@ impl Display for ActivityKind {
             ActivityKind::Version => "version",
             ActivityKind::Binary => "binary",
             ActivityKind::Setup => "setup",
+            ActivityKind::Doctor => "doctor",
             ActivityKind::Shim => "shim",
             ActivityKind::Completions => "completions",
             ActivityKind::Which => "which",
@@ -111,7 +131,7 @@ impl Session {
     /// Returns the current project's pinned platform image, if any.
     pub fn project_platform(&self) -> Fallible<Option<&PlatformSpec>> {
         if let Some(ref project) = self.project()? {
-            return Ok(project.platform());
+            return project.platform();
         }
         Ok(None)
     }
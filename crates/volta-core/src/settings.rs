@@ -0,0 +1,100 @@
+//! Provides the user settings, which are read from the `settings.json` file in the Volta directory
+
+use std::fs::File;
+use std::path::Path;
+
+use crate::error::{Context, ErrorKind, Fallible};
+use crate::layout::volta_home;
+use crate::tool::package::PackageManager;
+use log::warn;
+use serde::Deserialize;
+
+/// The settings that customize Volta for the current user
+pub struct Settings {
+    package_manager: PackageManager,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            package_manager: PackageManager::Npm,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawSettings {
+    package_manager: Option<String>,
+}
+
+impl Settings {
+    /// Load the settings of the current user, using the defaults if there is no settings file
+    pub fn current() -> Fallible<Self> {
+        Settings::from_file(volta_home()?.settings_file())
+    }
+
+    /// The package manager used to install global packages, unless another is requested
+    pub fn package_manager(&self) -> PackageManager {
+        self.package_manager
+    }
+
+    fn from_file(file_path: &Path) -> Fallible<Self> {
+        if !file_path.is_file() {
+            return Ok(Settings::default());
+        }
+
+        let file = File::open(file_path).with_context(|| ErrorKind::ReadSettingsError {
+            file: file_path.to_path_buf(),
+        })?;
+
+        let parse_error = || ErrorKind::ParseSettingsError {
+            file: file_path.to_path_buf(),
+        };
+        let raw: RawSettings = serde_json::de::from_reader(file).with_context(parse_error)?;
+
+        // Note: An unknown package manager falls back to the default with a warning, since the
+        // settings are read by shims, which shouldn't all fail because of it
+        let package_manager = match raw.package_manager {
+            Some(name) => name.parse().unwrap_or_else(|error| {
+                warn!("{} in '{}', using npm", error, file_path.display());
+                PackageManager::Npm
+            }),
+            None => PackageManager::Npm,
+        };
+
+        Ok(Settings { package_manager })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn fixture_path(fixture: &str) -> PathBuf {
+        let mut cargo_manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        cargo_manifest_dir.push("fixtures");
+        cargo_manifest_dir.push("settings");
+        cargo_manifest_dir.push(fixture);
+        cargo_manifest_dir
+    }
+
+    #[test]
+    fn test_package_manager() {
+        let settings = Settings::from_file(&fixture_path("pnpm.json")).unwrap();
+        assert_eq!(settings.package_manager(), PackageManager::Pnpm);
+    }
+
+    #[test]
+    fn test_missing_file_uses_npm() {
+        let settings = Settings::from_file(&fixture_path("missing.json")).unwrap();
+        assert_eq!(settings.package_manager(), PackageManager::Npm);
+    }
+
+    #[test]
+    fn test_unknown_package_manager_uses_npm() {
+        let settings = Settings::from_file(&fixture_path("unknown-manager.json")).unwrap();
+        assert_eq!(settings.package_manager(), PackageManager::Npm);
+    }
+}
@@ -34,6 +34,8 @@ fn get_shim_list_deduped(dir: &Path) -> Fallible<HashSet<String>> {
         shims.insert("node".into());
         shims.insert("npm".into());
         shims.insert("npx".into());
+        shims.insert("pnpm".into());
+        shims.insert("pnpx".into());
         shims.insert("yarn".into());
         Ok(shims)
     }
@@ -0,0 +1,281 @@
+//! Provides management of the cached tool archives and the cached Node index
+//!
+//! The archives in the inventory are only used to avoid downloading a tool again, so they can be
+//! listed, verified, and removed independently of the unpacked images.
+
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::SystemTime;
+
+use super::node;
+use super::uninstall::{Kind, KINDS};
+use crate::error::{Context, ErrorKind, Fallible};
+use crate::fs::{create_staging_dir, read_dir_eager, read_file, remove_file_if_exists};
+use crate::layout::volta_home;
+use crate::session::Session;
+use crate::style::success_prefix;
+use crate::sync::VoltaLock;
+use crate::tool::offline::is_offline;
+use archive::{Archive, ArchiveError, Tarball};
+use hyperx::header::HttpDate;
+use indicatif::HumanBytes;
+use log::{debug, info, warn};
+
+/// The file extensions used by the archives of every tool
+const ARCHIVE_EXTENSIONS: [&str; 3] = [".tar.gz", ".tgz", ".zip"];
+
+/// A tool archive in the inventory
+struct CachedArchive {
+    kind: Kind,
+    path: PathBuf,
+    size: u64,
+}
+
+/// Lists every cached archive, followed by the state of the cached Node index
+pub fn list() -> Fallible<()> {
+    let archives = cached_archives()?;
+
+    if archives.is_empty() {
+        info!("No cached archives");
+    }
+
+    for archive in archives {
+        info!(
+            "{} {} ({})",
+            archive.kind.name(),
+            archive.path.display(),
+            HumanBytes(archive.size)
+        );
+    }
+
+    let home = volta_home()?;
+    let index_file = home.node_index_file();
+    if index_file.is_file() {
+        let expiry = match index_expiry()? {
+            Some(expiry) if expiry > SystemTime::now() => {
+                format!("expires {}", HttpDate::from(expiry))
+            }
+            Some(_) => String::from("expired"),
+            None => String::from("no expiry"),
+        };
+        info!(
+            "Node index {} ({}, {})",
+            index_file.display(),
+            HumanBytes(file_size(index_file)),
+            expiry
+        );
+    } else {
+        info!("No cached Node index");
+    }
+
+    Ok(())
+}
+
+/// Reports the disk space used by the cached archives of each tool and by the Node index
+pub fn size() -> Fallible<()> {
+    let archives = cached_archives()?;
+    let mut total = 0;
+
+    for &kind in KINDS.iter() {
+        let (count, size) = archives
+            .iter()
+            .filter(|archive| archive.kind == kind)
+            .fold((0, 0), |(count, size), archive| {
+                (count + 1, size + archive.size)
+            });
+
+        if count > 0 {
+            info!(
+                "{}: {} {} ({})",
+                kind.name(),
+                count,
+                archive_noun(count),
+                HumanBytes(size)
+            );
+            total += size;
+        }
+    }
+
+    let index_size = file_size(volta_home()?.node_index_file());
+    if index_size > 0 {
+        info!("Node index: {}", HumanBytes(index_size));
+        total += index_size;
+    }
+
+    info!("{} total", HumanBytes(total));
+    Ok(())
+}
+
+/// Removes every cached archive, leaving the unpacked images in place
+///
+/// If `index` is set, the cached Node index and its expiry are removed as well, so the index
+/// will be fetched again the next time it is needed.
+pub fn clean(index: bool) -> Fallible<()> {
+    // Acquire a lock on the Volta directory, if possible, to prevent concurrent changes
+    let _lock = VoltaLock::acquire();
+    let archives = cached_archives()?;
+    let mut total = archives.iter().map(|archive| archive.size).sum();
+
+    for archive in &archives {
+        remove_file_if_exists(&archive.path)?;
+    }
+
+    if index {
+        let home = volta_home()?;
+        total += file_size(home.node_index_file());
+        remove_file_if_exists(home.node_index_file())?;
+        remove_file_if_exists(home.node_index_expiry_file())?;
+    }
+
+    info!(
+        "{} removed {} cached {} ({} freed)",
+        success_prefix(),
+        archives.len(),
+        archive_noun(archives.len()),
+        HumanBytes(total)
+    );
+    Ok(())
+}
+
+/// Checks that every cached archive can be unpacked, removing any that are corrupt
+///
+/// Archives are verified when they are downloaded, so a cached archive that was corrupted later on
+/// would otherwise only be detected when it fails to unpack. If `refresh_index` is set, the Node
+/// index is fetched again, replacing the cached copy even if it hasn't expired.
+pub fn verify(session: &mut Session, refresh_index: bool) -> Fallible<()> {
+    // Acquire a lock on the Volta directory, if possible, to prevent concurrent changes
+    let _lock = VoltaLock::acquire();
+    let archives = cached_archives()?;
+    let mut corrupt = 0;
+
+    for archive in &archives {
+        if let Err(error) = check_archive(archive)? {
+            warn!(
+                "Removing corrupt archive '{}': {}",
+                archive.path.display(),
+                error
+            );
+            remove_file_if_exists(&archive.path)?;
+            corrupt += 1;
+        }
+    }
+
+    match corrupt {
+        0 => info!(
+            "{} verified {} cached {}",
+            success_prefix(),
+            archives.len(),
+            archive_noun(archives.len())
+        ),
+        _ => info!(
+            "Verified {} cached {}, {} corrupt {} removed",
+            archives.len(),
+            archive_noun(archives.len()),
+            corrupt,
+            archive_noun(corrupt)
+        ),
+    }
+
+    if refresh_index {
+        if is_offline() {
+            warn!("Skipping refresh of the Node index while offline");
+        } else {
+            node::refresh_index(session)?;
+            info!("{} refreshed the Node index", success_prefix());
+        }
+    }
+
+    Ok(())
+}
+
+/// Collect every archive in the inventory directories of the tools
+fn cached_archives() -> Fallible<Vec<CachedArchive>> {
+    let home = volta_home()?;
+    let mut archives = Vec::new();
+
+    for &kind in KINDS.iter() {
+        let dir = match kind {
+            Kind::Node => home.node_inventory_dir(),
+            Kind::Npm => home.npm_inventory_dir(),
+            Kind::Pnpm => home.pnpm_inventory_dir(),
+            Kind::Yarn => home.yarn_inventory_dir(),
+        };
+
+        // Note: Volta directories created before pnpm support won't have a pnpm inventory
+        if !dir.exists() {
+            continue;
+        }
+
+        let entries = read_dir_eager(dir).with_context(|| ErrorKind::ReadDirError {
+            dir: dir.to_owned(),
+        })?;
+
+        let mut found: Vec<_> = entries
+            .filter(|(_, metadata)| metadata.is_file())
+            .filter(|(entry, _)| is_archive(&entry.path()))
+            .map(|(entry, metadata)| CachedArchive {
+                kind,
+                path: entry.path(),
+                size: metadata.len(),
+            })
+            .collect();
+        found.sort_by(|a, b| a.path.cmp(&b.path));
+        archives.extend(found);
+    }
+
+    Ok(archives)
+}
+
+/// Determine whether a file in the inventory is an archive, rather than other metadata such as
+/// the bundled npm version of a Node version
+fn is_archive(path: &Path) -> bool {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    ARCHIVE_EXTENSIONS.iter().any(|ext| name.ends_with(ext))
+}
+
+/// Re-open an archive and unpack it into a staging directory, to detect corruption
+///
+/// The outer `Result` holds errors with the staging directory, while the inner one holds the
+/// error that shows the archive is corrupt.
+fn check_archive(archive: &CachedArchive) -> Fallible<Result<(), ArchiveError>> {
+    debug!("Verifying cached archive at '{}'", archive.path.display());
+    let staging = create_staging_dir()?;
+
+    Ok(File::open(&archive.path)
+        .map_err(ArchiveError::from)
+        .and_then(|file| match archive.kind {
+            Kind::Node => archive::load_native(file),
+            // Note: The archives of the package managers are npm tarballs on every platform
+            Kind::Npm | Kind::Pnpm | Kind::Yarn => Tarball::load(file),
+        })
+        .and_then(|loaded: Box<dyn Archive>| loaded.unpack(staging.path(), &mut |_, _| {})))
+}
+
+/// Read the time at which the cached Node index expires, if there is one
+fn index_expiry() -> Fallible<Option<SystemTime>> {
+    let expiry_file = volta_home()?.node_index_expiry_file();
+    let expiry = read_file(expiry_file).with_context(|| ErrorKind::ReadNodeIndexExpiryError {
+        file: expiry_file.to_owned(),
+    })?;
+
+    expiry
+        .map(|date| {
+            HttpDate::from_str(&date)
+                .map(SystemTime::from)
+                .with_context(|| ErrorKind::ParseNodeIndexExpiryError)
+        })
+        .transpose()
+}
+
+/// The size of a file, or 0 if it doesn't exist
+fn file_size(path: &Path) -> u64 {
+    path.metadata().map(|metadata| metadata.len()).unwrap_or(0)
+}
+
+fn archive_noun(count: usize) -> &'static str {
+    match count {
+        1 => "archive",
+        _ => "archives",
+    }
+}
@@ -0,0 +1,61 @@
+//! Provides launcher scripts for tools that only ship a JavaScript entry point
+
+use std::fs::write;
+use std::path::Path;
+
+use crate::error::{Context, ErrorKind, Fallible};
+use crate::fs::set_executable;
+
+/// Write the launcher script(s) for the given tool, which run `entry_point` with Node
+///
+/// The entry point is resolved relative to the directory containing the launchers.
+pub(super) fn write_launchers(base_path: &Path, tool: &str, entry_point: &str) -> Fallible<()> {
+    write_launcher(base_path, tool, entry_point)?;
+
+    #[cfg(windows)]
+    write_cmd_launcher(base_path, tool, entry_point)?;
+
+    Ok(())
+}
+
+/// Write the launcher script
+fn write_launcher(base_path: &Path, tool: &str, entry_point: &str) -> Fallible<()> {
+    let path = base_path.join(tool);
+    write(
+        &path,
+        // Note: Adapted from the npm/npx launcher, calling the entry point directly
+        format!(
+            r#"#!/bin/sh
+(set -o igncr) 2>/dev/null && set -o igncr; # cygwin encoding fix
+
+basedir=`dirname "$0"`
+
+case `uname` in
+    *CYGWIN*) basedir=`cygpath -w "$basedir"`;;
+esac
+
+node "$basedir/{}" "$@"
+"#,
+            entry_point
+        ),
+    )
+    .and_then(|_| set_executable(&path))
+    .with_context(|| ErrorKind::WriteLauncherError { tool: tool.into() })
+}
+
+/// Write the CMD launcher
+#[cfg(windows)]
+fn write_cmd_launcher(base_path: &Path, tool: &str, entry_point: &str) -> Fallible<()> {
+    write(
+        base_path.join(format!("{}.cmd", tool)),
+        // Note: Adapted from the npm/npx cmd launcher, calling the entry point directly
+        format!(
+            r#"@ECHO OFF
+
+node "%~dp0\{}" %*
+"#,
+            entry_point
+        ),
+    )
+    .with_context(|| ErrorKind::WriteLauncherError { tool: tool.into() })
+}
@@ -7,19 +7,32 @@ use crate::sync::VoltaLock;
 use crate::version::VersionSpec;
 use log::{debug, info};
 
+pub mod cache;
+mod launcher;
 pub mod node;
 pub mod npm;
+mod offline;
+mod outdated;
 pub mod package;
+pub mod pnpm;
+mod prune;
 mod registry;
 mod serial;
+mod uninstall;
+mod upgrade;
 pub mod yarn;
 
 pub use node::{
     load_default_npm_version, Node, NODE_DISTRO_ARCH, NODE_DISTRO_EXTENSION, NODE_DISTRO_OS,
 };
 pub use npm::{BundledNpm, Npm};
-pub use package::{BinConfig, Package, PackageConfig, PackageManifest};
+pub use offline::{enable_offline, is_offline};
+pub use outdated::Updates;
+pub use package::{BinConfig, Package, PackageConfig, PackageManager, PackageManifest};
+pub use pnpm::Pnpm;
+pub use prune::prune;
 pub use registry::PackageDetails;
+pub use upgrade::upgrade;
 pub use yarn::Yarn;
 
 #[inline]
@@ -67,8 +80,11 @@ pub trait Tool: Display {
 pub enum Spec {
     Node(VersionSpec),
     Npm(VersionSpec),
+    Pnpm(VersionSpec),
     Yarn(VersionSpec),
     Package(String, VersionSpec),
+    /// A package from a git URL, tarball, or local directory, rather than the package registry
+    PackageSource(String),
 }
 
 impl Spec {
@@ -79,42 +95,30 @@ impl Spec {
                 let version = node::resolve(version, session)?;
                 Ok(Box::new(Node::new(version)))
             }
-            Spec::Npm(version) => match npm::resolve(version, session)? {
-                Some(version) => Ok(Box::new(Npm::new(version))),
+            Spec::Npm(version) => match npm::resolve_npm(version, session)? {
+                Some(npm) => Ok(Box::new(npm)),
                 None => Ok(Box::new(BundledNpm)),
             },
-            Spec::Yarn(version) => {
-                let version = yarn::resolve(version, session)?;
-                Ok(Box::new(Yarn::new(version)))
-            }
+            Spec::Pnpm(version) => Ok(Box::new(pnpm::resolve_pnpm(version, session)?)),
+            Spec::Yarn(version) => Ok(Box::new(yarn::resolve_yarn(version, session)?)),
             // When using global package install, we allow the package manager to perform the version resolution
             Spec::Package(name, version) => {
                 let package = Package::new(name, version)?;
                 Ok(Box::new(package))
             }
+            Spec::PackageSource(source) => {
+                let package = Package::from_source(source, PackageManager::Npm)?;
+                Ok(Box::new(package))
+            }
         }
     }
 
     /// Uninstall a tool, removing it from the local inventory
     ///
-    /// This is implemented on Spec, instead of Resolved, because there is currently no need to
-    /// resolve the specific version before uninstalling a tool.
-    pub fn uninstall(self) -> Fallible<()> {
-        match self {
-            Spec::Node(_) => Err(ErrorKind::Unimplemented {
-                feature: "Uninstalling node".into(),
-            }
-            .into()),
-            Spec::Npm(_) => Err(ErrorKind::Unimplemented {
-                feature: "Uninstalling npm".into(),
-            }
-            .into()),
-            Spec::Yarn(_) => Err(ErrorKind::Unimplemented {
-                feature: "Uninstalling yarn".into(),
-            }
-            .into()),
-            Spec::Package(name, _) => package::uninstall(&name),
-        }
+    /// Node, npm, pnpm, and Yarn require an exact version, and will not be removed if they are
+    /// still used by the default platform or an installed package, unless `force` is set.
+    pub fn uninstall(self, session: &mut Session, force: bool) -> Fallible<()> {
+        uninstall::uninstall(self, session, force)
     }
 
     /// The name of the tool, without the version, used for messaging
@@ -122,8 +126,10 @@ impl Spec {
         match self {
             Spec::Node(_) => "Node",
             Spec::Npm(_) => "npm",
+            Spec::Pnpm(_) => "pnpm",
             Spec::Yarn(_) => "Yarn",
             Spec::Package(name, _) => &name,
+            Spec::PackageSource(source) => source,
         }
     }
 }
@@ -133,8 +139,10 @@ impl Display for Spec {
         let s = match self {
             Spec::Node(ref version) => tool_version("node", version),
             Spec::Npm(ref version) => tool_version("npm", version),
+            Spec::Pnpm(ref version) => tool_version("pnpm", version),
             Spec::Yarn(ref version) => tool_version("yarn", version),
             Spec::Package(ref name, ref version) => tool_version(name, version),
+            Spec::PackageSource(ref source) => source.clone(),
         };
         f.write_str(&s)
     }
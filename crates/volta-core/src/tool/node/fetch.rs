@@ -1,22 +1,28 @@
 //! Provides fetcher for Node distributions
 
 use std::fs::{read_to_string, write, File};
+use std::io::{self, copy};
 use std::path::{Path, PathBuf};
 
 use super::NodeVersion;
 use crate::error::{Context, ErrorKind, Fallible};
-use crate::fs::{create_staging_dir, create_staging_file, rename};
+use crate::fs::{create_staging_dir, create_staging_file, remove_file_if_exists, rename};
 use crate::hook::ToolHooks;
 use crate::layout::volta_home;
 use crate::style::{progress_bar, tool_version};
-use crate::tool::{self, download_tool_error, Node};
+use crate::tool::{self, download_tool_error, is_offline, Node};
 use crate::version::{parse_version, VersionSpec};
 use archive::{self, Archive};
+use attohttpc::Response;
 use cfg_if::cfg_if;
 use fs_utils::ensure_containing_dir_exists;
 use log::debug;
 use semver::Version;
 use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+/// The name of the checksum file published alongside each Node distro
+const CHECKSUMS_FILE_NAME: &str = "SHASUMS256.txt";
 
 cfg_if! {
     if #[cfg(feature = "mock-network")] {
@@ -48,43 +54,81 @@ pub fn fetch(version: &Version, hooks: Option<&ToolHooks<Node>>) -> Fallible<Nod
     let node_dir = home.node_inventory_dir();
     let cache_file = node_dir.join(Node::archive_filename(version));
 
-    let (archive, staging) = match load_cached_distro(&cache_file) {
-        Some(archive) => {
-            debug!(
-                "Loading {} from cached archive at '{}'",
-                tool_version("node", &version),
-                cache_file.display()
-            );
-            (archive, None)
+    // Note: In offline mode, the published checksums can't be fetched, so a cached archive is used
+    // without verifying it
+    let remote = if is_offline() {
+        None
+    } else {
+        let remote_url = determine_remote_url(version, hooks)?;
+        let checksum = fetch_checksum(version, &remote_url)?;
+        Some((remote_url, checksum))
+    };
+
+    let cached_checksum = remote.as_ref().map(|(_, checksum)| checksum.as_str());
+    if let Some(archive) = load_cached_distro(&cache_file, cached_checksum) {
+        debug!(
+            "Loading {} from cached archive at '{}'",
+            tool_version("node", version),
+            cache_file.display()
+        );
+        match unpack_archive(archive, version, || Ok(())) {
+            Err(error) if matches!(error.kind(), ErrorKind::UnpackArchiveError { .. }) => {
+                debug!(
+                    "Cached archive at '{}' could not be unpacked, removing",
+                    cache_file.display()
+                );
+                // Note: Failing to remove the file isn't fatal, since it will be replaced by the
+                // new download in any case
+                let _ = remove_file_if_exists(&cache_file);
+            }
+            result => return result,
         }
+    }
+
+    // Note: Offline mode can still use an archive from the inventory, but not download one
+    let (remote_url, checksum) = match remote {
+        Some(remote) => remote,
         None => {
-            let staging = create_staging_file()?;
-            let remote_url = determine_remote_url(&version, hooks)?;
-            let archive = fetch_remote_distro(&version, &remote_url, staging.path())?;
-            (archive, Some(staging))
+            return Err(ErrorKind::OfflineFetchError {
+                tool: tool::Spec::Node(VersionSpec::Exact(version.clone())),
+            }
+            .into())
         }
     };
 
-    let node_version = unpack_archive(archive, version)?;
+    let staging = create_staging_file()?;
+    let archive = fetch_remote_distro(version, &remote_url, staging.path())?;
 
-    if let Some(staging_file) = staging {
-        ensure_containing_dir_exists(&cache_file).with_context(|| {
-            ErrorKind::ContainingDirError {
-                path: cache_file.clone(),
-            }
+    // Note: The archive is streamed to the staging file while it is unpacked, so it can only be
+    // verified once unpacking is complete, before the image is moved into place
+    let node_version = unpack_archive(archive, version, || {
+        verify_download(staging.path(), &checksum, version, &remote_url)
+    })?;
+
+    ensure_containing_dir_exists(&cache_file).with_context(|| ErrorKind::ContainingDirError {
+        path: cache_file.clone(),
+    })?;
+    staging
+        .persist(cache_file)
+        .with_context(|| ErrorKind::PersistInventoryError {
+            tool: "Node".into(),
         })?;
-        staging_file
-            .persist(cache_file)
-            .with_context(|| ErrorKind::PersistInventoryError {
-                tool: "Node".into(),
-            })?;
-    }
 
     Ok(node_version)
 }
 
 /// Unpack the node archive into the image directory so that it is ready for use
-fn unpack_archive(archive: Box<dyn Archive>, version: &Version) -> Fallible<NodeVersion> {
+///
+/// The `verify` callback is run after the archive has been unpacked into a staging directory and
+/// before it is moved into the image directory.
+fn unpack_archive<F>(
+    archive: Box<dyn Archive>,
+    version: &Version,
+    verify: F,
+) -> Fallible<NodeVersion>
+where
+    F: FnOnce() -> Fallible<()>,
+{
     let temp = create_staging_dir()?;
     debug!("Unpacking node into '{}'", temp.path().display());
 
@@ -106,6 +150,8 @@ fn unpack_archive(archive: Box<dyn Archive>, version: &Version) -> Fallible<Node
             version: version_string.clone(),
         })?;
 
+    verify()?;
+
     // Save the npm version number in the npm version file for this distro
     let npm_package_json = temp.path().join(npm_manifest_path(version));
     let npm = Manifest::version(&npm_package_json)?;
@@ -135,18 +181,107 @@ fn unpack_archive(archive: Box<dyn Archive>, version: &Version) -> Fallible<Node
     })
 }
 
-/// Return the archive if it is valid. It may have been corrupted or interrupted in the middle of
-/// downloading.
-// ISSUE(#134) - verify checksum
-fn load_cached_distro(file: &Path) -> Option<Box<dyn Archive>> {
+/// Return the archive if it can be loaded. It may have been corrupted or interrupted in the middle
+/// of downloading, in which case it is removed so that it will be fetched again.
+///
+/// If a checksum is given, an archive that doesn't match it is removed as well.
+fn load_cached_distro(file: &Path, checksum: Option<&str>) -> Option<Box<dyn Archive>> {
     if file.is_file() {
-        let file = File::open(file).ok()?;
-        archive::load_native(file).ok()
+        if let Some(checksum) = checksum {
+            if !matches!(file_checksum(file), Ok(ref actual) if actual == checksum) {
+                debug!(
+                    "Cached archive at '{}' does not match the published checksum, removing",
+                    file.display()
+                );
+                let _ = remove_file_if_exists(file);
+                return None;
+            }
+        }
+
+        let archive = File::open(file)
+            .ok()
+            .and_then(|f| archive::load_native(f).ok());
+        if archive.is_none() {
+            debug!(
+                "Cached archive at '{}' could not be loaded, removing",
+                file.display()
+            );
+            // Note: Failing to remove the file isn't fatal, since it will be replaced by the
+            // new download in any case
+            let _ = remove_file_if_exists(file);
+        }
+        archive
     } else {
         None
     }
 }
 
+/// Determine the URL of the checksum file, which is published in the same directory as the distro
+fn checksums_url(distro_url: &str) -> String {
+    match distro_url.rfind('/') {
+        Some(index) => format!("{}/{}", &distro_url[..index], CHECKSUMS_FILE_NAME),
+        None => CHECKSUMS_FILE_NAME.to_string(),
+    }
+}
+
+/// Fetch the published checksum for the distro of the given Node version
+fn fetch_checksum(version: &Version, distro_url: &str) -> Fallible<String> {
+    let url = checksums_url(distro_url);
+    debug!(
+        "Fetching checksums for {} from {}",
+        tool_version("node", version),
+        url
+    );
+
+    let checksums = attohttpc::get(&url)
+        .send()
+        .and_then(Response::error_for_status)
+        .and_then(Response::text)
+        .with_context(|| ErrorKind::ChecksumFetchError {
+            tool: tool::Spec::Node(VersionSpec::Exact(version.clone())),
+            from_url: url.clone(),
+        })?;
+
+    find_checksum(&checksums, &Node::archive_filename(version)).ok_or_else(|| {
+        ErrorKind::ChecksumNotFound {
+            tool: tool::Spec::Node(VersionSpec::Exact(version.clone())),
+            from_url: url,
+        }
+        .into()
+    })
+}
+
+/// Find the checksum for the given file name in the contents of a `SHASUMS256.txt` file
+fn find_checksum(checksums: &str, filename: &str) -> Option<String> {
+    checksums.lines().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        match (parts.next(), parts.next()) {
+            (Some(checksum), Some(name)) if name == filename => Some(checksum.to_lowercase()),
+            _ => None,
+        }
+    })
+}
+
+/// Calculate the SHA-256 checksum of a file as a lowercase hex string
+fn file_checksum(path: &Path) -> io::Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    copy(&mut file, &mut hasher)?;
+    Ok(hex::encode(hasher.result()))
+}
+
+/// Verify that a downloaded distro matches the published checksum
+fn verify_download(path: &Path, checksum: &str, version: &Version, url: &str) -> Fallible<()> {
+    match file_checksum(path) {
+        Ok(ref actual) if actual == checksum => Ok(()),
+        _ => Err(ErrorKind::ChecksumMismatch {
+            tool: tool::Spec::Node(VersionSpec::Exact(version.clone())),
+            from_url: url.into(),
+        }
+        .into()),
+    }
+}
+
 /// Determine the remote URL to download from, using the hooks if available
 fn determine_remote_url(version: &Version, hooks: Option<&ToolHooks<Node>>) -> Fallible<String> {
     let distro_file_name = Node::archive_filename(version);
@@ -215,3 +350,40 @@ fn save_default_npm_version(node: &Version, npm: &Version) -> Fallible<()> {
         }
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHECKSUMS: &str = "\
+0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef  node-v14.17.0-darwin-x64.tar.gz
+FEDCBA9876543210FEDCBA9876543210FEDCBA9876543210FEDCBA9876543210  node-v14.17.0-linux-x64.tar.gz
+";
+
+    #[test]
+    fn test_find_checksum() {
+        assert_eq!(
+            find_checksum(CHECKSUMS, "node-v14.17.0-darwin-x64.tar.gz"),
+            Some("0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef".to_string())
+        );
+        assert_eq!(
+            find_checksum(CHECKSUMS, "node-v14.17.0-linux-x64.tar.gz"),
+            Some("fedcba9876543210fedcba9876543210fedcba9876543210fedcba9876543210".to_string())
+        );
+        assert_eq!(find_checksum(CHECKSUMS, "node-v14.17.0-win-x64.zip"), None);
+    }
+
+    #[test]
+    fn test_checksums_url() {
+        assert_eq!(
+            checksums_url("https://nodejs.org/dist/v14.17.0/node-v14.17.0-linux-x64.tar.gz"),
+            "https://nodejs.org/dist/v14.17.0/SHASUMS256.txt"
+        );
+        assert_eq!(
+            checksums_url(
+                "https://mirror.example.com/node/node-v14.17.0-linux-x64.tar.gz?token=abc"
+            ),
+            "https://mirror.example.com/node/SHASUMS256.txt"
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use super::NODE_DISTRO_IDENTIFIER;
 #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
@@ -15,7 +15,8 @@ pub struct NodeIndex {
 #[derive(Debug)]
 pub struct NodeEntry {
     pub version: Version,
-    pub lts: bool,
+    /// The codename of the LTS line, if this is an LTS release
+    pub lts: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -30,7 +31,7 @@ pub struct RawNodeEntry {
     npm: Option<Version>,
     files: HashSet<String>,
     #[serde(deserialize_with = "lts_version_serde")]
-    lts: bool,
+    lts: Option<String>,
 }
 
 impl From<RawNodeIndex> for NodeIndex {
@@ -68,13 +69,24 @@ impl From<RawNodeIndex> for NodeIndex {
     }
 }
 
+impl RawNodeIndex {
+    /// Map each LTS release in the index to the codename of its LTS line
+    pub(super) fn lts_codenames(self) -> HashMap<Version, String> {
+        self.0
+            .into_iter()
+            .filter_map(|RawNodeEntry { version, lts, .. }| lts.map(|codename| (version, codename)))
+            .collect()
+    }
+}
+
+/// Deserializes the `lts` field, which is either `false` or the codename of the LTS line
 #[allow(clippy::unnecessary_wraps)] // Needs to match the API expected by Serde
-fn lts_version_serde<'de, D>(deserializer: D) -> Result<bool, D::Error>
+fn lts_version_serde<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
 where
     D: Deserializer<'de>,
 {
     match String::deserialize(deserializer) {
-        Ok(_) => Ok(true),
-        Err(_) => Ok(false),
+        Ok(codename) => Ok(Some(codename.to_lowercase())),
+        Err(_) => Ok(None),
     }
 }
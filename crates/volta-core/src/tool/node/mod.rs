@@ -18,7 +18,8 @@ mod metadata;
 mod resolve;
 
 pub use fetch::load_default_npm_version;
-pub use resolve::resolve;
+pub(crate) use resolve::resolve_with_hooks;
+pub use resolve::{cached_lts_codenames, check_updates, refresh_index, resolve};
 
 cfg_if! {
     if #[cfg(all(target_os = "windows", target_arch = "x86"))] {
@@ -224,7 +225,7 @@ impl Tool for Node {
             // If the user has a pinned version of `npm`, we shouldn't show the "(with npm@X.Y.ZZZ)" text in the success message
             // Instead we should check if the bundled version is higher than the pinned and inform the user
             // Note: The pin operation guarantees there will be a platform
-            if let Some(pinned_npm) = &project.platform().unwrap().npm {
+            if let Some(pinned_npm) = &project.platform()?.unwrap().npm {
                 info_pinned(self); // includes node version
 
                 if node_version.npm > *pinned_npm {
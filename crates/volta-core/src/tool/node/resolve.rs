@@ -1,5 +1,6 @@
 //! Provides resolution of Node requirements into specific versions, using the NodeJS index
 
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::Write;
 use std::str::FromStr;
@@ -8,12 +9,14 @@ use std::time::{Duration, SystemTime};
 use super::super::registry_fetch_error;
 use super::metadata::{NodeEntry, NodeIndex, RawNodeIndex};
 use crate::error::{Context, ErrorKind, Fallible};
-use crate::fs::{create_staging_file, read_file};
+use crate::fs::{create_staging_file, read_file, remove_file_if_exists};
 use crate::hook::ToolHooks;
+use crate::inventory::node_versions;
 use crate::layout::volta_home;
 use crate::session::Session;
 use crate::style::progress_spinner;
-use crate::tool::Node;
+use crate::tool::offline::{is_offline, resolve_local};
+use crate::tool::{Node, Updates};
 use crate::version::{VersionSpec, VersionTag};
 use attohttpc::header::HeaderMap;
 use attohttpc::Response;
@@ -38,16 +41,22 @@ cfg_if! {
 }
 
 pub fn resolve(matching: VersionSpec, session: &mut Session) -> Fallible<Version> {
-    let hooks = session.hooks()?.node();
+    resolve_with_hooks(matching, session.hooks()?.node())
+}
+
+/// Resolve a Node version using the given hooks, for use where there is no `Session` available
+pub(crate) fn resolve_with_hooks(
+    matching: VersionSpec,
+    hooks: Option<&ToolHooks<Node>>,
+) -> Fallible<Version> {
     match matching {
+        matching if is_offline() => resolve_offline(matching),
         VersionSpec::Semver(requirement) => resolve_semver(requirement, hooks),
         VersionSpec::Exact(version) => Ok(version),
         VersionSpec::None | VersionSpec::Tag(VersionTag::Lts) => resolve_lts(hooks),
         VersionSpec::Tag(VersionTag::Latest) => resolve_latest(hooks),
-        // Node doesn't have "tagged" versions (apart from 'latest' and 'lts'), so custom tags will always be an error
-        VersionSpec::Tag(VersionTag::Custom(tag)) => {
-            Err(ErrorKind::NodeVersionNotFound { matching: tag }.into())
-        }
+        // Apart from 'latest' and 'lts', the only tags Node has refer to LTS lines
+        VersionSpec::Tag(VersionTag::Custom(tag)) => resolve_lts_line(tag, hooks),
     }
 }
 
@@ -80,17 +89,8 @@ fn resolve_latest(hooks: Option<&ToolHooks<Node>>) -> Fallible<Version> {
 }
 
 fn resolve_lts(hooks: Option<&ToolHooks<Node>>) -> Fallible<Version> {
-    let url = match hooks {
-        Some(&ToolHooks {
-            index: Some(ref hook),
-            ..
-        }) => {
-            debug!("Using node.index hook to determine node index URL");
-            hook.resolve("index.json")?
-        }
-        _ => public_node_version_index(),
-    };
-    let version_opt = match_node_version(&url, |&NodeEntry { lts, .. }| lts)?;
+    let url = index_url(hooks)?;
+    let version_opt = match_node_version(&url, |NodeEntry { lts, .. }| lts.is_some())?;
 
     match version_opt {
         Some(version) => {
@@ -104,17 +104,180 @@ fn resolve_lts(hooks: Option<&ToolHooks<Node>>) -> Fallible<Version> {
     }
 }
 
-fn resolve_semver(matching: VersionReq, hooks: Option<&ToolHooks<Node>>) -> Fallible<Version> {
-    let url = match hooks {
+/// Resolve a tag referring to a specific LTS line
+///
+/// The line can be given by codename, either bare (`hydrogen`) or with a prefix
+/// (`lts/hydrogen`), or relative to the newest LTS line (`lts/-1` for the line before it).
+fn resolve_lts_line(tag: String, hooks: Option<&ToolHooks<Node>>) -> Fallible<Version> {
+    let url = index_url(hooks)?;
+    let index: NodeIndex = resolve_node_versions(&url)?.into();
+
+    match find_lts_line(index, &tag) {
+        Some((codename, version)) => {
+            debug!(
+                "Found newest node version ({}) in LTS line '{}' from {}",
+                version, codename, url
+            );
+            Ok(version)
+        }
+        None => Err(ErrorKind::NodeVersionNotFound { matching: tag }.into()),
+    }
+}
+
+/// Find the codename and newest version of the LTS line matching the given tag
+fn find_lts_line(index: NodeIndex, tag: &str) -> Option<(String, Version)> {
+    let codename = find_lts_codename(&index, tag)?;
+
+    index
+        .entries
+        .into_iter()
+        .find(|entry| entry.lts.as_deref() == Some(codename.as_str()))
+        .map(|entry| (codename, entry.version))
+}
+
+/// Find the codename of the LTS line referred to by the given tag
+fn find_lts_codename(index: &NodeIndex, tag: &str) -> Option<String> {
+    let tag = tag.to_lowercase();
+    let line = tag.strip_prefix("lts/");
+
+    match line.and_then(|line| line.strip_prefix('-')) {
+        Some(offset) => {
+            let offset: usize = offset.parse().ok()?;
+            let mut codenames: Vec<&str> = Vec::new();
+            for codename in index
+                .entries
+                .iter()
+                .filter_map(|entry| entry.lts.as_deref())
+            {
+                if !codenames.contains(&codename) {
+                    codenames.push(codename);
+                }
+            }
+            codenames.get(offset).map(|codename| codename.to_string())
+        }
+        None => Some(line.unwrap_or(&tag).to_string()),
+    }
+}
+
+/// Resolve a Node version from the installed versions, for use in offline mode
+///
+/// Tags are matched using the cached Node index, even if it has expired, since the LTS line of a
+/// version never changes.
+fn resolve_offline(matching: VersionSpec) -> Fallible<Version> {
+    let index = read_cached_index()
+        .map(NodeIndex::from)
+        .unwrap_or(NodeIndex {
+            entries: Vec::new(),
+        });
+    let codename_of = |version: &Version| {
+        index
+            .entries
+            .iter()
+            .find(|entry| &entry.version == version)
+            .and_then(|entry| entry.lts.as_deref())
+    };
+
+    // Note: As when online, no version means the newest LTS version
+    let matching = match matching {
+        VersionSpec::None => VersionSpec::Tag(VersionTag::Lts),
+        matching => matching,
+    };
+
+    resolve_local(
+        "node",
+        matching,
+        node_versions()?,
+        |tag, version| match tag {
+            VersionTag::Latest => true,
+            VersionTag::Lts => codename_of(version).is_some(),
+            VersionTag::Custom(tag) => {
+                let codename = find_lts_codename(&index, tag);
+                codename.is_some() && codename_of(version) == codename.as_deref()
+            }
+        },
+    )
+}
+
+/// Find the versions of Node that are newer than the given version
+///
+/// This shares the cache of the Node index with version resolution, so repeated checks don't
+/// need the network. In offline mode, the cache is used even if it has expired.
+pub fn check_updates(version: &Version, session: &mut Session) -> Fallible<Updates> {
+    let raw_index = if is_offline() {
+        match read_cached_index() {
+            Some(index) => index,
+            None => {
+                debug!("No cached Node index available in offline mode");
+                return Ok(Updates::default());
+            }
+        }
+    } else {
+        resolve_node_versions(&index_url(session.hooks()?.node())?)?
+    };
+
+    let index: NodeIndex = raw_index.into();
+    Ok(Updates::from_versions(
+        version,
+        index
+            .entries
+            .into_iter()
+            .map(|NodeEntry { version, lts }| (version, lts.is_some())),
+    ))
+}
+
+/// Fetch the Node index, replacing the cached copy even if it hasn't expired
+pub fn refresh_index(session: &mut Session) -> Fallible<()> {
+    let url = index_url(session.hooks()?.node())?;
+
+    // Removing the expiry ensures the cached copy is treated as invalid
+    remove_file_if_exists(volta_home()?.node_index_expiry_file())?;
+    resolve_node_versions(&url).map(|_| ())
+}
+
+/// Determine the URL of the full Node index, using the `index` hook if there is one
+fn index_url(hooks: Option<&ToolHooks<Node>>) -> Fallible<String> {
+    match hooks {
         Some(&ToolHooks {
             index: Some(ref hook),
             ..
         }) => {
             debug!("Using node.index hook to determine node index URL");
-            hook.resolve("index.json")?
+            hook.resolve("index.json")
         }
-        _ => public_node_version_index(),
-    };
+        _ => Ok(public_node_version_index()),
+    }
+}
+
+/// Look up the LTS codenames of Node versions from the cached copy of the Node index
+///
+/// This never fetches the index, so versions are missing if the cache doesn't exist or is older
+/// than the version. An expired cache is still used, since the codename of a version never
+/// changes.
+pub fn cached_lts_codenames() -> HashMap<Version, String> {
+    read_cached_index()
+        .map(RawNodeIndex::lts_codenames)
+        .unwrap_or_default()
+}
+
+/// Reads the cached Node index without checking whether it has expired
+fn read_cached_index() -> Option<RawNodeIndex> {
+    let cached = volta_home()
+        .ok()
+        .and_then(|home| read_file(home.node_index_file()).ok().flatten())?;
+
+    // The first line of the cache is the URL the index was fetched from
+    let (_, json) = cached.split_once('\n')?;
+    match serde_json::de::from_str(json) {
+        Ok(index) => Some(index),
+        Err(_) => {
+            debug!("Could not parse the Node index cache, ignoring it");
+            None
+        }
+    }
+}
+
+fn resolve_semver(matching: VersionReq, hooks: Option<&ToolHooks<Node>>) -> Fallible<Version> {
+    let url = index_url(hooks)?;
     let version_opt =
         match_node_version(&url, |NodeEntry { version, .. }| matching.matches(version))?;
 
@@ -270,3 +433,50 @@ fn resolve_node_versions(url: &str) -> Fallible<RawNodeIndex> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::find_lts_line;
+    use crate::tool::node::metadata::{NodeEntry, NodeIndex};
+
+    fn entry(version: &str, lts: Option<&str>) -> NodeEntry {
+        NodeEntry {
+            version: version.parse().unwrap(),
+            lts: lts.map(String::from),
+        }
+    }
+
+    fn index() -> NodeIndex {
+        NodeIndex {
+            entries: vec![
+                entry("20.5.0", None),
+                entry("18.17.1", Some("hydrogen")),
+                entry("18.17.0", Some("hydrogen")),
+                entry("17.9.1", None),
+                entry("16.20.2", Some("gallium")),
+                entry("14.21.3", Some("fermium")),
+            ],
+        }
+    }
+
+    fn find(tag: &str) -> Option<String> {
+        find_lts_line(index(), tag).map(|(codename, version)| format!("{}@{}", codename, version))
+    }
+
+    #[test]
+    fn test_find_lts_line_by_codename() {
+        assert_eq!(find("lts/gallium"), Some("gallium@16.20.2".into()));
+        assert_eq!(find("lts/Hydrogen"), Some("hydrogen@18.17.1".into()));
+        assert_eq!(find("fermium"), Some("fermium@14.21.3".into()));
+        assert_eq!(find("lts/argon"), None);
+    }
+
+    #[test]
+    fn test_find_lts_line_by_offset() {
+        assert_eq!(find("lts/-0"), Some("hydrogen@18.17.1".into()));
+        assert_eq!(find("lts/-1"), Some("gallium@16.20.2".into()));
+        assert_eq!(find("lts/-2"), Some("fermium@14.21.3".into()));
+        assert_eq!(find("lts/-3"), None);
+        assert_eq!(find("-1"), None);
+    }
+}
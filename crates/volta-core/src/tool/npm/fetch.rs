@@ -1,61 +1,125 @@
 //! Provides fetcher for npm distributions
 
-use std::fs::{write, File};
+use std::fs::write;
 use std::path::Path;
 
 use super::super::download_tool_error;
-use super::super::registry::public_registry_package;
+use super::super::registry::{
+    load_cached_tarball, public_registry_package, verify_tarball, version_details, PackageDetails,
+};
+use super::resolve::fetch_npm_index;
 use crate::error::{Context, ErrorKind, Fallible};
-use crate::fs::{create_staging_dir, create_staging_file, rename, set_executable};
+use crate::fs::{
+    create_staging_dir, create_staging_file, remove_file_if_exists, rename, set_executable,
+};
 use crate::hook::ToolHooks;
 use crate::layout::volta_home;
+use crate::project::PackageManagerHash;
 use crate::style::{progress_bar, tool_version};
-use crate::tool::{self, Npm};
+use crate::tool::{self, is_offline, Npm};
 use crate::version::VersionSpec;
 use archive::{Archive, Tarball};
 use fs_utils::ensure_containing_dir_exists;
 use log::debug;
 use semver::Version;
 
-pub fn fetch(version: &Version, hooks: Option<&ToolHooks<Npm>>) -> Fallible<()> {
+/// Fetch the given version, verifying it against the registry integrity and the `hash` from the
+/// project's `packageManager` field, if any
+///
+/// The registry `details` of the version are only looked up if they weren't found when resolving
+/// it, which for a download happens once it is complete.
+pub fn fetch(
+    version: &Version,
+    details: Option<PackageDetails>,
+    hooks: Option<&ToolHooks<Npm>>,
+    hash: Option<&PackageManagerHash>,
+) -> Fallible<()> {
     let npm_dir = volta_home()?.npm_inventory_dir();
     let cache_file = npm_dir.join(Npm::archive_filename(&version.to_string()));
 
-    let (archive, staging) = match load_cached_distro(&cache_file) {
-        Some(archive) => {
-            debug!(
-                "Loading {} from cached archive at '{}'",
-                tool_version("npm", &version),
-                cache_file.display()
-            );
-            (archive, None)
-        }
-        None => {
-            let staging = create_staging_file()?;
-            let remote_url = determine_remote_url(&version, hooks)?;
-            let archive = fetch_remote_distro(&version, &remote_url, staging.path())?;
-            (archive, Some(staging))
-        }
+    // Note: In offline mode, the registry can't be checked, so a cached archive is used without
+    // verifying its integrity
+    let details = match details {
+        _ if is_offline() => None,
+        None if cache_file.is_file() => Some(fetch_details(version, hooks)?),
+        details => details,
     };
 
-    unpack_archive(archive, version)?;
-
-    if let Some(staging_file) = staging {
-        ensure_containing_dir_exists(&cache_file).with_context(|| {
-            ErrorKind::ContainingDirError {
-                path: cache_file.clone(),
+    if let Some(archive) = load_cached_tarball(&cache_file, details.as_ref(), hash) {
+        debug!(
+            "Loading {} from cached archive at '{}'",
+            tool_version("npm", version),
+            cache_file.display()
+        );
+        match unpack_archive(archive, version, || Ok(())) {
+            Err(error) if matches!(error.kind(), ErrorKind::UnpackArchiveError { .. }) => {
+                debug!(
+                    "Cached archive at '{}' could not be unpacked, removing",
+                    cache_file.display()
+                );
+                // Note: Failing to remove the file isn't fatal, since it will be replaced by the
+                // new download in any case
+                let _ = remove_file_if_exists(&cache_file);
             }
-        })?;
-        staging_file
-            .persist(cache_file)
-            .with_context(|| ErrorKind::PersistInventoryError { tool: "npm".into() })?;
+            result => return result,
+        }
     }
 
+    // Note: Offline mode can still use an archive from the inventory, but not download one
+    if is_offline() {
+        return Err(ErrorKind::OfflineFetchError {
+            tool: tool::Spec::Npm(VersionSpec::Exact(version.clone())),
+        }
+        .into());
+    }
+
+    let staging = create_staging_file()?;
+    let remote_url = determine_remote_url(version, hooks)?;
+    let archive = fetch_remote_distro(version, &remote_url, staging.path())?;
+
+    unpack_archive(archive, version, || {
+        let details = match details {
+            Some(details) => details,
+            None => fetch_details(version, hooks)?,
+        };
+        verify_tarball(
+            staging.path(),
+            tool::Spec::Npm(VersionSpec::Exact(version.clone())),
+            &remote_url,
+            &details,
+            hash,
+        )
+    })?;
+
+    ensure_containing_dir_exists(&cache_file).with_context(|| ErrorKind::ContainingDirError {
+        path: cache_file.clone(),
+    })?;
+    staging
+        .persist(cache_file)
+        .with_context(|| ErrorKind::PersistInventoryError { tool: "npm".into() })?;
+
     Ok(())
 }
 
+/// Fetch the registry details of the given version, which are used to verify its tarball
+fn fetch_details(version: &Version, hooks: Option<&ToolHooks<Npm>>) -> Fallible<PackageDetails> {
+    let (url, index) = fetch_npm_index(hooks)?;
+    version_details(
+        tool::Spec::Npm(VersionSpec::Exact(version.clone())),
+        url,
+        index,
+        version,
+    )
+}
+
 /// Unpack the npm archive into the image directory so that it is ready for use
-fn unpack_archive(archive: Box<dyn Archive>, version: &Version) -> Fallible<()> {
+///
+/// The `verify` callback is run after the archive has been unpacked into a staging directory and
+/// before it is moved into the image directory.
+fn unpack_archive<F>(archive: Box<dyn Archive>, version: &Version, verify: F) -> Fallible<()>
+where
+    F: FnOnce() -> Fallible<()>,
+{
     let temp = create_staging_dir()?;
     debug!("Unpacking npm into '{}'", temp.path().display());
 
@@ -77,6 +141,8 @@ fn unpack_archive(archive: Box<dyn Archive>, version: &Version) -> Fallible<()>
             version: version_string.clone(),
         })?;
 
+    verify()?;
+
     let bin_path = temp.path().join("package").join("bin");
     overwrite_launcher(&bin_path, "npm")?;
     overwrite_launcher(&bin_path, "npx")?;
@@ -105,18 +171,6 @@ fn unpack_archive(archive: Box<dyn Archive>, version: &Version) -> Fallible<()>
     Ok(())
 }
 
-/// Return the archive if it is valid. It may have been corrupted or interrupted in the middle of
-/// downloading.
-/// ISSUE(#134) - verify checksum
-fn load_cached_distro(file: &Path) -> Option<Box<dyn Archive>> {
-    if file.is_file() {
-        let file = File::open(file).ok()?;
-        Tarball::load(file).ok()
-    } else {
-        None
-    }
-}
-
 /// Determine the remote URL to download from, using the hooks if avaialble
 fn determine_remote_url(version: &Version, hooks: Option<&ToolHooks<Npm>>) -> Fallible<String> {
     let version_str = version.to_string();
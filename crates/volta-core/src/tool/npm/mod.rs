@@ -1,12 +1,14 @@
 use std::fmt::{self, Display};
 
 use super::node::load_default_npm_version;
+use super::registry::PackageDetails;
 use super::{
     check_fetched, debug_already_fetched, info_fetched, info_installed, info_pinned,
     info_project_version, FetchStatus, Tool,
 };
 use crate::error::{Context, ErrorKind, Fallible};
 use crate::inventory::npm_available;
+use crate::project::PackageManagerKind;
 use crate::session::Session;
 use crate::style::{success_prefix, tool_version};
 use crate::sync::VoltaLock;
@@ -16,16 +18,30 @@ use semver::Version;
 mod fetch;
 mod resolve;
 
-pub use resolve::resolve;
+pub(crate) use resolve::resolve_npm;
+pub use resolve::{check_updates, resolve};
 
 /// The Tool implementation for fetching and installing npm
 pub struct Npm {
     pub(super) version: Version,
+    /// The registry details of the version, if they were looked up when resolving it
+    details: Option<PackageDetails>,
 }
 
 impl Npm {
     pub fn new(version: Version) -> Self {
-        Npm { version }
+        Npm {
+            version,
+            details: None,
+        }
+    }
+
+    /// Create the npm for a version found in the registry, whose details are used to verify it
+    fn from_details(details: PackageDetails) -> Self {
+        Npm {
+            version: details.version.clone(),
+            details: Some(details),
+        }
     }
 
     pub fn archive_basename(version: &str) -> String {
@@ -42,7 +58,17 @@ impl Npm {
                 debug_already_fetched(self);
                 Ok(())
             }
-            FetchStatus::FetchNeeded(_lock) => fetch::fetch(&self.version, session.hooks()?.npm()),
+            FetchStatus::FetchNeeded(_lock) => {
+                let hash = session.project()?.and_then(|project| {
+                    project.package_manager_hash(PackageManagerKind::Npm, &self.version)
+                });
+                fetch::fetch(
+                    &self.version,
+                    self.details.clone(),
+                    session.hooks()?.npm(),
+                    hash,
+                )
+            }
         }
     }
 }
@@ -139,7 +165,7 @@ impl Tool for BundledNpm {
             Some(project) => {
                 project.pin_npm(None)?;
 
-                let bundled_version = match project.platform() {
+                let bundled_version = match project.platform()? {
                     Some(platform) => {
                         let version =
                             load_default_npm_version(&platform.node).with_context(|| {
@@ -1,15 +1,17 @@
 //! Provides resolution of npm Version requirements into specific versions
 
 use super::super::registry::{
-    public_registry_index, PackageDetails, PackageIndex, RawPackageMetadata,
+    find_package_details, public_registry_index, PackageDetails, PackageIndex, RawPackageMetadata,
     NPM_ABBREVIATED_ACCEPT_HEADER,
 };
 use super::super::registry_fetch_error;
 use crate::error::{Context, ErrorKind, Fallible};
 use crate::hook::ToolHooks;
+use crate::inventory::npm_versions;
 use crate::session::Session;
 use crate::style::progress_spinner;
-use crate::tool::Npm;
+use crate::tool::offline::{is_offline, resolve_local};
+use crate::tool::{Npm, Updates};
 use crate::version::{VersionSpec, VersionTag};
 use attohttpc::header::ACCEPT;
 use attohttpc::Response;
@@ -17,19 +19,48 @@ use log::debug;
 use semver::{Version, VersionReq};
 
 pub fn resolve(matching: VersionSpec, session: &mut Session) -> Fallible<Option<Version>> {
+    Ok(resolve_npm(matching, session)?.map(|npm| npm.version))
+}
+
+/// Resolve a version requirement into the npm to fetch, or `None` for the bundled npm
+///
+/// If the version was found in the registry, its details are kept, so that the download can be
+/// verified without fetching the index again.
+pub(crate) fn resolve_npm(matching: VersionSpec, session: &mut Session) -> Fallible<Option<Npm>> {
     let hooks = session.hooks()?.npm();
     match matching {
+        VersionSpec::Tag(VersionTag::Custom(tag)) if tag == "bundled" => Ok(None),
+        // Note: Apart from `latest`, tags are only recorded in the registry, so they can't match
+        matching if is_offline() => {
+            resolve_local("npm", matching, npm_versions()?, |_, _| false).map(|v| Some(Npm::new(v)))
+        }
         VersionSpec::Semver(requirement) => resolve_semver(requirement, hooks).map(Some),
-        VersionSpec::Exact(version) => Ok(Some(version)),
+        VersionSpec::Exact(version) => Ok(Some(Npm::new(version))),
         VersionSpec::None | VersionSpec::Tag(VersionTag::Latest) => {
             resolve_tag("latest", hooks).map(Some)
         }
-        VersionSpec::Tag(VersionTag::Custom(tag)) if tag == "bundled" => Ok(None),
         VersionSpec::Tag(tag) => resolve_tag(&tag.to_string(), hooks).map(Some),
     }
 }
 
-fn fetch_npm_index(hooks: Option<&ToolHooks<Npm>>) -> Fallible<(String, PackageIndex)> {
+/// Find the versions of npm that are newer than the given version
+pub fn check_updates(version: &Version, session: &mut Session) -> Fallible<Updates> {
+    if is_offline() {
+        debug!("Skipping npm update check in offline mode");
+        return Ok(Updates::default());
+    }
+
+    let (_, index) = fetch_npm_index(session.hooks()?.npm())?;
+    Ok(Updates::from_versions(
+        version,
+        index
+            .entries
+            .into_iter()
+            .map(|PackageDetails { version, .. }| (version, false)),
+    ))
+}
+
+pub(super) fn fetch_npm_index(hooks: Option<&ToolHooks<Npm>>) -> Fallible<(String, PackageIndex)> {
     let url = match hooks {
         Some(&ToolHooks {
             index: Some(ref hook),
@@ -53,13 +84,16 @@ fn fetch_npm_index(hooks: Option<&ToolHooks<Npm>>) -> Fallible<(String, PackageI
     Ok((url, metadata.into()))
 }
 
-fn resolve_tag(tag: &str, hooks: Option<&ToolHooks<Npm>>) -> Fallible<Version> {
+fn resolve_tag(tag: &str, hooks: Option<&ToolHooks<Npm>>) -> Fallible<Npm> {
     let (url, mut index) = fetch_npm_index(hooks)?;
 
     match index.tags.remove(tag) {
         Some(version) => {
             debug!("Found npm@{} matching tag '{}' from {}", version, tag, url);
-            Ok(version)
+            Ok(match find_package_details(index, &version) {
+                Some(details) => Npm::from_details(details),
+                None => Npm::new(version),
+            })
         }
         None => Err(ErrorKind::NpmVersionNotFound {
             matching: tag.into(),
@@ -68,7 +102,7 @@ fn resolve_tag(tag: &str, hooks: Option<&ToolHooks<Npm>>) -> Fallible<Version> {
     }
 }
 
-fn resolve_semver(matching: VersionReq, hooks: Option<&ToolHooks<Npm>>) -> Fallible<Version> {
+fn resolve_semver(matching: VersionReq, hooks: Option<&ToolHooks<Npm>>) -> Fallible<Npm> {
     let (url, index) = fetch_npm_index(hooks)?;
 
     let details_opt = index
@@ -82,7 +116,7 @@ fn resolve_semver(matching: VersionReq, hooks: Option<&ToolHooks<Npm>>) -> Falli
                 "Found npm@{} matching requirement '{}' from {}",
                 details.version, matching, url
             );
-            Ok(details.version)
+            Ok(Npm::from_details(details))
         }
         None => Err(ErrorKind::NpmVersionNotFound {
             matching: matching.to_string(),
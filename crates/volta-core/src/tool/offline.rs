@@ -0,0 +1,126 @@
+//! Provides offline mode, in which tools are resolved only from the local inventory
+
+use std::collections::BTreeSet;
+use std::env;
+use std::ffi::OsStr;
+
+use crate::error::{ErrorKind, Fallible};
+use crate::version::{VersionSpec, VersionTag};
+use log::debug;
+use semver::Version;
+
+/// Environment variable that prevents Volta from accessing the network
+const VOLTA_OFFLINE: &str = "VOLTA_OFFLINE";
+
+/// Determine whether Volta is in offline mode
+///
+/// Offline mode is enabled by setting `VOLTA_OFFLINE` to any value other than an empty string,
+/// `0`, or `false`, so that it can also be turned off explicitly.
+pub fn is_offline() -> bool {
+    matches!(env::var_os(VOLTA_OFFLINE), Some(value) if enables_offline(&value))
+}
+
+/// Determine whether a value of `VOLTA_OFFLINE` enables offline mode
+fn enables_offline(value: &OsStr) -> bool {
+    let value = value.to_string_lossy();
+    let value = value.trim();
+    !(value.is_empty() || value == "0" || value.eq_ignore_ascii_case("false"))
+}
+
+/// Enable offline mode for the current process and any tools it runs
+pub fn enable_offline() {
+    env::set_var(VOLTA_OFFLINE, "1");
+}
+
+/// Resolve a version requirement against the locally available versions of a tool
+///
+/// `matches_tag` determines whether a version satisfies a tag other than `latest`, which always
+/// resolves to the newest available version.
+pub(super) fn resolve_local<F>(
+    tool: &str,
+    matching: VersionSpec,
+    available: BTreeSet<Version>,
+    matches_tag: F,
+) -> Fallible<Version>
+where
+    F: Fn(&VersionTag, &Version) -> bool,
+{
+    let found = match &matching {
+        VersionSpec::Exact(version) => return Ok(version.clone()),
+        VersionSpec::Semver(requirement) => available
+            .into_iter()
+            .rev()
+            .find(|version| requirement.matches(version)),
+        VersionSpec::None | VersionSpec::Tag(VersionTag::Latest) => {
+            available.into_iter().next_back()
+        }
+        VersionSpec::Tag(tag) => available
+            .into_iter()
+            .rev()
+            .find(|version| matches_tag(tag, version)),
+    };
+
+    match found {
+        Some(version) => {
+            debug!(
+                "Found local {}@{} matching '{}' in offline mode",
+                tool, version, matching
+            );
+            Ok(version)
+        }
+        None => Err(ErrorKind::OfflineVersionNotFound {
+            tool: tool.into(),
+            matching: matching.to_string(),
+        }
+        .into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{enables_offline, resolve_local};
+    use crate::version::{VersionSpec, VersionTag};
+    use std::ffi::OsStr;
+
+    #[test]
+    fn test_enables_offline() {
+        assert!(enables_offline(OsStr::new("1")));
+        assert!(enables_offline(OsStr::new("true")));
+        assert!(!enables_offline(OsStr::new("")));
+        assert!(!enables_offline(OsStr::new("0")));
+        assert!(!enables_offline(OsStr::new("false")));
+        assert!(!enables_offline(OsStr::new("FALSE")));
+    }
+
+    fn resolve(matching: &str) -> Option<String> {
+        let available = ["1.2.3", "1.4.0", "2.0.1"]
+            .iter()
+            .map(|version| version.parse().unwrap())
+            .collect();
+        let matching = matching.parse().unwrap();
+
+        resolve_local("tool", matching, available, |tag, version| {
+            tag == &VersionTag::Custom("legacy".into()) && version.major == 1
+        })
+        .ok()
+        .map(|version| version.to_string())
+    }
+
+    #[test]
+    fn test_resolve_local() {
+        assert_eq!(resolve("1"), Some("1.4.0".into()));
+        assert_eq!(resolve("~1.2"), Some("1.2.3".into()));
+        assert_eq!(resolve("latest"), Some("2.0.1".into()));
+        assert_eq!(resolve("legacy"), Some("1.4.0".into()));
+        assert_eq!(resolve("3.0.0"), Some("3.0.0".into()));
+        assert_eq!(resolve("3"), None);
+        assert_eq!(resolve("beta"), None);
+    }
+
+    #[test]
+    fn test_resolve_local_default() {
+        let available = ["1.2.3"].iter().map(|v| v.parse().unwrap()).collect();
+        let version = resolve_local("tool", VersionSpec::None, available, |_, _| false).unwrap();
+        assert_eq!(version.to_string(), "1.2.3");
+    }
+}
@@ -0,0 +1,103 @@
+//! Provides the comparison of an installed tool version against the versions available upstream
+
+use semver::Version;
+
+/// The newer versions available for a specific version of a tool
+///
+/// Each field is only set if the corresponding version is newer than the version being compared.
+/// In offline mode, the registry can't be checked, so no newer versions are found for any tool
+/// other than Node, which falls back to its cached index.
+#[derive(Clone, Default)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub struct Updates {
+    /// The newest version with the same major version
+    pub latest_in_major: Option<Version>,
+    /// The newest LTS version, for tools that publish LTS releases
+    pub latest_lts: Option<Version>,
+    /// The newest version overall
+    pub latest: Option<Version>,
+}
+
+impl Updates {
+    /// Compare a version against the available versions of a tool
+    ///
+    /// The available versions are paired with whether they are LTS releases. Prereleases are
+    /// never considered to be updates.
+    pub(super) fn from_versions<I>(current: &Version, available: I) -> Self
+    where
+        I: IntoIterator<Item = (Version, bool)>,
+    {
+        let mut updates = Updates::default();
+        let newer = available
+            .into_iter()
+            .filter(|(version, _)| !version.is_prerelease() && version > current);
+
+        for (version, is_lts) in newer {
+            if version.major == current.major {
+                update_max(&mut updates.latest_in_major, &version);
+            }
+            if is_lts {
+                update_max(&mut updates.latest_lts, &version);
+            }
+            update_max(&mut updates.latest, &version);
+        }
+
+        updates
+    }
+
+    /// Determine whether any newer version is available
+    pub fn is_outdated(&self) -> bool {
+        self.latest_in_major.is_some() || self.latest_lts.is_some() || self.latest.is_some()
+    }
+}
+
+fn update_max(max: &mut Option<Version>, version: &Version) {
+    // Note: `None` is ordered before any `Some`, so the first version is always recorded
+    if max.as_ref() < Some(version) {
+        *max = Some(version.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Updates;
+    use semver::Version;
+
+    fn versions(available: &[(&str, bool)]) -> Vec<(Version, bool)> {
+        available
+            .iter()
+            .map(|(version, is_lts)| (version.parse().unwrap(), *is_lts))
+            .collect()
+    }
+
+    #[test]
+    fn finds_newer_versions() {
+        let available = versions(&[
+            ("12.4.0", true),
+            ("12.22.1", true),
+            ("14.17.0", true),
+            ("16.3.0", false),
+            ("17.0.0-rc.1", false),
+        ]);
+        let updates = Updates::from_versions(&"12.4.0".parse().unwrap(), available);
+
+        assert_eq!(
+            updates,
+            Updates {
+                latest_in_major: Some("12.22.1".parse().unwrap()),
+                latest_lts: Some("14.17.0".parse().unwrap()),
+                latest: Some("16.3.0".parse().unwrap()),
+            }
+        );
+        assert!(updates.is_outdated());
+    }
+
+    #[test]
+    fn ignores_older_versions() {
+        let available = versions(&[("12.4.0", true), ("14.17.0", true), ("16.3.0", false)]);
+        let updates = Updates::from_versions(&"16.3.0".parse().unwrap(), available);
+
+        assert_eq!(updates, Updates::default());
+        assert!(!updates.is_outdated());
+    }
+}
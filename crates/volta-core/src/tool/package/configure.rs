@@ -1,10 +1,10 @@
 use std::path::PathBuf;
 
 use super::manager::PackageManager;
-use super::metadata::{BinConfig, PackageConfig, PackageManifest};
+use super::metadata::{BinConfig, PackageConfig, PackageManifest, PlatformOverrides};
 use crate::error::{ErrorKind, Fallible};
-use crate::layout::volta_home;
 use crate::platform::{Image, PlatformSpec};
+use crate::profile::active_profile;
 use crate::shim;
 
 /// Read the manifest for the package being installed
@@ -19,28 +19,63 @@ pub(super) fn parse_manifest(
     PackageManifest::for_dir(package_name, &package_dir)
 }
 
+/// How a package was requested, which is recorded in its package config so that it can be
+/// reinstalled or upgraded the same way
+#[derive(Default)]
+pub(super) struct InstallOptions {
+    /// The alias the package is installed as, if any
+    pub alias: Option<String>,
+    /// The git URL, tarball, or local directory the package was installed from, if any
+    pub source: Option<String>,
+    /// The version range the package was requested with, if any
+    pub range: Option<String>,
+    /// The tools of the platform that were given on the command line
+    pub overrides: PlatformOverrides,
+}
+
 /// Generate configuration files and shims for the package and each of its bins
+///
+/// If the package is installed under an alias, its bins are exposed under the names determined
+/// by `alias_bins`, and the package config is keyed by the alias rather than the package name.
+/// Returns the names that the bins are exposed as.
 pub(super) fn write_config_and_shims(
     name: &str,
+    options: &InstallOptions,
     manifest: &PackageManifest,
     image: &Image,
     manager: PackageManager,
-) -> Fallible<()> {
-    validate_bins(name, manifest)?;
+) -> Fallible<Vec<String>> {
+    let alias = options.alias.as_deref();
+    let bins = match alias {
+        Some(alias) => alias_bins(name, alias, &manifest.bin)?,
+        None => manifest
+            .bin
+            .iter()
+            .map(|bin_name| (bin_name.clone(), bin_name.clone()))
+            .collect(),
+    };
+    validate_bins(alias.unwrap_or(name), &bins)?;
 
     let platform = PlatformSpec {
         node: image.node.value.clone(),
         npm: image.npm.clone().map(|s| s.value),
+        pnpm: image.pnpm.clone().map(|s| s.value),
         yarn: image.yarn.clone().map(|s| s.value),
     };
 
     // Generate the shims and bin configs for each bin provided by the package
-    for bin_name in &manifest.bin {
-        shim::create(&bin_name)?;
+    for (bin_name, target) in &bins {
+        shim::create(bin_name)?;
 
         BinConfig {
             name: bin_name.clone(),
             package: name.into(),
+            alias: alias.map(String::from),
+            target: if target != bin_name {
+                Some(target.clone())
+            } else {
+                None
+            },
             version: manifest.version.clone(),
             platform: platform.clone(),
             manager,
@@ -48,34 +83,72 @@ pub(super) fn write_config_and_shims(
         .write()?;
     }
 
+    let bins: Vec<String> = bins.into_iter().map(|(bin_name, _)| bin_name).collect();
+
     // Write the config for the package
     PackageConfig {
         name: name.into(),
+        alias: alias.map(String::from),
+        source: options.source.clone(),
+        range: options.range.clone(),
         version: manifest.version.clone(),
         platform,
-        bins: manifest.bin.clone(),
+        bins: bins.clone(),
         manager,
+        overrides: options.overrides,
     }
     .write()?;
 
-    Ok(())
+    Ok(bins)
+}
+
+/// Determine the names that the bins of an aliased package are exposed as
+///
+/// If the alias starts with the name of one of the bins, the rest of the alias is appended to the
+/// name of every bin, so `typescript` installed as `tsc4` exposes `tsc4` and `tsserver4`.
+/// Otherwise, a package with a single bin exposes it as the alias itself.
+///
+/// Returns pairs of the exposed name and the name of the bin within the package.
+fn alias_bins(name: &str, alias: &str, bins: &[String]) -> Fallible<Vec<(String, String)>> {
+    let prefix = bins
+        .iter()
+        .filter(|bin_name| alias.starts_with(bin_name.as_str()))
+        .max_by_key(|bin_name| bin_name.len());
+
+    match (prefix, bins) {
+        (Some(prefix), _) => {
+            let suffix = &alias[prefix.len()..];
+            Ok(bins
+                .iter()
+                .map(|bin_name| (format!("{}{}", bin_name, suffix), bin_name.clone()))
+                .collect())
+        }
+        (None, [bin_name]) => Ok(vec![(alias.into(), bin_name.clone())]),
+        (None, []) => Ok(Vec::new()),
+        (None, _) => Err(ErrorKind::InvalidPackageAlias {
+            alias: alias.into(),
+            package: name.into(),
+            bins: bins.join(", "),
+        }
+        .into()),
+    }
 }
 
 /// Validate that we aren't attempting to install a bin that is already installed by
 /// another package.
-fn validate_bins(package_name: &str, manifest: &PackageManifest) -> Fallible<()> {
-    let home = volta_home()?;
-    for bin_name in &manifest.bin {
+fn validate_bins(install_name: &str, bins: &[(String, String)]) -> Fallible<()> {
+    let profile = active_profile()?;
+    for (bin_name, _) in bins {
         // Check for name conflicts with already-installed bins
         // Some packages may install bins with the same name
-        if let Ok(config) = BinConfig::from_file(home.default_tool_bin_config(&bin_name)) {
+        if let Ok(config) = BinConfig::from_file(profile.bin_config_file(bin_name)) {
             // The file exists, so there is a bin with this name
             // That is okay iff it came from the package that is currently being installed
-            if package_name != config.package {
+            if install_name != config.install_name() {
                 return Err(ErrorKind::BinaryAlreadyInstalled {
                     bin_name: bin_name.into(),
-                    existing_package: config.package,
-                    new_package: package_name.into(),
+                    existing_package: config.install_name().into(),
+                    new_package: install_name.into(),
                 }
                 .into());
             }
@@ -84,3 +157,50 @@ fn validate_bins(package_name: &str, manifest: &PackageManifest) -> Fallible<()>
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::alias_bins;
+
+    fn bins(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn alias_bins_appends_suffix() {
+        let exposed = alias_bins("typescript", "tsc4", &bins(&["tsc", "tsserver"])).unwrap();
+
+        assert_eq!(
+            exposed,
+            vec![
+                ("tsc4".to_string(), "tsc".to_string()),
+                ("tsserver4".to_string(), "tsserver".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn alias_bins_uses_longest_prefix() {
+        let exposed = alias_bins("pkg", "foobar-next", &bins(&["foo", "foobar"])).unwrap();
+
+        assert_eq!(
+            exposed,
+            vec![
+                ("foo-next".to_string(), "foo".to_string()),
+                ("foobar-next".to_string(), "foobar".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn alias_bins_renames_single_bin() {
+        let exposed = alias_bins("cowsay", "moo", &bins(&["cowsay"])).unwrap();
+
+        assert_eq!(exposed, vec![("moo".to_string(), "cowsay".to_string())]);
+    }
+
+    #[test]
+    fn alias_bins_requires_prefix_for_several_bins() {
+        assert!(alias_bins("typescript", "ts4", &bins(&["tsc", "tsserver"])).is_err());
+    }
+}
@@ -1,4 +1,5 @@
 use std::path::PathBuf;
+use std::process::Command;
 
 use super::manager::PackageManager;
 use crate::command::create_command;
@@ -6,28 +7,27 @@ use crate::error::{Context, ErrorKind, Fallible};
 use crate::platform::Image;
 use crate::style::progress_spinner;
 use log::debug;
+use semver::Version;
 
-/// Use `npm install --global` to install the package
+/// Use the given package manager to install the package globally
 ///
-/// Sets the environment variable `npm_config_prefix` to redirect the install to the Volta
-/// data directory, taking advantage of the standard global install behavior with a custom
-/// location
+/// Sets the environment variable `npm_config_prefix` (along with the settings needed by the
+/// specific package manager) to redirect the install to the Volta data directory, taking
+/// advantage of the standard global install behavior with a custom location
+///
+/// `local` is true when the package is installed from the local filesystem, rather than from the
+/// registry or a remote source
 pub(super) fn run_global_install(
     package: String,
+    local: bool,
     staging_dir: PathBuf,
     platform_image: &Image,
+    manager: PackageManager,
 ) -> Fallible<()> {
-    let mut command = create_command("npm");
-    command.args(&[
-        "install",
-        "--global",
-        "--loglevel=warn",
-        "--no-update-notifier",
-        "--no-audit",
-    ]);
+    let mut command = install_command(manager, local, platform_image)?;
     command.arg(&package);
     command.env("PATH", platform_image.path()?);
-    PackageManager::Npm.setup_global_command(&mut command, staging_dir);
+    manager.setup_global_command(&mut command, staging_dir);
 
     debug!("Installing {} with command: {:?}", package, command);
     let spinner = progress_spinner(&format!("Installing {}", package));
@@ -40,19 +40,100 @@ pub(super) fn run_global_install(
     let output = output_result?;
 
     let stderr = String::from_utf8_lossy(&output.stderr);
+    let stdout = String::from_utf8_lossy(&output.stdout);
     debug!("[install stderr]\n{}", stderr);
-    debug!(
-        "[install stdout]\n{}",
-        String::from_utf8_lossy(&output.stdout)
-    );
+    debug!("[install stdout]\n{}", stdout);
 
     if output.status.success() {
         Ok(())
-    } else if stderr.contains("code E404") {
-        // npm outputs "code E404" as part of the error output when a package couldn't be found
-        // Detect that and show a nicer error message (since we likely know the problem in that case)
+    } else if is_not_found_error(manager, &stderr, &stdout) {
+        // Detect a package that couldn't be found and show a nicer error message (since we likely
+        // know the problem in that case)
         Err(ErrorKind::PackageNotFound { package }.into())
     } else {
         Err(ErrorKind::PackageInstallFailed { package }.into())
     }
 }
+
+/// Create the command to globally install a package with the given package manager
+///
+/// pnpm and Yarn are only available if the platform includes them, since unlike npm they
+/// aren't bundled with Node
+fn install_command(
+    manager: PackageManager,
+    local: bool,
+    platform_image: &Image,
+) -> Fallible<Command> {
+    let command = match manager {
+        PackageManager::Npm => {
+            let mut command = create_command("npm");
+            command.args([
+                "install",
+                "--global",
+                "--loglevel=warn",
+                "--no-update-notifier",
+                "--no-audit",
+            ]);
+
+            // Copy a package from a local directory, rather than linking to the directory.
+            // Note: Older versions of npm don't know the option, and always link to the directory
+            if local && supports_install_links(&platform_image.resolve_npm()?.value) {
+                command.arg("--install-links");
+            }
+
+            command
+        }
+        PackageManager::Pnpm => {
+            if platform_image.pnpm.is_none() {
+                return Err(ErrorKind::NoDefaultPnpm.into());
+            }
+
+            // Note: The install is into the staging directory as a local project, since pnpm
+            // global installs share a single directory for every package
+            let mut command = create_command("pnpm");
+            command.args(["add", "--ignore-workspace", "--loglevel=warn"]);
+            command
+        }
+        PackageManager::Yarn => {
+            if platform_image.yarn.is_none() {
+                return Err(ErrorKind::NoDefaultYarn.into());
+            }
+
+            let mut command = create_command("yarn");
+            command.args(["global", "add", "--non-interactive", "--no-progress"]);
+            command
+        }
+    };
+
+    Ok(command)
+}
+
+/// Determine whether a version of npm supports the `--install-links` option, added in npm 8.17
+fn supports_install_links(npm: &Version) -> bool {
+    (npm.major, npm.minor) >= (8, 17)
+}
+
+/// Determine whether the error output of an install shows that the package couldn't be found
+fn is_not_found_error(manager: PackageManager, stderr: &str, stdout: &str) -> bool {
+    match manager {
+        // npm outputs "code E404" as part of the error output when a package couldn't be found
+        PackageManager::Npm => stderr.contains("code E404"),
+        // Note: pnpm reports errors on stdout rather than stderr
+        PackageManager::Pnpm => stdout.contains("ERR_PNPM_FETCH_404"),
+        // Yarn reports the registry URL that couldn't be found, e.g. "...: Not found"
+        PackageManager::Yarn => stderr.contains("Not found"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::supports_install_links;
+
+    #[test]
+    fn test_supports_install_links() {
+        assert!(!supports_install_links(&"6.14.18".parse().unwrap()));
+        assert!(!supports_install_links(&"8.16.0".parse().unwrap()));
+        assert!(supports_install_links(&"8.17.0".parse().unwrap()));
+        assert!(supports_install_links(&"10.2.4".parse().unwrap()));
+    }
+}
@@ -1,6 +1,8 @@
+use std::fmt;
 use std::fs::File;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::str::FromStr;
 
 use super::metadata::GlobalYarnManifest;
 use crate::fs::read_dir_eager;
@@ -11,9 +13,33 @@ use crate::fs::read_dir_eager;
 )]
 pub enum PackageManager {
     Npm,
+    Pnpm,
     Yarn,
 }
 
+impl fmt::Display for PackageManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PackageManager::Npm => "npm",
+            PackageManager::Pnpm => "pnpm",
+            PackageManager::Yarn => "yarn",
+        })
+    }
+}
+
+impl FromStr for PackageManager {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "npm" => Ok(PackageManager::Npm),
+            "pnpm" => Ok(PackageManager::Pnpm),
+            "yarn" => Ok(PackageManager::Yarn),
+            _ => Err(format!("unknown package manager '{}'", s)),
+        }
+    }
+}
+
 impl PackageManager {
     /// Given the `package_root`, returns the directory where the source is stored for this
     /// package manager. This will include the top-level `node_modules`, where appropriate.
@@ -28,7 +54,7 @@ impl PackageManager {
     /// contain the top-level `node-modules`
     #[cfg(unix)]
     pub fn source_root(self, package_root: PathBuf) -> PathBuf {
-        // On Unix, the source is always within a `lib` subdirectory, with both npm and Yarn
+        // On Unix, the source is always within a `lib` subdirectory, with npm, pnpm, and Yarn
         let mut path = package_root;
         path.push("lib");
 
@@ -42,8 +68,8 @@ impl PackageManager {
         match self {
             // On Windows, npm puts the source node_modules directory in the root of the `prefix`
             PackageManager::Npm => package_root,
-            // On Windows, we still tell yarn to use the `lib` subdirectory
-            PackageManager::Yarn => {
+            // On Windows, we still tell pnpm and Yarn to use the `lib` subdirectory
+            PackageManager::Pnpm | PackageManager::Yarn => {
                 let mut path = package_root;
                 path.push("lib");
 
@@ -56,11 +82,22 @@ impl PackageManager {
     /// manager.
     #[cfg(unix)]
     pub fn binary_dir(self, package_root: PathBuf) -> PathBuf {
-        // On Unix, the binaries are always within a `bin` subdirectory for both npm and Yarn
-        let mut path = package_root;
-        path.push("bin");
+        match self {
+            // pnpm installs into a local `node_modules`, so the binaries are linked into `.bin`
+            PackageManager::Pnpm => {
+                let mut path = self.source_dir(package_root);
+                path.push(".bin");
 
-        path
+                path
+            }
+            // On Unix, the binaries are otherwise within a `bin` subdirectory for npm and Yarn
+            _ => {
+                let mut path = package_root;
+                path.push("bin");
+
+                path
+            }
+        }
     }
 
     /// Given the `package_root`, returns the directory where binaries are stored for this package
@@ -70,6 +107,13 @@ impl PackageManager {
         match self {
             // On Windows, npm leaves the binaries at the root of the `prefix` directory
             PackageManager::Npm => package_root,
+            // pnpm installs into a local `node_modules`, so the binaries are linked into `.bin`
+            PackageManager::Pnpm => {
+                let mut path = self.source_dir(package_root);
+                path.push(".bin");
+
+                path
+            }
             // On Windows, Yarn still includes the `bin` subdirectory
             PackageManager::Yarn => {
                 let mut path = package_root;
@@ -84,8 +128,15 @@ impl PackageManager {
     pub fn setup_global_command(self, command: &mut Command, package_root: PathBuf) {
         command.env("npm_config_prefix", &package_root);
 
-        if let PackageManager::Yarn = self {
-            command.env("npm_config_global_folder", self.source_root(package_root));
+        match self {
+            PackageManager::Npm => {}
+            // pnpm has no global prefix, so it installs into the source root as a local project
+            PackageManager::Pnpm => {
+                command.current_dir(self.source_root(package_root));
+            }
+            PackageManager::Yarn => {
+                command.env("npm_config_global_folder", self.source_root(package_root));
+            }
         }
     }
 
@@ -95,7 +146,9 @@ impl PackageManager {
     pub(super) fn get_installed_package(self, package_root: PathBuf) -> Option<String> {
         match self {
             PackageManager::Npm => get_npm_package_name(self.source_dir(package_root)),
-            PackageManager::Yarn => get_yarn_package_name(self.source_root(package_root)),
+            PackageManager::Pnpm | PackageManager::Yarn => {
+                get_manifest_package_name(self.source_root(package_root))
+            }
         }
     }
 }
@@ -141,10 +194,10 @@ fn get_single_directory_name(parent_dir: &Path) -> Option<String> {
     }
 }
 
-/// Determine the package name for a Yarn global install
+/// Determine the package name for a pnpm or Yarn global install
 ///
-/// Yarn creates a `package.json` file with the globally installed package as a dependency
-fn get_yarn_package_name(source_root: PathBuf) -> Option<String> {
+/// Both create a `package.json` file with the globally installed package as a dependency
+fn get_manifest_package_name(source_root: PathBuf) -> Option<String> {
     let package_file = source_root.join("package.json");
     let file = File::open(package_file).ok()?;
     let manifest: GlobalYarnManifest = serde_json::de::from_reader(file).ok()?;
@@ -6,18 +6,32 @@ use std::path::Path;
 use super::manager::PackageManager;
 use crate::error::{Context, ErrorKind, Fallible, VoltaError};
 use crate::layout::volta_home;
-use crate::platform::PlatformSpec;
-use crate::version::{option_version_serde, version_serde};
+use crate::platform::{CliPlatform, InheritOption, PlatformSpec};
+use crate::profile::active_profile;
+use crate::version::{option_version_serde, vec_version_serde, version_serde};
 use fs_utils::ensure_containing_dir_exists;
 use semver::Version;
 
 /// Configuration information about an installed package
 ///
-/// Will be stored in <VOLTA_HOME>/tools/user/packages/<package>.json
+/// Will be stored in <VOLTA_HOME>/tools/user/packages/<package>.json, or <alias>.json if the
+/// package was installed under an alias. Packages installed in a profile other than the default
+/// are stored in <VOLTA_HOME>/tools/user/profiles/<profile>/packages instead.
 #[derive(serde::Serialize, serde::Deserialize, PartialOrd, Ord, PartialEq, Eq)]
 pub struct PackageConfig {
     /// The package name
     pub name: String,
+    /// The alias the package was installed as, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+    /// The git URL, tarball, or local directory the package was installed from, if it wasn't
+    /// installed from the package registry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// The version range the package was requested with, if it was requested with one, which
+    /// limits the versions it is upgraded to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub range: Option<String>,
     /// The package version
     #[serde(with = "version_serde")]
     pub version: Version,
@@ -28,6 +42,10 @@ pub struct PackageConfig {
     pub bins: Vec<String>,
     /// The package manager that was used to install this package
     pub manager: PackageManager,
+    /// The tools of the platform that were given on the command line when the package was
+    /// installed, which are kept when it is reinstalled
+    #[serde(default, skip_serializing_if = "PlatformOverrides::is_empty")]
+    pub overrides: PlatformOverrides,
 }
 
 impl PackageConfig {
@@ -65,9 +83,14 @@ impl PackageConfig {
         }
     }
 
+    /// The name the package is installed as, which is the alias if there is one
+    pub fn install_name(&self) -> &str {
+        self.alias.as_ref().unwrap_or(&self.name)
+    }
+
     /// Write this `PackageConfig` into the appropriate config file
     pub fn write(self) -> Fallible<()> {
-        let config_file_path = volta_home()?.default_package_config_file(&self.name);
+        let config_file_path = active_profile()?.package_config_file(self.install_name());
 
         ensure_containing_dir_exists(&config_file_path).with_context(|| {
             ErrorKind::ContainingDirError {
@@ -85,15 +108,68 @@ impl PackageConfig {
     }
 }
 
+/// The tools of a package's platform that were given with `--node` or `--npm`, rather than
+/// taken from the default platform
+#[derive(
+    Clone, Copy, Default, serde::Serialize, serde::Deserialize, PartialOrd, Ord, PartialEq, Eq,
+)]
+pub struct PlatformOverrides {
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub node: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub npm: bool,
+}
+
+impl PlatformOverrides {
+    pub fn is_empty(&self) -> bool {
+        !self.node && !self.npm
+    }
+
+    /// Build the command-line platform that overrides the same tools with the versions from the
+    /// given platform, if any tools were overridden
+    pub fn to_cli_platform(self, platform: &PlatformSpec) -> Option<CliPlatform> {
+        if self.is_empty() {
+            return None;
+        }
+
+        Some(CliPlatform {
+            node: Some(platform.node.clone()).filter(|_| self.node),
+            npm: if self.npm {
+                platform.npm.clone().into()
+            } else {
+                InheritOption::Inherit
+            },
+            pnpm: InheritOption::Inherit,
+            yarn: InheritOption::Inherit,
+        })
+    }
+}
+
+impl From<&CliPlatform> for PlatformOverrides {
+    fn from(platform: &CliPlatform) -> Self {
+        PlatformOverrides {
+            node: platform.node.is_some(),
+            npm: !matches!(platform.npm, InheritOption::Inherit),
+        }
+    }
+}
+
 /// Configuration information about a single installed binary from a package
 ///
-/// Will be stored in <VOLTA_HOME>/tools/user/bins/<bin-name>.json
+/// Will be stored in <VOLTA_HOME>/tools/user/bins/<bin-name>.json, or in
+/// <VOLTA_HOME>/tools/user/profiles/<profile>/bins for a profile other than the default
 #[derive(serde::Serialize, serde::Deserialize)]
 pub struct BinConfig {
     /// The binary name
     pub name: String,
     /// The package that installed the binary
     pub package: String,
+    /// The alias the package was installed as, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+    /// The name of the binary within the package, if it is exposed under a different name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
     /// The package version
     #[serde(with = "version_serde")]
     pub version: Version,
@@ -139,9 +215,19 @@ impl BinConfig {
         }
     }
 
+    /// The name the package that provides this binary is installed as
+    pub fn install_name(&self) -> &str {
+        self.alias.as_ref().unwrap_or(&self.package)
+    }
+
+    /// The name of the binary within the package's image
+    pub fn target_name(&self) -> &str {
+        self.target.as_ref().unwrap_or(&self.name)
+    }
+
     /// Write this `BinConfig` to the appropriate config file
     pub fn write(self) -> Fallible<()> {
-        let config_file_path = volta_home()?.default_tool_bin_config(&self.name);
+        let config_file_path = active_profile()?.bin_config_file(&self.name);
 
         ensure_containing_dir_exists(&config_file_path).with_context(|| {
             ErrorKind::ContainingDirError {
@@ -158,6 +244,66 @@ impl BinConfig {
     }
 }
 
+/// Information about a package that has been pinned in a project
+///
+/// Will be stored in <VOLTA_HOME>/tools/pinned/<package>.json. The binaries are recorded when the
+/// package is pinned, so that running one of them only needs the package that provides it.
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct PinnedConfig {
+    /// The package name
+    pub name: String,
+    /// The versions of the package that have been pinned, whose images are kept when the package
+    /// is uninstalled
+    #[serde(default, with = "vec_version_serde")]
+    pub versions: Vec<Version>,
+    /// The binaries provided by the most recently pinned version of the package
+    pub bins: Vec<String>,
+}
+
+impl PinnedConfig {
+    pub fn from_file_if_exists<P>(file: P) -> Fallible<Option<Self>>
+    where
+        P: AsRef<Path>,
+    {
+        match File::open(&file) {
+            Err(error) => {
+                if error.kind() == io::ErrorKind::NotFound {
+                    Ok(None)
+                } else {
+                    Err(VoltaError::from_source(
+                        error,
+                        ErrorKind::ReadPackageConfigError {
+                            file: file.as_ref().to_owned(),
+                        },
+                    ))
+                }
+            }
+            Ok(config) => serde_json::from_reader(config)
+                .with_context(|| ErrorKind::ParsePackageConfigError)
+                .map(Some),
+        }
+    }
+
+    /// Write this `PinnedConfig` into the appropriate config file
+    pub fn write(self) -> Fallible<()> {
+        let config_file_path = volta_home()?.pinned_package_file(&self.name);
+
+        ensure_containing_dir_exists(&config_file_path).with_context(|| {
+            ErrorKind::ContainingDirError {
+                path: config_file_path.clone(),
+            }
+        })?;
+
+        let file = File::create(&config_file_path).with_context(|| {
+            ErrorKind::WritePackageConfigError {
+                file: config_file_path,
+            }
+        })?;
+        serde_json::to_writer_pretty(file, &self)
+            .with_context(|| ErrorKind::StringifyPackageConfigError)
+    }
+}
+
 #[derive(serde::Serialize, serde::Deserialize)]
 #[serde(remote = "PlatformSpec")]
 struct RawPlatformSpec {
@@ -165,6 +311,13 @@ struct RawPlatformSpec {
     node: Version,
     #[serde(with = "option_version_serde")]
     npm: Option<Version>,
+    // Note: Configs written before pnpm support won't include the key, so we need a default
+    #[serde(
+        default,
+        with = "option_version_serde",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pnpm: Option<Version>,
     #[serde(with = "option_version_serde")]
     yarn: Option<Version>,
 }
@@ -217,7 +370,8 @@ impl PackageManifest {
 /// Struct to read the `dependencies` out of Yarn's global manifest.
 ///
 /// For global installs, yarn creates a `package.json` file in the `global-folder` and installs
-/// global packages as dependencies of that pseudo-package
+/// global packages as dependencies of that pseudo-package. The pnpm installs done by Volta use
+/// the same structure, with the `package.json` in the source root of the staging directory
 pub(super) struct GlobalYarnManifest {
     #[serde(default)]
     pub dependencies: HashMap<String, String>,
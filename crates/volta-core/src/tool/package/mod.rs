@@ -1,66 +1,202 @@
 use std::fmt::{self, Display};
-use std::fs::create_dir_all;
+use std::fs::{create_dir_all, write};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
-use super::Tool;
+use super::{info_pinned, is_offline, Spec, Tool};
 use crate::error::{Context, ErrorKind, Fallible};
 use crate::fs::{remove_dir_if_exists, rename, symlink_dir};
 use crate::layout::volta_home;
-use crate::platform::{Image, PlatformSpec};
+use crate::platform::{CliPlatform, Image, PlatformSpec};
+use crate::profile::active_profile;
 use crate::session::Session;
 use crate::style::{success_prefix, tool_version};
 use crate::sync::VoltaLock;
 use crate::version::VersionSpec;
+use configure::InstallOptions;
 use fs_utils::ensure_containing_dir_exists;
 use log::info;
+use semver::Version;
 use tempfile::{tempdir_in, TempDir};
 
 mod configure;
 mod install;
 mod manager;
 mod metadata;
+mod pinned;
+mod reinstall;
+mod resolve;
+mod source;
 mod uninstall;
 
 pub use manager::PackageManager;
-pub use metadata::{BinConfig, PackageConfig, PackageManifest};
-pub use uninstall::uninstall;
+pub use metadata::{BinConfig, PackageConfig, PackageManifest, PlatformOverrides};
+pub use pinned::{find_pinned_binary, install_pinned_packages};
+pub use reinstall::reinstall;
+pub use resolve::{check_range_update, check_updates};
+pub use source::is_package_source;
+pub(crate) use source::normalize as normalize_source;
+pub(crate) use uninstall::{remove_image_if_unused, remove_shared_link_dir};
+pub use uninstall::{uninstall, uninstall_source};
 
 /// The Tool implementation for installing 3rd-party global packages
 pub struct Package {
-    name: String,
-    version: VersionSpec,
+    requested: Requested,
+    alias: Option<String>,
+    range: Option<String>,
+    platform: Option<CliPlatform>,
+    manager: PackageManager,
     staging: TempDir,
 }
 
+/// The package that was requested to be installed
+enum Requested {
+    /// A package from the registry, with the version for the package manager to resolve
+    Registry(String, VersionSpec),
+    /// A git URL, tarball, or local directory, where the name of the package isn't known until
+    /// it has been installed
+    Source(String),
+}
+
 impl Package {
     pub fn new(name: String, version: VersionSpec) -> Fallible<Self> {
-        let staging = setup_staging_directory(PackageManager::Npm, NeedsScope::No)?;
+        Package::with_manager(name, version, PackageManager::Npm)
+    }
+
+    /// Create a package that is installed using the given package manager, rather than npm
+    pub fn with_manager(
+        name: String,
+        version: VersionSpec,
+        manager: PackageManager,
+    ) -> Fallible<Self> {
+        let range = match &version {
+            VersionSpec::Semver(range) => Some(range.to_string()),
+            _ => None,
+        };
+        let package = Package::create(Requested::Registry(name, version), manager)?;
+
+        Ok(Package { range, ..package })
+    }
+
+    /// Create a package that is installed from a git URL, tarball, or local directory
+    pub fn from_source(source: String, manager: PackageManager) -> Fallible<Self> {
+        let source = source::normalize(source)?;
+        Package::create(Requested::Source(source), manager)
+    }
+
+    /// Create a package that reinstalls an installed package, requested the same way as it was
+    /// originally installed, including any tools that were given on the command line
+    pub fn from_config(config: &PackageConfig) -> Fallible<Self> {
+        let package = match &config.source {
+            Some(source) => Package::from_source(source.clone(), config.manager)?,
+            None => Package::create(
+                Requested::Registry(
+                    config.name.clone(),
+                    VersionSpec::Exact(config.version.clone()),
+                ),
+                config.manager,
+            )?,
+        };
 
         Ok(Package {
-            name,
-            version,
+            alias: config.alias.clone(),
+            range: config.range.clone(),
+            platform: config.overrides.to_cli_platform(&config.platform),
+            ..package
+        })
+    }
+
+    fn create(requested: Requested, manager: PackageManager) -> Fallible<Self> {
+        let staging = setup_staging_directory(manager, NeedsScope::No)?;
+
+        Ok(Package {
+            requested,
+            alias: None,
+            range: None,
+            platform: None,
+            manager,
             staging,
         })
     }
 
+    /// Install the package under an alias, so it can exist alongside other installed versions
+    pub fn with_alias(mut self, alias: String) -> Self {
+        self.alias = Some(alias);
+        self
+    }
+
+    /// Record the version range the package was requested with, which limits the versions it is
+    /// upgraded to
+    pub fn with_range(mut self, range: String) -> Self {
+        self.range = Some(range);
+        self
+    }
+
+    /// Install and run the package with the given tools, in place of those from the default
+    /// platform
+    pub fn with_platform(mut self, platform: CliPlatform) -> Self {
+        self.platform = Some(platform);
+        self
+    }
+
     pub fn run_install(&self, platform_image: &Image) -> Fallible<()> {
+        let local =
+            matches!(&self.requested, Requested::Source(source) if source::is_local(source));
+
+        // Note: The package manager would need the registry or the remote source, so in offline
+        // mode only a local directory or tarball can be installed
+        if is_offline() && !local {
+            let tool = match &self.requested {
+                Requested::Registry(name, version) => Spec::Package(name.clone(), version.clone()),
+                Requested::Source(source) => Spec::PackageSource(source.clone()),
+            };
+            return Err(ErrorKind::OfflineFetchError { tool }.into());
+        }
+
         install::run_global_install(
             self.to_string(),
+            local,
             self.staging.path().to_owned(),
             platform_image,
+            self.manager,
         )
     }
 
     pub fn complete_install(self, image: &Image) -> Fallible<PackageManifest> {
-        let manager = PackageManager::Npm;
-        let manifest =
-            configure::parse_manifest(&self.name, self.staging.path().to_owned(), manager)?;
-
-        persist_install(&self.name, &self.version, self.staging.path())?;
-        link_package_to_shared_dir(&self.name, manager)?;
-        configure::write_config_and_shims(&self.name, &manifest, image, manager)?;
+        let manager = self.manager;
+        let mut options = InstallOptions {
+            alias: self.alias,
+            source: None,
+            range: self.range,
+            overrides: self
+                .platform
+                .as_ref()
+                .map(PlatformOverrides::from)
+                .unwrap_or_default(),
+        };
+        let name = match self.requested {
+            Requested::Registry(name, _) => name,
+            Requested::Source(source) => {
+                options.source = Some(source);
+                manager
+                    .get_installed_package(self.staging.path().to_owned())
+                    .ok_or(ErrorKind::InstalledPackageNameError)?
+            }
+        };
+        let mut manifest =
+            configure::parse_manifest(&name, self.staging.path().to_owned(), manager)?;
+
+        let bins = persist_and_configure(
+            &name,
+            &options,
+            &manifest,
+            self.staging.path(),
+            image,
+            manager,
+        )?;
 
+        // Report the bins under the names they are exposed as, which differ for an alias
+        manifest.bin = bins;
         Ok(manifest)
     }
 }
@@ -76,28 +212,35 @@ impl Tool for Package {
     fn install(self: Box<Self>, session: &mut Session) -> Fallible<()> {
         let _lock = VoltaLock::acquire();
 
-        let default_image = session
-            .default_platform()?
-            .map(PlatformSpec::as_default)
-            .ok_or(ErrorKind::NoPlatform)?
-            .checkout(session)?;
+        let default_platform = session.default_platform()?.map(PlatformSpec::as_default);
+        let platform = match (self.platform.clone(), default_platform) {
+            (Some(cli), Some(base)) => Some(cli.merge(base)),
+            (Some(cli), None) => cli.into(),
+            (None, base) => base,
+        };
+        let image = platform.ok_or(ErrorKind::NoPlatform)?.checkout(session)?;
 
-        self.run_install(&default_image)?;
-        let manifest = self.complete_install(&default_image)?;
+        self.run_install(&image)?;
+        let alias = self.alias.clone();
+        let manifest = self.complete_install(&image)?;
 
         let bins = manifest.bin.join(", ");
+        let installed = match alias {
+            Some(alias) => format!(
+                "{} as {}",
+                tool_version(manifest.name, manifest.version),
+                alias
+            ),
+            None => tool_version(manifest.name, manifest.version),
+        };
 
         if bins.is_empty() {
-            info!(
-                "{} installed {}",
-                success_prefix(),
-                tool_version(manifest.name, manifest.version)
-            );
+            info!("{} installed {}", success_prefix(), installed);
         } else {
             info!(
                 "{} installed {} with executables: {}",
                 success_prefix(),
-                tool_version(manifest.name, manifest.version),
+                installed,
                 bins
             );
         }
@@ -105,16 +248,28 @@ impl Tool for Package {
         Ok(())
     }
 
-    fn pin(self: Box<Self>, _session: &mut Session) -> Fallible<()> {
-        Err(ErrorKind::CannotPinPackage { package: self.name }.into())
+    fn pin(self: Box<Self>, session: &mut Session) -> Fallible<()> {
+        if session.project()?.is_none() {
+            return Err(ErrorKind::NotInPackage.into());
+        }
+
+        let manifest = self.install_pinned(session)?;
+
+        // Note: We know this will succeed, since we checked above
+        let project = session.project_mut()?.unwrap();
+        project.pin_package(&manifest.name, manifest.version.clone())?;
+
+        info_pinned(tool_version(&manifest.name, &manifest.version));
+        Ok(())
     }
 }
 
 impl Display for Package {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self.version {
-            VersionSpec::None => f.write_str(&self.name),
-            _ => f.write_str(&tool_version(&self.name, &self.version)),
+        match &self.requested {
+            Requested::Registry(name, VersionSpec::None) => f.write_str(name),
+            Requested::Registry(name, version) => f.write_str(&tool_version(name, version)),
+            Requested::Source(source) => f.write_str(source),
         }
     }
 }
@@ -170,28 +325,37 @@ impl DirectInstall {
             .ok_or(ErrorKind::InstalledPackageNameError)?;
         let manifest = configure::parse_manifest(&name, staging.path().to_owned(), manager)?;
 
-        persist_install(&name, &manifest.version, staging.path())?;
-        link_package_to_shared_dir(&name, manager)?;
-        configure::write_config_and_shims(&name, &manifest, image, manager)
+        persist_and_configure(
+            &name,
+            &InstallOptions::default(),
+            &manifest,
+            staging.path(),
+            image,
+            manager,
+        )?;
+        Ok(())
     }
 }
 
 /// Helper struct for direct in-place upgrades using `npm update -g` or `yarn global upgrade`
 ///
-/// Upgrades the requested package directly in the image directory
+/// Upgrades the requested package directly in the image directory of the installed version, which
+/// is then moved to the image directory of the new version
 pub struct InPlaceUpgrade {
     package: String,
-    directory: PathBuf,
+    installed: Option<PackageConfig>,
     manager: PackageManager,
 }
 
 impl InPlaceUpgrade {
     pub fn new(package: String, manager: PackageManager) -> Fallible<Self> {
-        let directory = volta_home()?.package_image_dir(&package);
+        // Note: A config that can't be read is reported by `check_upgraded_package`
+        let installed =
+            PackageConfig::from_file(active_profile()?.package_config_file(&package)).ok();
 
         Ok(Self {
             package,
-            directory,
+            installed,
             manager,
         })
     }
@@ -200,12 +364,7 @@ impl InPlaceUpgrade {
     ///     - The package is not installed as a global
     ///     - The package exists, but was installed with a different package manager
     pub fn check_upgraded_package(&self) -> Fallible<()> {
-        let config =
-            PackageConfig::from_file(volta_home()?.default_package_config_file(&self.package))
-                .with_context(|| ErrorKind::UpgradePackageNotFound {
-                    package: self.package.clone(),
-                    manager: self.manager,
-                })?;
+        let config = self.installed_config()?;
 
         if config.manager != self.manager {
             Err(ErrorKind::UpgradePackageWrongManager {
@@ -218,16 +377,46 @@ impl InPlaceUpgrade {
         }
     }
 
-    pub fn setup_command(&self, command: &mut Command) {
-        self.manager
-            .setup_global_command(command, self.directory.clone());
+    pub fn setup_command(&self, command: &mut Command) -> Fallible<()> {
+        let directory = self.directory()?;
+        self.manager.setup_global_command(command, directory);
+        Ok(())
     }
 
     pub fn complete_upgrade(self, image: &Image) -> Fallible<()> {
-        let manifest = configure::parse_manifest(&self.package, self.directory, self.manager)?;
+        let directory = self.directory()?;
+        let manifest = configure::parse_manifest(&self.package, directory.clone(), self.manager)?;
+
+        if self.installed_config()?.version != manifest.version {
+            persist_install(&self.package, &manifest.version, &directory)?;
+        }
 
-        link_package_to_shared_dir(&self.package, self.manager)?;
-        configure::write_config_and_shims(&self.package, &manifest, image, self.manager)
+        link_package_to_shared_dir(&self.package, &manifest.version, self.manager)?;
+        let installed = self.installed_config()?;
+        let options = InstallOptions {
+            alias: None,
+            source: installed.source.clone(),
+            range: installed.range.clone(),
+            overrides: installed.overrides,
+        };
+        configure::write_config_and_shims(&self.package, &options, &manifest, image, self.manager)?;
+        Ok(())
+    }
+
+    fn installed_config(&self) -> Fallible<&PackageConfig> {
+        self.installed.as_ref().ok_or_else(|| {
+            ErrorKind::UpgradePackageNotFound {
+                package: self.package.clone(),
+                manager: self.manager,
+            }
+            .into()
+        })
+    }
+
+    /// The image directory of the installed version, which is upgraded in place
+    fn directory(&self) -> Fallible<PathBuf> {
+        let config = self.installed_config()?;
+        Ok(volta_home()?.package_image_dir(&self.package, &config.version.to_string()))
     }
 }
 
@@ -252,7 +441,7 @@ impl From<bool> for NeedsScope {
 fn setup_staging_directory(manager: PackageManager, needs_scope: NeedsScope) -> Fallible<TempDir> {
     // Workaround to ensure relative symlinks continue to work.
     // The final installed location of packages is:
-    //      $VOLTA_HOME/tools/image/packages/{name}/
+    //      $VOLTA_HOME/tools/image/packages/{name}@{version}/
     // To ensure that the temp directory has the same amount of nesting, we use:
     //      $VOLTA_HOME/tmp/image/packages/{tempdir}/
     // This way any relative symlinks will have the same amount of nesting and will remain valid
@@ -280,34 +469,101 @@ fn setup_staging_directory(manager: PackageManager, needs_scope: NeedsScope) ->
     ensure_containing_dir_exists(&binary_dir)
         .with_context(|| ErrorKind::ContainingDirError { path: binary_dir })?;
 
+    // pnpm installs into the nearest project, so the source root needs its own `package.json` to
+    // prevent pnpm from searching the parent directories
+    if manager == PackageManager::Pnpm {
+        let manifest = manager
+            .source_root(staging.path().to_owned())
+            .join("package.json");
+        write(&manifest, "{}").with_context(|| ErrorKind::WritePackageManifestError {
+            file: manifest.clone(),
+        })?;
+    }
+
     Ok(staging)
 }
 
-fn persist_install<V>(package_name: &str, package_version: V, staging_dir: &Path) -> Fallible<()>
-where
-    V: Display,
-{
-    let package_dir = volta_home()?.package_image_dir(package_name);
+/// Move a package install into the image directory for its name and version, then write its
+/// configs and shims
+///
+/// Anything left over from a previous install under the same name is cleaned up, and the names
+/// the package's bins are exposed as are returned.
+fn persist_and_configure(
+    name: &str,
+    options: &InstallOptions,
+    manifest: &PackageManifest,
+    staging_dir: &Path,
+    image: &Image,
+    manager: PackageManager,
+) -> Fallible<Vec<String>> {
+    let install_name = options.alias.as_deref().unwrap_or(name);
+    let previous =
+        PackageConfig::from_file_if_exists(active_profile()?.package_config_file(install_name))?;
+
+    persist_install(name, &manifest.version, staging_dir)?;
+    // Note: Only the default install of a package is available to `require` from other packages
+    if options.alias.is_none() {
+        link_package_to_shared_dir(name, &manifest.version, manager)?;
+    }
+    let bins = configure::write_config_and_shims(name, options, manifest, image, manager)?;
 
-    remove_dir_if_exists(&package_dir)?;
+    if let Some(previous) = previous {
+        uninstall::remove_replaced(previous, &bins)?;
+    }
+
+    Ok(bins)
+}
+
+fn persist_install(
+    package_name: &str,
+    package_version: &Version,
+    staging_dir: &Path,
+) -> Fallible<()> {
+    let home = volta_home()?;
+    let package_dir = home.package_image_dir(package_name, &package_version.to_string());
+    let image_error = || ErrorKind::SetupToolImageError {
+        tool: package_name.into(),
+        version: package_version.to_string(),
+        dir: package_dir.clone(),
+    };
 
     // Handle scoped packages (@vue/cli), which have an extra directory for the scope
     ensure_containing_dir_exists(&package_dir).with_context(|| ErrorKind::ContainingDirError {
         path: package_dir.to_owned(),
     })?;
 
-    rename(staging_dir, &package_dir).with_context(|| ErrorKind::SetupToolImageError {
-        tool: package_name.into(),
-        version: package_version.to_string(),
-        dir: package_dir,
-    })?;
+    // Move an existing image for the same version aside rather than removing it, so the bins
+    // that use it keep working if the new image can't be moved into place
+    let previous = if package_dir.exists() {
+        let previous =
+            tempdir_in(home.tmp_dir()).with_context(|| ErrorKind::CreateTempDirError {
+                in_dir: home.tmp_dir().to_owned(),
+            })?;
+        rename(&package_dir, previous.path().join("image")).with_context(image_error)?;
+        Some(previous)
+    } else {
+        None
+    };
+
+    if let Err(error) = rename(staging_dir, &package_dir) {
+        if let Some(previous) = &previous {
+            // Note: If restoring fails as well, there is nothing more we can do
+            let _ = rename(previous.path().join("image"), &package_dir);
+        }
+        return Err(error).with_context(image_error);
+    }
 
     Ok(())
 }
 
-fn link_package_to_shared_dir(package_name: &str, manager: PackageManager) -> Fallible<()> {
+pub(crate) fn link_package_to_shared_dir(
+    package_name: &str,
+    package_version: &Version,
+    manager: PackageManager,
+) -> Fallible<()> {
     let home = volta_home()?;
-    let mut source = manager.source_dir(home.package_image_dir(package_name));
+    let mut source =
+        manager.source_dir(home.package_image_dir(package_name, &package_version.to_string()));
     source.push(package_name);
 
     let target = home.shared_lib_dir(package_name);
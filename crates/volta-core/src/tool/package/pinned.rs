@@ -0,0 +1,192 @@
+//! Provides support for packages pinned by a project, which are run from the image for the pinned
+//! version so they don't affect the default package or other projects
+
+use std::path::PathBuf;
+
+use super::manager::PackageManager;
+use super::metadata::{PackageManifest, PinnedConfig};
+use super::{configure, persist_install, Package, Requested};
+use crate::error::{ErrorKind, Fallible};
+use crate::layout::volta_home;
+use crate::platform::Platform;
+use crate::project::PackageManagerKind;
+use crate::session::Session;
+use crate::settings::Settings;
+use crate::shim;
+use crate::sync::VoltaLock;
+use crate::version::VersionSpec;
+use log::debug;
+use semver::Version;
+
+impl Package {
+    /// Install the package into the image for its version, using the current platform and the
+    /// package manager of the project
+    ///
+    /// Unlike a default install, this doesn't write any package or binary configs, it only
+    /// ensures that the shims for the package's binaries exist.
+    pub(super) fn install_pinned(self, session: &mut Session) -> Fallible<PackageManifest> {
+        let (name, version) = match &self.requested {
+            Requested::Registry(name, version) => (name.clone(), version.clone()),
+            Requested::Source(source) => {
+                return Err(ErrorKind::CannotPinPackageSource {
+                    source: source.clone(),
+                }
+                .into())
+            }
+        };
+
+        let manager = pinned_manager(session)?;
+        let package = if self.manager == manager {
+            self
+        } else {
+            Package::with_manager(name.clone(), version, manager)?
+        };
+
+        let _lock = VoltaLock::acquire();
+
+        let image = Platform::current(session)?
+            .ok_or(ErrorKind::NoPlatform)?
+            .checkout(session)?;
+        package.run_install(&image)?;
+
+        let staging = package.staging.path();
+        let manifest = configure::parse_manifest(&name, staging.to_owned(), manager)?;
+        persist_install(&name, &manifest.version, staging)?;
+
+        for bin_name in &manifest.bin {
+            shim::create(bin_name)?;
+        }
+
+        let config_file = volta_home()?.pinned_package_file(&name);
+        let mut versions = PinnedConfig::from_file_if_exists(config_file)?
+            .map(|config| config.versions)
+            .unwrap_or_default();
+        if !versions.contains(&manifest.version) {
+            versions.push(manifest.version.clone());
+        }
+
+        PinnedConfig {
+            name,
+            versions,
+            bins: manifest.bin.clone(),
+        }
+        .write()?;
+
+        Ok(manifest)
+    }
+}
+
+/// Install the packages pinned by the current project that haven't been pinned on this machine
+/// yet, such as in a fresh clone of the project, which also creates the shims for their binaries
+pub fn install_pinned_packages(session: &mut Session) -> Fallible<()> {
+    for (name, version) in pinned_packages(session)? {
+        let config = PinnedConfig::from_file_if_exists(volta_home()?.pinned_package_file(&name))?;
+        let pinned = matches!(config, Some(config) if config.versions.contains(&version));
+        let package_dir = volta_home()?.package_image_dir(&name, &version.to_string());
+
+        if !pinned || !package_dir.exists() {
+            install_pinned_version(name, version, session)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Find a binary provided by one of the packages pinned by the current project
+///
+/// Only the package that provides the binary, according to the binaries recorded when it was
+/// pinned, is installed if it isn't installed yet. A package that hasn't been pinned on this
+/// machine has no recorded binaries, so it is installed from the project's pin to find them, once
+/// none of the recorded packages provide the binary.
+pub fn find_pinned_binary(bin: &str, session: &mut Session) -> Fallible<Option<PathBuf>> {
+    let pinned = pinned_packages(session)?;
+    if pinned.is_empty() {
+        return Ok(None);
+    }
+
+    let manager = pinned_manager(session)?;
+    let mut unrecorded = Vec::new();
+
+    for (name, version) in pinned {
+        let provides_bin =
+            match PinnedConfig::from_file_if_exists(volta_home()?.pinned_package_file(&name))? {
+                Some(config) => config.bins.iter().any(|name| name == bin),
+                None => {
+                    unrecorded.push((name, version));
+                    continue;
+                }
+            };
+
+        if !provides_bin {
+            continue;
+        }
+
+        let package_dir = volta_home()?.package_image_dir(&name, &version.to_string());
+
+        // Note: The binaries were recorded for the most recently pinned version, which may not be
+        // the version pinned by this project, so they are checked against the installed manifest
+        let manifest = if package_dir.exists() {
+            configure::parse_manifest(&name, package_dir.clone(), manager)?
+        } else {
+            install_pinned_version(name, version, session)?
+        };
+
+        if manifest.bin.iter().any(|name| name == bin) {
+            return Ok(Some(pinned_bin_path(package_dir, manager, bin)));
+        }
+    }
+
+    for (name, version) in unrecorded {
+        let package_dir = volta_home()?.package_image_dir(&name, &version.to_string());
+        let manifest = install_pinned_version(name, version, session)?;
+
+        if manifest.bin.iter().any(|name| name == bin) {
+            return Ok(Some(pinned_bin_path(package_dir, manager, bin)));
+        }
+    }
+
+    Ok(None)
+}
+
+fn pinned_bin_path(package_dir: PathBuf, manager: PackageManager, bin: &str) -> PathBuf {
+    let mut bin_path = manager.binary_dir(package_dir);
+    bin_path.push(bin);
+    bin_path
+}
+
+/// The names and versions of the packages pinned by the current project, if any
+fn pinned_packages(session: &mut Session) -> Fallible<Vec<(String, Version)>> {
+    Ok(match session.project()? {
+        Some(project) => project
+            .pinned_packages()
+            .map(|(name, version)| (name.clone(), version.clone()))
+            .collect(),
+        None => Vec::new(),
+    })
+}
+
+/// The package manager that pinned packages are installed with, which is the one declared by the
+/// project's `packageManager` field, or otherwise the one from the user settings
+fn pinned_manager(session: &mut Session) -> Fallible<PackageManager> {
+    let kind = session
+        .project()?
+        .and_then(|project| project.package_manager())
+        .map(|manager| manager.kind);
+
+    match kind {
+        Some(PackageManagerKind::Npm) => Ok(PackageManager::Npm),
+        Some(PackageManagerKind::Pnpm) => Ok(PackageManager::Pnpm),
+        Some(PackageManagerKind::Yarn) => Ok(PackageManager::Yarn),
+        None => Ok(Settings::current()?.package_manager()),
+    }
+}
+
+fn install_pinned_version(
+    name: String,
+    version: Version,
+    session: &mut Session,
+) -> Fallible<PackageManifest> {
+    debug!("Installing pinned package {}@{}", name, version);
+    let manager = pinned_manager(session)?;
+    Package::with_manager(name, VersionSpec::Exact(version), manager)?.install_pinned(session)
+}
@@ -0,0 +1,61 @@
+//! Provides reinstalling global packages with the current default platform, such as after the
+//! default Node has changed
+
+use super::metadata::PackageConfig;
+use super::Package;
+use crate::error::{ErrorKind, Fallible};
+use crate::inventory::package_configs;
+use crate::session::Session;
+use crate::style::{success_prefix, tool_version};
+use crate::tool::Tool;
+use log::info;
+
+/// Reinstalls the given packages, which may be the aliases they were installed as, or every
+/// installed package if `all` is set
+///
+/// Each package is reinstalled the same way it was originally installed, but with the default
+/// platform rather than the one recorded when it was installed. Tools that were given with
+/// `--node` or `--npm` when the package was installed are kept. The existing install is only
+/// replaced once the reinstall has succeeded, so its bins keep working if the reinstall fails.
+pub fn reinstall(session: &mut Session, names: &[String], all: bool) -> Fallible<()> {
+    let configs = package_configs()?;
+
+    if !all {
+        for name in names {
+            if !configs.iter().any(|config| config.install_name() == name) {
+                return Err(ErrorKind::ReinstallPackageNotFound {
+                    package: name.clone(),
+                }
+                .into());
+            }
+        }
+    }
+
+    let selected: Vec<PackageConfig> = configs
+        .into_iter()
+        .filter(|config| all || names.iter().any(|name| name == config.install_name()))
+        .collect();
+
+    if selected.is_empty() {
+        info!("No global packages are installed");
+        return Ok(());
+    }
+
+    let count = selected.len();
+    for config in &selected {
+        Box::new(Package::from_config(config)?).install(session)?;
+    }
+
+    // Note: The installs above would have failed if there were no default platform
+    if let Some(platform) = session.default_platform()? {
+        info!(
+            "{} reinstalled {} {} with {}",
+            success_prefix(),
+            count,
+            if count == 1 { "package" } else { "packages" },
+            tool_version("node", &platform.node)
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+//! Provides the lookup of published versions of 3rd-party packages from the npm registry
+
+use super::super::registry::{
+    public_registry_index, PackageDetails, PackageIndex, RawPackageMetadata,
+    NPM_ABBREVIATED_ACCEPT_HEADER,
+};
+use super::super::registry_fetch_error;
+use crate::error::{Context, Fallible};
+use crate::style::progress_spinner;
+use crate::tool::offline::is_offline;
+use crate::tool::Updates;
+use attohttpc::header::ACCEPT;
+use attohttpc::Response;
+use log::debug;
+use semver::{Version, VersionReq};
+
+/// Find the published versions of a package that are newer than the given version
+pub fn check_updates(name: &str, version: &Version) -> Fallible<Updates> {
+    if is_offline() {
+        debug!("Skipping update check for {} in offline mode", name);
+        return Ok(Updates::default());
+    }
+
+    let index = fetch_package_index(name)?;
+    Ok(Updates::from_versions(
+        version,
+        index
+            .entries
+            .into_iter()
+            .map(|PackageDetails { version, .. }| (version, false)),
+    ))
+}
+
+/// Find the newest published version of a package that is newer than the given version and
+/// satisfies the range it was installed with
+///
+/// In offline mode, the registry can't be checked, so no newer version is found.
+pub fn check_range_update(
+    name: &str,
+    version: &Version,
+    range: &VersionReq,
+) -> Fallible<Option<Version>> {
+    if is_offline() {
+        debug!("Skipping update check for {} in offline mode", name);
+        return Ok(None);
+    }
+
+    let index = fetch_package_index(name)?;
+    Ok(index
+        .entries
+        .into_iter()
+        .map(|PackageDetails { version, .. }| version)
+        .filter(|available| available > version && range.matches(available))
+        .max())
+}
+
+fn fetch_package_index(name: &str) -> Fallible<PackageIndex> {
+    let url = public_registry_index(name);
+    let spinner = progress_spinner(&format!("Fetching public registry: {}", url));
+    let metadata: RawPackageMetadata = attohttpc::get(&url)
+        .header(ACCEPT, NPM_ABBREVIATED_ACCEPT_HEADER)
+        .send()
+        .and_then(Response::error_for_status)
+        .and_then(Response::json)
+        .with_context(registry_fetch_error(name, &url))?;
+
+    spinner.finish_and_clear();
+    Ok(metadata.into())
+}
@@ -0,0 +1,141 @@
+//! Provides support for installing packages from a git URL, a tarball, or a local directory,
+//! rather than from the package registry
+
+use std::env;
+
+use crate::error::{Context, ErrorKind, Fallible};
+use dunce::canonicalize;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+/// The prefixes of the remote sources supported by npm, pnpm, and Yarn
+const REMOTE_PREFIXES: [&str; 8] = [
+    "git+",
+    "git://",
+    "github:",
+    "gitlab:",
+    "bitbucket:",
+    "gist:",
+    "http://",
+    "https://",
+];
+
+/// The extensions of local tarballs, which npm recognizes even without a leading `./`
+const TARBALL_EXTENSIONS: [&str; 3] = [".tgz", ".tar.gz", ".tar"];
+
+lazy_static! {
+    /// A GitHub shorthand, like `org/tool` or `org/tool#v1.2.0`, which unlike a scoped package
+    /// name doesn't start with `@`
+    static ref GITHUB_SHORTHAND: Regex =
+        Regex::new(r"^[^@./\\][^@/\\:]*/[^/\\]+$").expect("regex is valid");
+    /// A local path, relative to the current directory or absolute
+    static ref LOCAL_PATH: Regex =
+        Regex::new(r"^(\.\.?([/\\]|$)|/|~/|[a-zA-Z]:[/\\])").expect("regex is valid");
+}
+
+/// Determine whether a tool spec is a package source instead of a package name
+pub fn is_package_source(spec: &str) -> bool {
+    spec.starts_with("file:")
+        || REMOTE_PREFIXES
+            .iter()
+            .any(|prefix| spec.starts_with(prefix))
+        || GITHUB_SHORTHAND.is_match(spec)
+        || is_local_path(spec)
+}
+
+/// Determine whether a package source is on the local filesystem, including `file:` sources
+pub(super) fn is_local(source: &str) -> bool {
+    source.starts_with("file:") || is_local_path(source)
+}
+
+/// Determine whether a package source is a path to a local directory or tarball
+fn is_local_path(spec: &str) -> bool {
+    LOCAL_PATH.is_match(spec)
+        || (!spec.contains(':') && TARBALL_EXTENSIONS.iter().any(|ext| spec.ends_with(ext)))
+}
+
+/// Resolve a local path, including a `file:` source or a path in the home directory, to an
+/// absolute path, so that it is recorded in a way that can be reinstalled from any directory.
+/// Other sources are returned unchanged.
+pub(crate) fn normalize(source: String) -> Fallible<String> {
+    let path = match source.strip_prefix("file:") {
+        Some(path) => path,
+        None if is_local_path(&source) => &source,
+        None => return Ok(source),
+    };
+
+    // Note: The shell doesn't expand `~` inside a `file:` source or a quoted argument, so the
+    // home directory is expanded here rather than resolving `~` as a relative directory
+    let path = match path.strip_prefix("~/").or_else(|| path.strip_prefix("~\\")) {
+        Some(relative) => dirs::home_dir()
+            .ok_or(ErrorKind::NoHomeEnvironmentVar)?
+            .join(relative),
+        None => env::current_dir()
+            .with_context(|| ErrorKind::CurrentDirError)?
+            .join(path),
+    };
+
+    let path = canonicalize(path).with_context(|| ErrorKind::LocalPackageNotFound {
+        path: source.clone(),
+    })?;
+
+    Ok(path.to_string_lossy().into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_local, is_package_source, normalize};
+    use dunce::canonicalize;
+
+    #[test]
+    fn test_remote_sources() {
+        assert!(is_package_source("github:org/tool"));
+        assert!(is_package_source("git+ssh://git@github.com/org/tool.git"));
+        assert!(is_package_source("https://example.com/tool-1.0.0.tgz"));
+        assert!(is_package_source("org/tool"));
+        assert!(is_package_source("org/tool#v1.2.0"));
+    }
+
+    #[test]
+    fn test_local_sources() {
+        assert!(is_package_source("."));
+        assert!(is_package_source("./my-cli"));
+        assert!(is_package_source("../my-cli"));
+        assert!(is_package_source("/opt/my-cli"));
+        assert!(is_package_source("file:../my-cli"));
+        assert!(is_package_source("my-cli-1.0.0.tgz"));
+        assert!(is_package_source(r"C:\tools\my-cli"));
+    }
+
+    #[test]
+    fn test_is_local() {
+        assert!(is_local("/opt/my-cli"));
+        assert!(is_local("file:../my-cli"));
+        assert!(is_local("my-cli-1.0.0.tgz"));
+        assert!(!is_local("github:org/tool"));
+        assert!(!is_local("https://example.com/my-cli-1.0.0.tgz"));
+    }
+
+    #[test]
+    fn test_normalize_file_source() {
+        let dir = env!("CARGO_MANIFEST_DIR");
+        let expected = canonicalize(dir).unwrap().to_string_lossy().into_owned();
+
+        assert_eq!(normalize(format!("file:{}", dir)).unwrap(), expected);
+        assert_eq!(normalize(dir.to_string()).unwrap(), expected);
+        assert_eq!(
+            normalize("github:org/tool".to_string()).unwrap(),
+            "github:org/tool"
+        );
+    }
+
+    #[test]
+    fn test_package_names() {
+        assert!(!is_package_source("typescript"));
+        assert!(!is_package_source("typescript@4"));
+        assert!(!is_package_source("@vue/cli"));
+        assert!(!is_package_source("@vue/cli@5.0.8"));
+        assert!(!is_package_source("git@2.0.0"));
+        assert!(!is_package_source(".hidden"));
+    }
+}
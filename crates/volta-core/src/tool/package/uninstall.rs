@@ -1,36 +1,48 @@
-use super::metadata::{BinConfig, PackageConfig};
+use super::metadata::{BinConfig, PackageConfig, PinnedConfig};
+use super::source::normalize;
 use crate::error::{Context, ErrorKind, Fallible};
 use crate::fs::{
     dir_entry_match, ok_if_not_found, read_dir_eager, remove_dir_if_exists, remove_file_if_exists,
 };
+use crate::inventory::{all_package_configs, package_configs};
 use crate::layout::volta_home;
+use crate::profile::active_profile;
 use crate::shim;
 use crate::style::success_prefix;
 use crate::sync::VoltaLock;
-use log::{info, warn};
+use log::{debug, info, warn};
+use semver::Version;
 
-/// Uninstalls the specified package.
+/// Uninstalls the specified package, which may be the alias it was installed as.
 ///
 /// This removes:
 ///
 /// - The JSON configuration files for both the package and its bins
 /// - The shims for the package bins
-/// - The package directory itself
+/// - The package directory itself, unless another install uses the same version
 pub fn uninstall(name: &str) -> Fallible<()> {
-    let home = volta_home()?;
     // Acquire a lock on the Volta directory, if possible, to prevent concurrent changes
     let _lock = VoltaLock::acquire();
 
     // If the package config file exists, use that to remove any installed bins and shims
-    let package_config_file = home.default_package_config_file(name);
+    let package_config_file = active_profile()?.package_config_file(name);
 
     let package_found = match PackageConfig::from_file_if_exists(&package_config_file)? {
         None => {
             // there is no package config - check for orphaned binaries
             let package_binary_list = binaries_from_package(name)?;
             if !package_binary_list.is_empty() {
-                for bin_name in package_binary_list {
-                    remove_config_and_shim(&bin_name, name)?;
+                for config in &package_binary_list {
+                    remove_config_and_shim(&config.name, name)?;
+                }
+                if package_binary_list
+                    .iter()
+                    .all(|config| config.alias.is_none())
+                {
+                    remove_shared_link_dir(name)?;
+                }
+                for config in package_binary_list {
+                    remove_image_if_unused(&config.package, &config.version)?;
                 }
                 true
             } else {
@@ -38,21 +50,19 @@ pub fn uninstall(name: &str) -> Fallible<()> {
             }
         }
         Some(package_config) => {
-            for bin_name in package_config.bins {
-                remove_config_and_shim(&bin_name, name)?;
+            for bin_name in &package_config.bins {
+                remove_config_and_shim(bin_name, name)?;
             }
 
             remove_file_if_exists(package_config_file)?;
+            if package_config.alias.is_none() {
+                remove_shared_link_dir(name)?;
+            }
+            remove_image_if_unused(&package_config.name, &package_config.version)?;
             true
         }
     };
 
-    remove_shared_link_dir(name)?;
-
-    // Remove the package directory itself
-    let package_image_dir = home.package_image_dir(name);
-    remove_dir_if_exists(package_image_dir)?;
-
     if package_found {
         info!("{} package '{}' uninstalled", success_prefix(), name);
     } else {
@@ -62,10 +72,75 @@ pub fn uninstall(name: &str) -> Fallible<()> {
     Ok(())
 }
 
+/// Uninstalls the package that was installed from the given git URL, tarball, or local directory
+pub fn uninstall_source(source: &str) -> Fallible<()> {
+    // Note: A local path is recorded as an absolute path, which can't be determined if the path
+    // has since been removed, so the source is also compared as given
+    let normalized = normalize(source.into()).unwrap_or_else(|_| source.into());
+    let installed = package_configs()?.into_iter().find(|config| {
+        matches!(&config.source, Some(recorded) if *recorded == normalized || recorded == source)
+    });
+
+    match installed {
+        Some(config) => uninstall(config.install_name()),
+        None => {
+            warn!("No package installed from '{}' found to uninstall", source);
+            Ok(())
+        }
+    }
+}
+
+/// Remove what is left over from a previous install that has been replaced by a new one
+///
+/// This includes the bins that the new install no longer provides, as well as the previous image,
+/// if no install uses that version any more.
+pub(super) fn remove_replaced(previous: PackageConfig, bins: &[String]) -> Fallible<()> {
+    for bin_name in previous
+        .bins
+        .iter()
+        .filter(|bin_name| !bins.contains(bin_name))
+    {
+        remove_config_and_shim(bin_name, previous.install_name())?;
+    }
+
+    remove_image_if_unused(&previous.name, &previous.version)
+}
+
+/// Remove the image for a version of a package, unless a package installed in any profile still
+/// uses it, or the version has been pinned in a project
+///
+/// If the installed packages can't be read, the image is kept, since it may still be in use.
+pub(crate) fn remove_image_if_unused(name: &str, version: &Version) -> Fallible<()> {
+    let pinned = match PinnedConfig::from_file_if_exists(volta_home()?.pinned_package_file(name)) {
+        Ok(config) => matches!(config, Some(config) if config.versions.contains(version)),
+        Err(error) => {
+            debug!("Unable to read pinned package config: {}", error);
+            true
+        }
+    };
+
+    let in_use = pinned
+        || match all_package_configs() {
+            Ok(configs) => configs
+                .iter()
+                .any(|config| config.name == name && &config.version == version),
+            Err(error) => {
+                debug!("Unable to read package configs: {}", error);
+                true
+            }
+        };
+
+    if !in_use {
+        remove_dir_if_exists(volta_home()?.package_image_dir(name, &version.to_string()))?;
+    }
+
+    Ok(())
+}
+
 /// Remove a shim and its associated configuration file
 fn remove_config_and_shim(bin_name: &str, pkg_name: &str) -> Fallible<()> {
     shim::delete(bin_name)?;
-    let config_file = volta_home()?.default_tool_bin_config(bin_name);
+    let config_file = active_profile()?.bin_config_file(bin_name);
     remove_file_if_exists(config_file)?;
     info!(
         "Removed executable '{}' installed by '{}'",
@@ -74,19 +149,17 @@ fn remove_config_and_shim(bin_name: &str, pkg_name: &str) -> Fallible<()> {
     Ok(())
 }
 
-/// Reads the contents of a directory and returns a Vec containing the names of
+/// Reads the contents of a directory and returns a Vec containing the configs of
 /// all the binaries installed by the given package.
-fn binaries_from_package(package: &str) -> Fallible<Vec<String>> {
-    let bin_config_dir = volta_home()?.default_bin_dir();
+fn binaries_from_package(package: &str) -> Fallible<Vec<BinConfig>> {
+    let bin_config_dir = active_profile()?.bin_dir();
 
     dir_entry_match(&bin_config_dir, |entry| {
         let path = entry.path();
-        if let Ok(config) = BinConfig::from_file(path) {
-            if config.package == package {
-                return Some(config.name);
-            }
+        match BinConfig::from_file(path) {
+            Ok(config) if config.install_name() == package => Some(config),
+            _ => None,
         }
-        None
     })
     .or_else(ok_if_not_found)
     .with_context(|| ErrorKind::ReadBinConfigDirError {
@@ -97,7 +170,7 @@ fn binaries_from_package(package: &str) -> Fallible<Vec<String>> {
 /// Remove the link to the package in the shared lib directory
 ///
 /// For scoped packages, if the scope directory is now empty, it will also be removed
-fn remove_shared_link_dir(name: &str) -> Fallible<()> {
+pub(crate) fn remove_shared_link_dir(name: &str) -> Fallible<()> {
     // Remove the link in the shared package directory, if it exists
     let mut shared_lib_dir = volta_home()?.shared_lib_dir(name);
     remove_dir_if_exists(&shared_lib_dir)?;
@@ -0,0 +1,200 @@
+//! Provides fetcher for pnpm distributions
+
+use std::path::Path;
+
+use super::super::download_tool_error;
+use super::super::launcher::write_launchers;
+use super::super::registry::{
+    load_cached_tarball, public_registry_package, verify_tarball, version_details, PackageDetails,
+};
+use super::resolve::fetch_pnpm_index;
+use crate::error::{Context, ErrorKind, Fallible};
+use crate::fs::{create_staging_dir, create_staging_file, remove_file_if_exists, rename};
+use crate::hook::ToolHooks;
+use crate::layout::volta_home;
+use crate::project::PackageManagerHash;
+use crate::style::{progress_bar, tool_version};
+use crate::tool::{self, is_offline, Pnpm};
+use crate::version::VersionSpec;
+use archive::{Archive, Tarball};
+use fs_utils::ensure_containing_dir_exists;
+use log::debug;
+use semver::Version;
+
+/// Fetch the given version, verifying it against the registry integrity and the `hash` from the
+/// project's `packageManager` field, if any
+///
+/// The registry `details` of the version are only looked up if they weren't found when resolving
+/// it, which for a download happens once it is complete.
+pub fn fetch(
+    version: &Version,
+    details: Option<PackageDetails>,
+    hooks: Option<&ToolHooks<Pnpm>>,
+    hash: Option<&PackageManagerHash>,
+) -> Fallible<()> {
+    let pnpm_dir = volta_home()?.pnpm_inventory_dir();
+    let cache_file = pnpm_dir.join(Pnpm::archive_filename(&version.to_string()));
+
+    // Note: In offline mode, the registry can't be checked, so a cached archive is used without
+    // verifying its integrity
+    let details = match details {
+        _ if is_offline() => None,
+        None if cache_file.is_file() => Some(fetch_details(version, hooks)?),
+        details => details,
+    };
+
+    if let Some(archive) = load_cached_tarball(&cache_file, details.as_ref(), hash) {
+        debug!(
+            "Loading {} from cached archive at '{}'",
+            tool_version("pnpm", version),
+            cache_file.display()
+        );
+        match unpack_archive(archive, version, || Ok(())) {
+            Err(error) if matches!(error.kind(), ErrorKind::UnpackArchiveError { .. }) => {
+                debug!(
+                    "Cached archive at '{}' could not be unpacked, removing",
+                    cache_file.display()
+                );
+                // Note: Failing to remove the file isn't fatal, since it will be replaced by the
+                // new download in any case
+                let _ = remove_file_if_exists(&cache_file);
+            }
+            result => return result,
+        }
+    }
+
+    // Note: Offline mode can still use an archive from the inventory, but not download one
+    if is_offline() {
+        return Err(ErrorKind::OfflineFetchError {
+            tool: tool::Spec::Pnpm(VersionSpec::Exact(version.clone())),
+        }
+        .into());
+    }
+
+    let staging = create_staging_file()?;
+    let remote_url = determine_remote_url(version, hooks)?;
+    let archive = fetch_remote_distro(version, &remote_url, staging.path())?;
+
+    unpack_archive(archive, version, || {
+        let details = match details {
+            Some(details) => details,
+            None => fetch_details(version, hooks)?,
+        };
+        verify_tarball(
+            staging.path(),
+            tool::Spec::Pnpm(VersionSpec::Exact(version.clone())),
+            &remote_url,
+            &details,
+            hash,
+        )
+    })?;
+
+    ensure_containing_dir_exists(&cache_file).with_context(|| ErrorKind::ContainingDirError {
+        path: cache_file.clone(),
+    })?;
+    staging
+        .persist(cache_file)
+        .with_context(|| ErrorKind::PersistInventoryError {
+            tool: "pnpm".into(),
+        })?;
+
+    Ok(())
+}
+
+/// Fetch the registry details of the given version, which are used to verify its tarball
+fn fetch_details(version: &Version, hooks: Option<&ToolHooks<Pnpm>>) -> Fallible<PackageDetails> {
+    let (url, index) = fetch_pnpm_index(hooks)?;
+    version_details(
+        tool::Spec::Pnpm(VersionSpec::Exact(version.clone())),
+        url,
+        index,
+        version,
+    )
+}
+
+/// Unpack the pnpm archive into the image directory so that it is ready for use
+///
+/// The `verify` callback is run after the archive has been unpacked into a staging directory and
+/// before it is moved into the image directory.
+fn unpack_archive<F>(archive: Box<dyn Archive>, version: &Version, verify: F) -> Fallible<()>
+where
+    F: FnOnce() -> Fallible<()>,
+{
+    let temp = create_staging_dir()?;
+    debug!("Unpacking pnpm into '{}'", temp.path().display());
+
+    let progress = progress_bar(
+        archive.origin(),
+        &tool_version("pnpm", version),
+        archive
+            .uncompressed_size()
+            .unwrap_or_else(|| archive.compressed_size()),
+    );
+    let version_string = version.to_string();
+
+    archive
+        .unpack(temp.path(), &mut |_, read| {
+            progress.inc(read as u64);
+        })
+        .with_context(|| ErrorKind::UnpackArchiveError {
+            tool: "pnpm".into(),
+            version: version_string.clone(),
+        })?;
+
+    verify()?;
+
+    // The pnpm package only ships the `.cjs` entry points, so we need to write our own launchers
+    let bin_path = temp.path().join("package").join("bin");
+    write_launchers(&bin_path, "pnpm", "pnpm.cjs")?;
+
+    // Note: Older versions of pnpm don't include `pnpx`, so we only create the launcher if needed
+    if bin_path.join("pnpx.cjs").is_file() {
+        write_launchers(&bin_path, "pnpx", "pnpx.cjs")?;
+    }
+
+    let dest = volta_home()?.pnpm_image_dir(&version_string);
+    ensure_containing_dir_exists(&dest)
+        .with_context(|| ErrorKind::ContainingDirError { path: dest.clone() })?;
+
+    rename(temp.path().join("package"), &dest).with_context(|| ErrorKind::SetupToolImageError {
+        tool: "pnpm".into(),
+        version: version_string.clone(),
+        dir: dest.clone(),
+    })?;
+
+    progress.finish_and_clear();
+
+    // Note: We write this after the progress bar is finished to avoid display bugs with re-renders of the progress
+    debug!("Installing pnpm in '{}'", dest.display());
+
+    Ok(())
+}
+
+/// Determine the remote URL to download from, using the hooks if available
+fn determine_remote_url(version: &Version, hooks: Option<&ToolHooks<Pnpm>>) -> Fallible<String> {
+    let version_str = version.to_string();
+    match hooks {
+        Some(&ToolHooks {
+            distro: Some(ref hook),
+            ..
+        }) => {
+            debug!("Using pnpm.distro hook to determine download URL");
+            let distro_file_name = Pnpm::archive_filename(&version_str);
+            hook.resolve(version, &distro_file_name)
+        }
+        _ => Ok(public_registry_package("pnpm", &version_str)),
+    }
+}
+
+/// Fetch the distro archive from the internet
+fn fetch_remote_distro(
+    version: &Version,
+    url: &str,
+    staging_path: &Path,
+) -> Fallible<Box<dyn Archive>> {
+    debug!("Downloading {} from {}", tool_version("pnpm", version), url);
+    Tarball::fetch(url, staging_path).with_context(download_tool_error(
+        tool::Spec::Pnpm(VersionSpec::Exact(version.clone())),
+        url,
+    ))
+}
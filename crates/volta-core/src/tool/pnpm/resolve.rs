@@ -0,0 +1,125 @@
+//! Provides resolution of pnpm Version requirements into specific versions
+
+use super::super::registry::{
+    find_package_details, public_registry_index, PackageDetails, PackageIndex, RawPackageMetadata,
+    NPM_ABBREVIATED_ACCEPT_HEADER,
+};
+use super::super::registry_fetch_error;
+use crate::error::{Context, ErrorKind, Fallible};
+use crate::hook::ToolHooks;
+use crate::inventory::pnpm_versions;
+use crate::session::Session;
+use crate::style::progress_spinner;
+use crate::tool::offline::{is_offline, resolve_local};
+use crate::tool::{Pnpm, Updates};
+use crate::version::{VersionSpec, VersionTag};
+use attohttpc::header::ACCEPT;
+use attohttpc::Response;
+use log::debug;
+use semver::{Version, VersionReq};
+
+pub fn resolve(matching: VersionSpec, session: &mut Session) -> Fallible<Version> {
+    Ok(resolve_pnpm(matching, session)?.version)
+}
+
+/// Resolve a version requirement into the pnpm to fetch
+///
+/// If the version was found in the registry, its details are kept, so that the download can be
+/// verified without fetching the index again.
+pub(crate) fn resolve_pnpm(matching: VersionSpec, session: &mut Session) -> Fallible<Pnpm> {
+    let hooks = session.hooks()?.pnpm();
+    match matching {
+        // Note: Apart from `latest`, tags are only recorded in the registry, so they can't match
+        matching if is_offline() => {
+            resolve_local("pnpm", matching, pnpm_versions()?, |_, _| false).map(Pnpm::new)
+        }
+        VersionSpec::Semver(requirement) => resolve_semver(requirement, hooks),
+        VersionSpec::Exact(version) => Ok(Pnpm::new(version)),
+        VersionSpec::None | VersionSpec::Tag(VersionTag::Latest) => resolve_tag("latest", hooks),
+        VersionSpec::Tag(tag) => resolve_tag(&tag.to_string(), hooks),
+    }
+}
+
+/// Find the versions of pnpm that are newer than the given version
+pub fn check_updates(version: &Version, session: &mut Session) -> Fallible<Updates> {
+    if is_offline() {
+        debug!("Skipping pnpm update check in offline mode");
+        return Ok(Updates::default());
+    }
+
+    let (_, index) = fetch_pnpm_index(session.hooks()?.pnpm())?;
+    Ok(Updates::from_versions(
+        version,
+        index
+            .entries
+            .into_iter()
+            .map(|PackageDetails { version, .. }| (version, false)),
+    ))
+}
+
+pub(super) fn fetch_pnpm_index(
+    hooks: Option<&ToolHooks<Pnpm>>,
+) -> Fallible<(String, PackageIndex)> {
+    let url = match hooks {
+        Some(&ToolHooks {
+            index: Some(ref hook),
+            ..
+        }) => {
+            debug!("Using pnpm.index hook to determine pnpm index URL");
+            hook.resolve("pnpm")?
+        }
+        _ => public_registry_index("pnpm"),
+    };
+
+    let spinner = progress_spinner(&format!("Fetching public registry: {}", url));
+    let metadata: RawPackageMetadata = attohttpc::get(&url)
+        .header(ACCEPT, NPM_ABBREVIATED_ACCEPT_HEADER)
+        .send()
+        .and_then(Response::error_for_status)
+        .and_then(Response::json)
+        .with_context(registry_fetch_error("pnpm", &url))?;
+
+    spinner.finish_and_clear();
+    Ok((url, metadata.into()))
+}
+
+fn resolve_tag(tag: &str, hooks: Option<&ToolHooks<Pnpm>>) -> Fallible<Pnpm> {
+    let (url, mut index) = fetch_pnpm_index(hooks)?;
+
+    match index.tags.remove(tag) {
+        Some(version) => {
+            debug!("Found pnpm@{} matching tag '{}' from {}", version, tag, url);
+            Ok(match find_package_details(index, &version) {
+                Some(details) => Pnpm::from_details(details),
+                None => Pnpm::new(version),
+            })
+        }
+        None => Err(ErrorKind::PnpmVersionNotFound {
+            matching: tag.into(),
+        }
+        .into()),
+    }
+}
+
+fn resolve_semver(matching: VersionReq, hooks: Option<&ToolHooks<Pnpm>>) -> Fallible<Pnpm> {
+    let (url, index) = fetch_pnpm_index(hooks)?;
+
+    let details_opt = index
+        .entries
+        .into_iter()
+        .find(|PackageDetails { version, .. }| matching.matches(version));
+
+    match details_opt {
+        Some(details) => {
+            debug!(
+                "Found pnpm@{} matching requirement '{}' from {}",
+                details.version, matching, url
+            );
+            Ok(Pnpm::from_details(details))
+        }
+        None => Err(ErrorKind::PnpmVersionNotFound {
+            matching: matching.to_string(),
+        }
+        .into()),
+    }
+}
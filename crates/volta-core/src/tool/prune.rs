@@ -0,0 +1,237 @@
+//! Provides removal of the Node, npm, pnpm, and Yarn versions that are no longer referenced
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use super::uninstall::{inventory_paths, Kind, KINDS};
+use crate::error::{Context, ErrorKind, Fallible};
+use crate::fs::{read_dir_eager, remove_dir_if_exists, remove_file_if_exists};
+use crate::inventory::{
+    all_package_configs, node_versions, npm_versions, pnpm_versions, yarn_versions,
+};
+use crate::layout::volta_home;
+use crate::platform::PlatformSpec;
+use crate::profile::{active_profile, profiles};
+use crate::project::Project;
+use crate::session::Session;
+use crate::style::success_prefix;
+use crate::sync::VoltaLock;
+use crate::usage::last_used;
+use indicatif::HumanBytes;
+use log::info;
+use semver::Version;
+use walkdir::WalkDir;
+
+/// Removes the tool images and cached archives that aren't referenced by any platform
+///
+/// A version is kept if it is used by the default platform of any profile, by the platform of
+/// any package installed in any profile, or by the project in any of the given directories. The
+/// current project is included as well, unless `skip_current_project` is set. If `unused_for` is
+/// given, a version that was used more recently than that is kept as well. Every other image and
+/// archive in the inventory is removed, and the disk space reclaimed is reported for each tool.
+/// If `dry_run` is set, the report is shown without removing anything.
+pub fn prune(
+    session: &mut Session,
+    project_dirs: &[PathBuf],
+    skip_current_project: bool,
+    unused_for: Option<Duration>,
+    dry_run: bool,
+) -> Fallible<()> {
+    // Acquire a lock on the Volta directory, if possible, to prevent concurrent changes
+    let _lock = VoltaLock::acquire();
+    let platforms = referenced_platforms(session, project_dirs, skip_current_project)?;
+
+    let mut total = 0;
+    for &kind in KINDS.iter() {
+        let (versions, paths) = unreferenced_paths(kind, &platforms, unused_for)?;
+        if paths.is_empty() {
+            continue;
+        }
+
+        let size = paths.iter().map(|path| disk_usage(path)).sum();
+        total += size;
+
+        let versions = match versions.len() {
+            0 => String::from("cached archives only"),
+            _ => versions
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", "),
+        };
+
+        if dry_run {
+            info!(
+                "Would prune {}: {} ({})",
+                kind.name(),
+                versions,
+                HumanBytes(size)
+            );
+        } else {
+            for path in paths {
+                if path.is_dir() {
+                    remove_dir_if_exists(path)?;
+                } else {
+                    remove_file_if_exists(path)?;
+                }
+            }
+            info!(
+                "{} pruned {}: {} ({})",
+                success_prefix(),
+                kind.name(),
+                versions,
+                HumanBytes(size)
+            );
+        }
+    }
+
+    match (total, dry_run) {
+        (0, _) => info!("Nothing to prune, all fetched tools are in use"),
+        (_, true) => info!("{} would be freed", HumanBytes(total)),
+        (_, false) => info!("{} freed", HumanBytes(total)),
+    }
+
+    Ok(())
+}
+
+/// Collect every platform that references tool versions which should be kept
+fn referenced_platforms(
+    session: &mut Session,
+    project_dirs: &[PathBuf],
+    skip_current_project: bool,
+) -> Fallible<Vec<PlatformSpec>> {
+    let mut platforms = Vec::new();
+
+    // Note: The default platform of the active profile may have changed in this session, so it is
+    // taken from the session rather than read from its file
+    let active = active_profile()?;
+    if let Some(platform) = session.default_platform()? {
+        platforms.push(platform.clone());
+    }
+    for profile in profiles()? {
+        if profile.name() != active.name() {
+            platforms.extend(profile.platform()?);
+        }
+    }
+
+    platforms.extend(
+        all_package_configs()?
+            .into_iter()
+            .map(|config| config.platform),
+    );
+
+    if !skip_current_project {
+        if let Some(project) = session.project()? {
+            platforms.extend(project.platform()?.cloned());
+        }
+    }
+
+    for dir in project_dirs {
+        let project =
+            Project::for_dir(dir.clone())?.ok_or_else(|| ErrorKind::PruneProjectNotFound {
+                dir: dir.to_owned(),
+            })?;
+
+        platforms.extend(project.platform()?.cloned());
+    }
+
+    Ok(platforms)
+}
+
+/// Determine the versions of a tool that can be removed, along with every path to remove
+///
+/// Along with the images and archives of unreferenced versions, this includes any other file in
+/// the inventory directory, such as an archive left behind after its image was removed.
+fn unreferenced_paths(
+    kind: Kind,
+    platforms: &[PlatformSpec],
+    unused_for: Option<Duration>,
+) -> Fallible<(Vec<Version>, Vec<PathBuf>)> {
+    let home = volta_home()?;
+    let (fetched, inventory_dir) = match kind {
+        Kind::Node => (node_versions()?, home.node_inventory_dir()),
+        Kind::Npm => (npm_versions()?, home.npm_inventory_dir()),
+        Kind::Pnpm => (pnpm_versions()?, home.pnpm_inventory_dir()),
+        Kind::Yarn => (yarn_versions()?, home.yarn_inventory_dir()),
+    };
+
+    let mut versions = Vec::new();
+    let mut paths = Vec::new();
+    let mut keep_files = BTreeSet::new();
+
+    for version in fetched {
+        let (image_dir, inventory_files) = inventory_paths(kind, &version)?;
+        let referenced = platforms
+            .iter()
+            .any(|platform| kind.used_by(platform, &version));
+
+        if referenced || used_within(kind, &version, &image_dir, unused_for) {
+            keep_files.extend(inventory_files);
+        } else {
+            paths.push(image_dir);
+            versions.push(version);
+        }
+    }
+
+    // Note: The inventory files of referenced versions are kept even if there is no image
+    for version in platforms
+        .iter()
+        .filter_map(|platform| kind.version_in(platform))
+    {
+        let (_, inventory_files) = inventory_paths(kind, version)?;
+        keep_files.extend(inventory_files);
+    }
+
+    if inventory_dir.exists() {
+        let entries = read_dir_eager(inventory_dir).with_context(|| ErrorKind::ReadDirError {
+            dir: inventory_dir.to_owned(),
+        })?;
+
+        paths.extend(
+            entries
+                .filter(|(_, metadata)| metadata.is_file())
+                .map(|(entry, _)| entry.path())
+                .filter(|path| !keep_files.contains(path)),
+        );
+    }
+
+    Ok((versions, paths))
+}
+
+/// Determine whether a version was used within the given duration
+///
+/// Versions that were fetched before usage was tracked, or never run, fall back to the time
+/// their image was unpacked.
+fn used_within(
+    kind: Kind,
+    version: &Version,
+    image_dir: &Path,
+    unused_for: Option<Duration>,
+) -> bool {
+    let unused_for = match unused_for {
+        Some(duration) => duration,
+        None => return false,
+    };
+
+    let elapsed = last_used(kind.name(), version)
+        .or_else(|| image_dir.metadata().and_then(|meta| meta.modified()).ok())
+        .and_then(|time| SystemTime::now().duration_since(time).ok());
+
+    match elapsed {
+        Some(elapsed) => elapsed < unused_for,
+        // If the time can't be determined, err on the side of keeping the version
+        None => true,
+    }
+}
+
+/// Calculate the total size of the files in a directory, or the size of a single file
+fn disk_usage(path: &Path) -> u64 {
+    WalkDir::new(path)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter_map(|entry| entry.metadata().ok())
+        .filter(|metadata| metadata.is_file())
+        .map(|metadata| metadata.len())
+        .sum()
+}
@@ -1,12 +1,19 @@
 use std::collections::HashMap;
+use std::fs::File;
 use std::path::{Path, PathBuf};
 
 use crate::error::{Context, ErrorKind, Fallible};
-use crate::fs::read_dir_eager;
+use crate::fs::{hash_file, read_dir_eager, remove_file_if_exists};
+use crate::project::PackageManagerHash;
+use crate::tool;
 use crate::version::{hashmap_version_serde, version_serde};
+use archive::{Archive, Tarball};
 use cfg_if::cfg_if;
+use log::debug;
 use semver::Version;
 use serde::Deserialize;
+use sha1::Sha1;
+use sha2::Sha512;
 
 // Accept header needed to request the abbreviated metadata from the npm registry
 // See https://github.com/npm/registry/blob/master/docs/responses/package-metadata.md
@@ -26,10 +33,12 @@ cfg_if! {
 }
 
 pub fn public_registry_package(package: &str, version: &str) -> String {
+    // Note: The tarball for a scoped package is named without the scope
+    let basename = package.rsplit('/').next().unwrap_or(package);
     format!(
         "{}/-/{}-{}.tgz",
         public_registry_index(package),
-        package,
+        basename,
         version
     )
 }
@@ -53,11 +62,121 @@ pub fn find_unpack_dir(in_dir: &Path) -> Fallible<PathBuf> {
 }
 
 /// Details about a package in the npm Registry
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct PackageDetails {
     pub(crate) version: Version,
     pub(crate) tarball_url: String,
     pub(crate) shasum: String,
+    pub(crate) integrity: Option<String>,
+}
+
+impl PackageDetails {
+    /// Check whether a tarball matches the integrity metadata for this package version
+    ///
+    /// Uses the SHA-512 Subresource Integrity string from `dist.integrity` when it is available,
+    /// falling back to the SHA-1 `dist.shasum` for older packages.
+    pub(crate) fn matches_integrity(&self, file: &Path) -> bool {
+        let sha512 = self.integrity.as_ref().and_then(|integrity| {
+            integrity
+                .split_whitespace()
+                .find_map(|hash| hash.strip_prefix("sha512-"))
+        });
+
+        let matches = match sha512 {
+            Some(expected) => {
+                hash_file::<Sha512>(file).map(|actual| base64::encode(&actual) == expected)
+            }
+            None => hash_file::<Sha1>(file)
+                .map(|actual| hex::encode(&actual).eq_ignore_ascii_case(&self.shasum)),
+        };
+
+        matches.unwrap_or(false)
+    }
+}
+
+/// Load a tarball from the inventory, if it is there
+///
+/// The tarball is checked against the registry `details` of its version, along with the `hash`
+/// from the project's `packageManager` field, if any. The details are only missing in offline
+/// mode, when the registry can't be checked. A tarball that can't be loaded or doesn't match is
+/// removed so that it will be fetched again.
+pub(crate) fn load_cached_tarball(
+    file: &Path,
+    details: Option<&PackageDetails>,
+    hash: Option<&PackageManagerHash>,
+) -> Option<Box<dyn Archive>> {
+    if !file.is_file() {
+        return None;
+    }
+
+    let matches = details
+        .iter()
+        .all(|details| details.matches_integrity(file))
+        && hash.iter().all(|hash| hash.matches(file));
+    let archive = if matches {
+        File::open(file)
+            .ok()
+            .and_then(|file| Tarball::load(file).ok())
+    } else {
+        None
+    };
+
+    if archive.is_none() {
+        debug!(
+            "Cached archive at '{}' is not valid, removing",
+            file.display()
+        );
+        // Note: Failing to remove the file isn't fatal, since it will be replaced by the new
+        // download in any case
+        let _ = remove_file_if_exists(file);
+    }
+
+    archive
+}
+
+/// Verify that a downloaded tarball matches the registry `details` of its version and the `hash`
+/// from the project's `packageManager` field, if any
+pub(crate) fn verify_tarball(
+    file: &Path,
+    tool: tool::Spec,
+    url: &str,
+    details: &PackageDetails,
+    hash: Option<&PackageManagerHash>,
+) -> Fallible<()> {
+    if details.matches_integrity(file) && hash.iter().all(|hash| hash.matches(file)) {
+        Ok(())
+    } else {
+        Err(ErrorKind::ChecksumMismatch {
+            tool,
+            from_url: url.into(),
+        }
+        .into())
+    }
+}
+
+/// Find the details of a version in the index fetched from `index_url`, which are needed to
+/// verify its tarball
+pub(crate) fn version_details(
+    tool: tool::Spec,
+    index_url: String,
+    index: PackageIndex,
+    version: &Version,
+) -> Fallible<PackageDetails> {
+    find_package_details(index, version).ok_or_else(|| {
+        ErrorKind::ChecksumNotFound {
+            tool,
+            from_url: index_url,
+        }
+        .into()
+    })
+}
+
+/// Find the details for a specific version in the index of a package
+pub fn find_package_details(index: PackageIndex, version: &Version) -> Option<PackageDetails> {
+    index
+        .entries
+        .into_iter()
+        .find(|details| &details.version == version)
 }
 
 /// Index of versions of a specific package from the npm Registry
@@ -93,6 +212,9 @@ pub struct RawPackageVersionInfo {
 pub struct RawDistInfo {
     pub shasum: String,
     pub tarball: String,
+    // Note: Older packages were published before the registry included `integrity`
+    #[serde(default)]
+    pub integrity: Option<String>,
 }
 
 impl From<RawPackageMetadata> for PackageIndex {
@@ -104,6 +226,7 @@ impl From<RawPackageMetadata> for PackageIndex {
                 version: version_info.version,
                 tarball_url: version_info.dist.tarball,
                 shasum: version_info.dist.shasum,
+                integrity: version_info.dist.integrity,
             })
             .collect();
 
@@ -1,5 +1,6 @@
 use std::cmp::Ordering;
 
+use super::package::is_package_source;
 use super::Spec;
 use crate::error::{ErrorKind, Fallible};
 use crate::version::{VersionSpec, VersionTag};
@@ -20,13 +21,20 @@ impl Spec {
         match tool_name {
             "node" => Spec::Node(version),
             "npm" => Spec::Npm(version),
+            "pnpm" => Spec::Pnpm(version),
             "yarn" => Spec::Yarn(version),
             package => Spec::Package(package.to_string(), version),
         }
     }
 
     /// Try to parse a tool and version from a string like `<tool>[@<version>].
+    ///
+    /// A git URL, tarball, or path to a local directory is parsed as the source of a package.
     pub fn try_from_str(tool_spec: &str) -> Fallible<Self> {
+        if is_package_source(tool_spec) {
+            return Ok(Spec::PackageSource(tool_spec.into()));
+        }
+
         let captures =
             TOOL_SPEC_PATTERN
                 .captures(tool_spec)
@@ -53,6 +61,7 @@ impl Spec {
         Ok(match name {
             "node" => Spec::Node(version),
             "npm" => Spec::Npm(version),
+            "pnpm" => Spec::Pnpm(version),
             "yarn" => Spec::Yarn(version),
             package => Spec::Package(package.into(), version),
         })
@@ -116,7 +125,7 @@ impl Spec {
     ///
     /// We want to preserve the original order as much as possible, so we treat tools in
     /// the same tool category as equal. We still need to pull Node to the front of the
-    /// list, followed by Npm / Yarn, and then Packages last.
+    /// list, followed by Npm / pnpm / Yarn, and then Packages last.
     fn sort_comparator(left: &Spec, right: &Spec) -> Ordering {
         match (left, right) {
             (Spec::Node(_), Spec::Node(_)) => Ordering::Equal,
@@ -125,10 +134,14 @@ impl Spec {
             (Spec::Npm(_), Spec::Npm(_)) => Ordering::Equal,
             (Spec::Npm(_), _) => Ordering::Less,
             (_, Spec::Npm(_)) => Ordering::Greater,
+            (Spec::Pnpm(_), Spec::Pnpm(_)) => Ordering::Equal,
+            (Spec::Pnpm(_), _) => Ordering::Less,
+            (_, Spec::Pnpm(_)) => Ordering::Greater,
             (Spec::Yarn(_), Spec::Yarn(_)) => Ordering::Equal,
             (Spec::Yarn(_), _) => Ordering::Less,
             (_, Spec::Yarn(_)) => Ordering::Greater,
-            (Spec::Package(_, _), Spec::Package(_, _)) => Ordering::Equal,
+            // Packages from the registry and from other sources are both treated as packages
+            (_, _) => Ordering::Equal,
         }
     }
 }
@@ -239,6 +252,34 @@ mod tests {
             );
         }
 
+        #[test]
+        fn parses_bare_pnpm() {
+            assert_eq!(
+                Spec::try_from_str("pnpm").expect("succeeds"),
+                Spec::Pnpm(VersionSpec::default())
+            );
+        }
+
+        #[test]
+        fn parses_pnpm_with_valid_versions() {
+            let tool = "pnpm";
+
+            assert_eq!(
+                Spec::try_from_str(&versioned_tool!(tool, MAJOR)).expect("succeeds"),
+                Spec::Pnpm(VersionSpec::from_str(MAJOR).expect("`VersionSpec` has its own tests"))
+            );
+
+            assert_eq!(
+                Spec::try_from_str(&versioned_tool!(tool, PATCH)).expect("succeeds"),
+                Spec::Pnpm(VersionSpec::from_str(PATCH).expect("`VersionSpec` has its own tests"))
+            );
+
+            assert_eq!(
+                Spec::try_from_str(&versioned_tool!(tool, LATEST)).expect("succeeds"),
+                Spec::Pnpm(VersionSpec::Tag(VersionTag::Latest))
+            );
+        }
+
         #[test]
         fn parses_bare_packages() {
             let package = "ember-cli";
@@ -257,6 +298,20 @@ mod tests {
             );
         }
 
+        #[test]
+        fn parses_package_sources() {
+            for source in &[
+                "github:org/tool",
+                "./my-cli",
+                "https://example.com/tool.tgz",
+            ] {
+                assert_eq!(
+                    Spec::try_from_str(source).expect("succeeds"),
+                    Spec::PackageSource((*source).into())
+                );
+            }
+        }
+
         #[test]
         fn parses_bare_packages_with_valid_versions() {
             let package = "something-awesome";
@@ -426,12 +481,14 @@ mod tests {
             let multiple = [
                 "ember-cli@3".to_owned(),
                 "yarn".to_owned(),
+                "pnpm@8".to_owned(),
                 "npm@5".to_owned(),
                 "node@latest".to_owned(),
             ];
             let expected = [
                 Spec::Node(VersionSpec::Tag(VersionTag::Latest)),
                 Spec::Npm(VersionSpec::from_str("5").expect("requirement is valid")),
+                Spec::Pnpm(VersionSpec::from_str("8").expect("requirement is valid")),
                 Spec::Yarn(VersionSpec::default()),
                 Spec::Package(
                     "ember-cli".to_owned(),
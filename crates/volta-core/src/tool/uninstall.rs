@@ -0,0 +1,185 @@
+//! Provides removal of Node, npm, pnpm, and Yarn versions from the local inventory
+
+use std::path::PathBuf;
+
+use super::{Node, Npm, Pnpm, Spec, Yarn};
+use crate::error::{ErrorKind, Fallible};
+use crate::fs::{remove_dir_if_exists, remove_file_if_exists};
+use crate::inventory::all_package_configs;
+use crate::layout::volta_home;
+use crate::platform::PlatformSpec;
+use crate::profile::{active_profile, profiles};
+use crate::session::Session;
+use crate::style::{success_prefix, tool_version};
+use crate::sync::VoltaLock;
+use crate::version::VersionSpec;
+use log::{info, warn};
+use semver::Version;
+
+/// The tools that can be uninstalled as a specific version from the inventory
+#[derive(Clone, Copy, PartialEq)]
+pub(super) enum Kind {
+    Node,
+    Npm,
+    Pnpm,
+    Yarn,
+}
+
+/// Every kind of tool, in the order they are reported
+pub(super) const KINDS: [Kind; 4] = [Kind::Node, Kind::Npm, Kind::Pnpm, Kind::Yarn];
+
+impl Kind {
+    pub(super) fn name(self) -> &'static str {
+        match self {
+            Kind::Node => "node",
+            Kind::Npm => "npm",
+            Kind::Pnpm => "pnpm",
+            Kind::Yarn => "yarn",
+        }
+    }
+
+    /// The version of the tool used by the given platform, if any
+    pub(super) fn version_in(self, platform: &PlatformSpec) -> Option<&Version> {
+        match self {
+            Kind::Node => Some(&platform.node),
+            Kind::Npm => platform.npm.as_ref(),
+            Kind::Pnpm => platform.pnpm.as_ref(),
+            Kind::Yarn => platform.yarn.as_ref(),
+        }
+    }
+
+    /// Determine whether the given platform uses this exact version of the tool
+    pub(super) fn used_by(self, platform: &PlatformSpec, version: &Version) -> bool {
+        self.version_in(platform) == Some(version)
+    }
+}
+
+/// Uninstalls a specific version of Node, npm, pnpm, or Yarn.
+///
+/// This removes:
+///
+/// - The unpacked image directory
+/// - The cached archive in the inventory directory
+/// - For Node, the file recording the bundled npm version
+///
+/// If the version is used by the default platform of any profile or by any installed package, the
+/// uninstall is refused unless `force` is set.
+pub(super) fn uninstall(spec: Spec, session: &mut Session, force: bool) -> Fallible<()> {
+    let (kind, matching) = match spec {
+        Spec::Node(matching) => (Kind::Node, matching),
+        Spec::Npm(matching) => (Kind::Npm, matching),
+        Spec::Pnpm(matching) => (Kind::Pnpm, matching),
+        Spec::Yarn(matching) => (Kind::Yarn, matching),
+        Spec::Package(name, _) => return super::package::uninstall(&name),
+        Spec::PackageSource(source) => return super::package::uninstall_source(&source),
+    };
+
+    let version = match matching {
+        VersionSpec::Exact(version) => version,
+        _ => {
+            return Err(ErrorKind::UninstallExactVersionRequired {
+                tool: kind.name().into(),
+            }
+            .into())
+        }
+    };
+
+    // Acquire a lock on the Volta directory, if possible, to prevent concurrent changes
+    let _lock = VoltaLock::acquire();
+    let tool = tool_version(kind.name(), &version);
+
+    if !force {
+        let dependents = find_dependents(kind, &version, session)?;
+        if !dependents.is_empty() {
+            return Err(ErrorKind::UninstallToolInUse { tool, dependents }.into());
+        }
+    }
+
+    let (image_dir, inventory_files) = inventory_paths(kind, &version)?;
+
+    let image_found = image_dir.exists();
+
+    // Note: We always clean up the inventory files, even if there is no image to remove
+    remove_dir_if_exists(image_dir)?;
+    for file in inventory_files {
+        remove_file_if_exists(file)?;
+    }
+
+    if image_found {
+        info!("{} {} uninstalled", success_prefix(), tool);
+    } else {
+        warn!("No {} found to uninstall", tool);
+    }
+
+    Ok(())
+}
+
+/// Collect descriptions of everything that depends on the given tool version
+fn find_dependents(kind: Kind, version: &Version, session: &Session) -> Fallible<Vec<String>> {
+    let mut dependents = Vec::new();
+
+    let active = active_profile()?;
+    if let Some(platform) = session.default_platform()? {
+        if kind.used_by(platform, version) {
+            dependents.push("the default platform".to_string());
+        }
+    }
+    for profile in profiles()? {
+        if profile.name() == active.name() {
+            continue;
+        }
+        if let Some(platform) = profile.platform()? {
+            if kind.used_by(&platform, version) {
+                dependents.push(format!(
+                    "the default platform of profile '{}'",
+                    profile.name()
+                ));
+            }
+        }
+    }
+
+    // Note: The same package may be installed in several profiles, but it is only listed once
+    for config in all_package_configs()? {
+        let dependent = format!("package '{}'", config.name);
+        if kind.used_by(&config.platform, version) && !dependents.contains(&dependent) {
+            dependents.push(dependent);
+        }
+    }
+
+    Ok(dependents)
+}
+
+/// Determine the image directory and the inventory files for the given tool version
+pub(super) fn inventory_paths(kind: Kind, version: &Version) -> Fallible<(PathBuf, Vec<PathBuf>)> {
+    let home = volta_home()?;
+    let version_str = version.to_string();
+
+    Ok(match kind {
+        Kind::Node => (
+            home.node_image_dir(&version_str),
+            vec![
+                home.node_inventory_dir()
+                    .join(Node::archive_filename(version)),
+                home.node_npm_version_file(&version_str),
+            ],
+        ),
+        Kind::Npm => (
+            home.npm_image_dir(&version_str),
+            vec![home
+                .npm_inventory_dir()
+                .join(Npm::archive_filename(&version_str))],
+        ),
+        Kind::Pnpm => (
+            home.pnpm_image_dir(&version_str),
+            vec![home
+                .pnpm_inventory_dir()
+                .join(Pnpm::archive_filename(&version_str))],
+        ),
+        Kind::Yarn => (
+            home.yarn_image_dir(&version_str),
+            vec![home
+                .yarn_inventory_dir()
+                .join(Yarn::archive_filename(&version_str))],
+        ),
+    })
+}
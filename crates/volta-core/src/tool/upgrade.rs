@@ -0,0 +1,216 @@
+//! Provides the upgrade of the default tools and global packages to the newest versions that
+//! match the versions they were installed with
+
+use std::fmt::{self, Display};
+
+use super::{node, npm, package, pnpm, yarn};
+use super::{Node, Npm, Package, PackageConfig, Pnpm, Tool, Yarn};
+use crate::error::{ErrorKind, Fallible};
+use crate::inventory::package_configs;
+use crate::session::Session;
+use crate::style::success_prefix;
+use crate::version::{parse_requirements, VersionSpec};
+use log::info;
+use semver::Version;
+
+/// A tool that can be upgraded
+enum Target {
+    Node(Version),
+    Npm(Version),
+    Pnpm(Version),
+    Yarn(Version),
+    Package(Box<PackageConfig>),
+}
+
+impl Target {
+    /// The name the tool is known by, which for a package is the name it is installed as
+    fn name(&self) -> &str {
+        match self {
+            Target::Node(_) => "node",
+            Target::Npm(_) => "npm",
+            Target::Pnpm(_) => "pnpm",
+            Target::Yarn(_) => "yarn",
+            Target::Package(config) => config.install_name(),
+        }
+    }
+
+    fn version(&self) -> &Version {
+        match self {
+            Target::Node(version)
+            | Target::Npm(version)
+            | Target::Pnpm(version)
+            | Target::Yarn(version) => version,
+            Target::Package(config) => &config.version,
+        }
+    }
+}
+
+/// An upgrade of a tool from its current version to a newer one
+struct Upgrade {
+    target: Target,
+    to: Version,
+}
+
+impl Display for Upgrade {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} -> {}",
+            self.target.name(),
+            self.target.version(),
+            self.to
+        )
+    }
+}
+
+/// Upgrades the default tools and global packages to the newest versions they allow
+///
+/// Node, npm, pnpm, and Yarn are upgraded to the newest version with the same major version,
+/// as are packages, unless a package was installed with a version range, in which case it is
+/// upgraded to the newest version in that range. Packages installed from a git URL, tarball, or
+/// local directory are skipped, since the version they resolve to isn't known without installing
+/// them again. Packages keep the platform they were installed with.
+///
+/// Without any `tools`, only the default platform is upgraded, while `all` upgrades every global
+/// package as well. The plan is shown before any upgrade is installed.
+pub fn upgrade(session: &mut Session, tools: &[String], all: bool) -> Fallible<()> {
+    let mut upgrades = Vec::new();
+    for target in select_targets(session, tools, all)? {
+        upgrades.extend(plan_upgrade(target, session)?);
+    }
+
+    if upgrades.is_empty() {
+        info!("{} Everything is already up to date", success_prefix());
+        return Ok(());
+    }
+
+    info!("Upgrading:");
+    for upgrade in &upgrades {
+        info!("    {}", upgrade);
+    }
+
+    let count = upgrades.len();
+    for upgrade in upgrades {
+        install_upgrade(upgrade, session)?;
+    }
+
+    info!(
+        "{} upgraded {} {}",
+        success_prefix(),
+        count,
+        if count == 1 { "tool" } else { "tools" }
+    );
+    Ok(())
+}
+
+/// Determine the tools to upgrade, in the order they should be upgraded
+///
+/// The default platform comes first, so that the Node it uses is upgraded before the packages.
+fn select_targets(session: &mut Session, tools: &[String], all: bool) -> Fallible<Vec<Target>> {
+    let mut targets = Vec::new();
+    if let Some(platform) = session.default_platform()? {
+        targets.push(Target::Node(platform.node.clone()));
+        targets.extend(platform.npm.clone().map(Target::Npm));
+        targets.extend(platform.pnpm.clone().map(Target::Pnpm));
+        targets.extend(platform.yarn.clone().map(Target::Yarn));
+    }
+
+    if tools.is_empty() && !all {
+        return Ok(targets);
+    }
+    targets.extend(
+        package_configs()?
+            .into_iter()
+            .map(|config| Target::Package(Box::new(config))),
+    );
+
+    if all {
+        return Ok(targets);
+    }
+
+    for tool in tools {
+        if !targets.iter().any(|target| target.name() == tool) {
+            return Err(ErrorKind::UpgradeToolNotFound { tool: tool.clone() }.into());
+        }
+    }
+
+    Ok(targets
+        .into_iter()
+        .filter(|target| tools.iter().any(|tool| tool == target.name()))
+        .collect())
+}
+
+/// Find the version a tool should be upgraded to, if there is a newer one
+fn plan_upgrade(target: Target, session: &mut Session) -> Fallible<Option<Upgrade>> {
+    let to = match &target {
+        Target::Node(version) => node::check_updates(version, session)?.latest_in_major,
+        Target::Npm(version) => npm::check_updates(version, session)?.latest_in_major,
+        Target::Pnpm(version) => pnpm::check_updates(version, session)?.latest_in_major,
+        Target::Yarn(version) => yarn::check_updates(version, session)?.latest_in_major,
+        Target::Package(config) => match (&config.source, &config.range) {
+            (Some(source), _) => {
+                info!(
+                    "Skipping {}, which was installed from {}",
+                    config.install_name(),
+                    source
+                );
+                None
+            }
+            (None, Some(range)) => {
+                let range = parse_requirements(range)?;
+                package::check_range_update(&config.name, &config.version, &range)?
+            }
+            (None, None) => package::check_updates(&config.name, &config.version)?.latest_in_major,
+        },
+    };
+
+    Ok(to.map(|to| Upgrade { target, to }))
+}
+
+fn install_upgrade(upgrade: Upgrade, session: &mut Session) -> Fallible<()> {
+    let to = upgrade.to;
+    let tool: Box<dyn Tool> = match upgrade.target {
+        Target::Node(_) => Box::new(Node::new(to)),
+        Target::Npm(_) => Box::new(Npm::new(to)),
+        Target::Pnpm(_) => Box::new(Pnpm::new(to)),
+        Target::Yarn(_) => Box::new(Yarn::new(to)),
+        Target::Package(config) => Box::new(upgraded_package(*config, to)?),
+    };
+
+    tool.install(session)
+}
+
+/// Create the package to install for an upgrade, which is the exact version that was planned,
+/// keeping the range, alias, and platform overrides of the installed package
+fn upgraded_package(config: PackageConfig, to: Version) -> Fallible<Package> {
+    let package = Package::with_manager(config.name, VersionSpec::Exact(to), config.manager)?;
+    let package = match config.range {
+        Some(range) => package.with_range(range),
+        None => package,
+    };
+
+    let package = match config.overrides.to_cli_platform(&config.platform) {
+        Some(platform) => package.with_platform(platform),
+        None => package,
+    };
+
+    Ok(match config.alias {
+        Some(alias) => package.with_alias(alias),
+        None => package,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Target, Upgrade};
+
+    #[test]
+    fn test_upgrade_display() {
+        let upgrade = Upgrade {
+            target: Target::Node("12.4.0".parse().unwrap()),
+            to: "12.22.1".parse().unwrap(),
+        };
+
+        assert_eq!(upgrade.to_string(), "node 12.4.0 -> 12.22.1");
+    }
+}
@@ -1,63 +1,131 @@
 //! Provides fetcher for Yarn distributions
 
-use std::fs::File;
 use std::path::Path;
 
 use super::super::download_tool_error;
-use super::super::registry::{find_unpack_dir, public_registry_package};
+use super::super::launcher::write_launchers;
+use super::super::registry::{
+    find_unpack_dir, load_cached_tarball, public_registry_package, verify_tarball, version_details,
+    PackageDetails,
+};
+use super::resolve::fetch_yarn_index;
+use super::{is_berry, registry_package};
 use crate::error::{Context, ErrorKind, Fallible};
-use crate::fs::{create_staging_dir, create_staging_file, rename};
+use crate::fs::{
+    create_staging_dir, create_staging_file, read_dir_eager, remove_file_if_exists, rename,
+};
 use crate::hook::ToolHooks;
 use crate::layout::volta_home;
+use crate::project::PackageManagerHash;
 use crate::style::{progress_bar, tool_version};
-use crate::tool::{self, Yarn};
+use crate::tool::{self, is_offline, Yarn};
 use crate::version::VersionSpec;
 use archive::{Archive, Tarball};
 use fs_utils::ensure_containing_dir_exists;
 use log::debug;
 use semver::Version;
 
-pub fn fetch(version: &Version, hooks: Option<&ToolHooks<Yarn>>) -> Fallible<()> {
+/// Fetch the given version, verifying it against the registry integrity and the `hash` from the
+/// project's `packageManager` field, if any
+///
+/// The registry `details` of the version are only looked up if they weren't found when resolving
+/// it, which for a download happens once it is complete.
+pub fn fetch(
+    version: &Version,
+    details: Option<PackageDetails>,
+    hooks: Option<&ToolHooks<Yarn>>,
+    hash: Option<&PackageManagerHash>,
+) -> Fallible<()> {
     let yarn_dir = volta_home()?.yarn_inventory_dir();
     let cache_file = yarn_dir.join(Yarn::archive_filename(&version.to_string()));
 
-    let (archive, staging) = match load_cached_distro(&cache_file) {
-        Some(archive) => {
-            debug!(
-                "Loading {} from cached archive at '{}'",
-                tool_version("yarn", &version),
-                cache_file.display(),
-            );
-            (archive, None)
+    // Note: Corepack hashes the tarball for Yarn 1, but the bundled script for Yarn 2+, so the
+    // hash for those versions can only be checked once the tarball is unpacked
+    let tarball_hash = hash.filter(|_| !is_berry(version));
+    let cache_url = cache_file.to_string_lossy().into_owned();
+
+    // Note: In offline mode, the registry can't be checked, so a cached archive is used without
+    // verifying its integrity
+    let details = match details {
+        _ if is_offline() => None,
+        None if cache_file.is_file() => Some(fetch_details(version)?),
+        details => details,
+    };
+
+    if let Some(archive) = load_cached_tarball(&cache_file, details.as_ref(), tarball_hash) {
+        debug!(
+            "Loading {} from cached archive at '{}'",
+            tool_version("yarn", version),
+            cache_file.display(),
+        );
+        match unpack_archive(archive, version, |unpack_dir| {
+            verify_berry_hash(unpack_dir, version, hash, &cache_url)
+        }) {
+            Err(error)
+                if matches!(
+                    error.kind(),
+                    ErrorKind::UnpackArchiveError { .. } | ErrorKind::ChecksumMismatch { .. }
+                ) =>
+            {
+                debug!(
+                    "Cached archive at '{}' could not be unpacked or verified, removing",
+                    cache_file.display()
+                );
+                // Note: Failing to remove the file isn't fatal, since it will be replaced by the
+                // new download in any case
+                let _ = remove_file_if_exists(&cache_file);
+            }
+            result => return result,
         }
-        None => {
-            let staging = create_staging_file()?;
-            let remote_url = determine_remote_url(&version, hooks)?;
-            let archive = fetch_remote_distro(&version, &remote_url, staging.path())?;
-            (archive, Some(staging))
+    }
+
+    // Note: Offline mode can still use an archive from the inventory, but not download one
+    if is_offline() {
+        return Err(ErrorKind::OfflineFetchError {
+            tool: tool::Spec::Yarn(VersionSpec::Exact(version.clone())),
         }
-    };
+        .into());
+    }
 
-    unpack_archive(archive, version)?;
+    let staging = create_staging_file()?;
+    let remote_url = determine_remote_url(version, hooks)?;
+    let archive = fetch_remote_distro(version, &remote_url, staging.path())?;
 
-    if let Some(staging_file) = staging {
-        ensure_containing_dir_exists(&cache_file).with_context(|| {
-            ErrorKind::ContainingDirError {
-                path: cache_file.clone(),
-            }
+    unpack_archive(archive, version, |unpack_dir| {
+        let details = match details {
+            Some(details) => details,
+            None => fetch_details(version)?,
+        };
+        verify_tarball(
+            staging.path(),
+            tool::Spec::Yarn(VersionSpec::Exact(version.clone())),
+            &remote_url,
+            &details,
+            tarball_hash,
+        )?;
+        verify_berry_hash(unpack_dir, version, hash, &remote_url)
+    })?;
+
+    ensure_containing_dir_exists(&cache_file).with_context(|| ErrorKind::ContainingDirError {
+        path: cache_file.clone(),
+    })?;
+    staging
+        .persist(cache_file)
+        .with_context(|| ErrorKind::PersistInventoryError {
+            tool: "Yarn".into(),
         })?;
-        staging_file
-            .persist(cache_file)
-            .with_context(|| ErrorKind::PersistInventoryError {
-                tool: "Yarn".into(),
-            })?;
-    }
 
     Ok(())
 }
 
 /// Unpack the yarn archive into the image directory so that it is ready for use
-fn unpack_archive(archive: Box<dyn Archive>, version: &Version) -> Fallible<()> {
+///
+/// The `verify` callback is run with the unpacked package directory after the archive has been
+/// unpacked into a staging directory and before it is moved into the image directory.
+fn unpack_archive<F>(archive: Box<dyn Archive>, version: &Version, verify: F) -> Fallible<()>
+where
+    F: FnOnce(&Path) -> Fallible<()>,
+{
     let temp = create_staging_dir()?;
     debug!("Unpacking yarn into '{}'", temp.path().display());
 
@@ -79,11 +147,21 @@ fn unpack_archive(archive: Box<dyn Archive>, version: &Version) -> Fallible<()>
             version: version_string.clone(),
         })?;
 
+    let unpack_dir = find_unpack_dir(temp.path())?;
+    verify(&unpack_dir)?;
+
+    // Yarn 2+ only ships the bundled script, so we need to write our own launchers
+    if is_berry(version) {
+        let bin_path = unpack_dir.join("bin");
+        let entry_point = find_berry_entry_point(&bin_path)?;
+        write_launchers(&bin_path, "yarn", &entry_point)?;
+        write_launchers(&bin_path, "yarnpkg", &entry_point)?;
+    }
+
     let dest = volta_home()?.yarn_image_dir(&version_string);
     ensure_containing_dir_exists(&dest)
         .with_context(|| ErrorKind::ContainingDirError { path: dest.clone() })?;
 
-    let unpack_dir = find_unpack_dir(temp.path())?;
     rename(unpack_dir, &dest).with_context(|| ErrorKind::SetupToolImageError {
         tool: "Yarn".into(),
         version: version_string.clone(),
@@ -98,15 +176,62 @@ fn unpack_archive(archive: Box<dyn Archive>, version: &Version) -> Fallible<()>
     Ok(())
 }
 
-/// Return the archive if it is valid. It may have been corrupted or interrupted in the middle of
-/// downloading.
-// ISSUE(#134) - verify checksum
-fn load_cached_distro(file: &Path) -> Option<Box<dyn Archive>> {
-    if file.is_file() {
-        let file = File::open(file).ok()?;
-        Tarball::load(file).ok()
+/// Find the bundled script in the `bin` directory of a Yarn 2+ distribution
+///
+/// The registry package names it `yarn.js`, while the standalone releases (as vendored with
+/// `yarnPath` in `.yarnrc.yml`) are named `yarn-<version>.cjs`.
+fn find_berry_entry_point(bin_path: &Path) -> Fallible<String> {
+    if bin_path.join("yarn.js").is_file() {
+        return Ok("yarn.js".into());
+    }
+
+    read_dir_eager(bin_path)
+        .with_context(|| ErrorKind::PackageUnpackError)?
+        .filter(|(_, metadata)| metadata.is_file())
+        .filter_map(|(entry, _)| entry.file_name().into_string().ok())
+        .find(|name| name.starts_with("yarn-") && name.ends_with(".cjs"))
+        .ok_or_else(|| ErrorKind::PackageUnpackError.into())
+}
+
+/// Fetch the registry details of the given version, which are used to verify its tarball
+///
+/// The legacy format used by the `yarn.index` hook doesn't include any integrity metadata, so the
+/// details always come from the registry package that publishes the version.
+fn fetch_details(version: &Version) -> Fallible<PackageDetails> {
+    let (url, index) = fetch_yarn_index(registry_package(version))?;
+    version_details(
+        tool::Spec::Yarn(VersionSpec::Exact(version.clone())),
+        url,
+        index,
+        version,
+    )
+}
+
+/// Verify the bundled script of an unpacked Yarn 2+ package against the `hash` from the project's
+/// `packageManager` field, if any
+///
+/// For those versions, Corepack calculates the hash from `bin/yarn.js` rather than the tarball.
+fn verify_berry_hash(
+    unpack_dir: &Path,
+    version: &Version,
+    hash: Option<&PackageManagerHash>,
+    url: &str,
+) -> Fallible<()> {
+    let hash = match hash {
+        Some(hash) if is_berry(version) => hash,
+        _ => return Ok(()),
+    };
+
+    let bin_path = unpack_dir.join("bin");
+    let entry_point = find_berry_entry_point(&bin_path)?;
+    if hash.matches(&bin_path.join(entry_point)) {
+        Ok(())
     } else {
-        None
+        Err(ErrorKind::ChecksumMismatch {
+            tool: tool::Spec::Yarn(VersionSpec::Exact(version.clone())),
+            from_url: url.into(),
+        }
+        .into())
     }
 }
 
@@ -122,7 +247,10 @@ fn determine_remote_url(version: &Version, hooks: Option<&ToolHooks<Yarn>>) -> F
             let distro_file_name = Yarn::archive_filename(&version_str);
             hook.resolve(&version, &distro_file_name)
         }
-        _ => Ok(public_registry_package("yarn", &version_str)),
+        _ => Ok(public_registry_package(
+            registry_package(version),
+            &version_str,
+        )),
     }
 }
 
@@ -1,11 +1,13 @@
 use std::fmt::{self, Display};
 
+use super::registry::PackageDetails;
 use super::{
     check_fetched, debug_already_fetched, info_fetched, info_installed, info_pinned,
     info_project_version, FetchStatus, Tool,
 };
 use crate::error::{ErrorKind, Fallible};
 use crate::inventory::yarn_available;
+use crate::project::PackageManagerKind;
 use crate::session::Session;
 use crate::style::tool_version;
 use crate::sync::VoltaLock;
@@ -15,16 +17,50 @@ mod fetch;
 mod metadata;
 mod resolve;
 
-pub use resolve::resolve;
+pub(crate) use resolve::resolve_yarn;
+pub use resolve::{check_updates, resolve};
+
+/// The registry package for Yarn 1 ("Classic")
+const CLASSIC_PACKAGE: &str = "yarn";
+
+/// The registry package for Yarn 2+ ("Berry"), which ships as a single bundled script
+const BERRY_PACKAGE: &str = "@yarnpkg/cli-dist";
+
+/// Determine whether the given version of Yarn is Yarn 2+ ("Berry")
+fn is_berry(version: &Version) -> bool {
+    version.major >= 2
+}
+
+/// Determine the registry package that publishes the given version of Yarn
+fn registry_package(version: &Version) -> &'static str {
+    if is_berry(version) {
+        BERRY_PACKAGE
+    } else {
+        CLASSIC_PACKAGE
+    }
+}
 
 /// The Tool implementation for fetching and installing Yarn
 pub struct Yarn {
     pub(super) version: Version,
+    /// The registry details of the version, if they were looked up when resolving it
+    details: Option<PackageDetails>,
 }
 
 impl Yarn {
     pub fn new(version: Version) -> Self {
-        Yarn { version }
+        Yarn {
+            version,
+            details: None,
+        }
+    }
+
+    /// Create the Yarn for a version found in the registry, whose details are used to verify it
+    fn from_details(details: PackageDetails) -> Self {
+        Yarn {
+            version: details.version.clone(),
+            details: Some(details),
+        }
     }
 
     pub fn archive_basename(version: &str) -> String {
@@ -41,7 +77,17 @@ impl Yarn {
                 debug_already_fetched(self);
                 Ok(())
             }
-            FetchStatus::FetchNeeded(_lock) => fetch::fetch(&self.version, session.hooks()?.yarn()),
+            FetchStatus::FetchNeeded(_lock) => {
+                let hash = session.project()?.and_then(|project| {
+                    project.package_manager_hash(PackageManagerKind::Yarn, &self.version)
+                });
+                fetch::fetch(
+                    &self.version,
+                    self.details.clone(),
+                    session.hooks()?.yarn(),
+                    hash,
+                )
+            }
         }
     }
 }
@@ -106,4 +152,17 @@ mod tests {
     fn test_yarn_archive_filename() {
         assert_eq!(Yarn::archive_filename("1.2.3"), "yarn-v1.2.3.tar.gz");
     }
+
+    #[test]
+    fn test_registry_package() {
+        assert_eq!(registry_package(&"1.22.19".parse().unwrap()), "yarn");
+        assert_eq!(
+            registry_package(&"2.0.0-rc.29".parse().unwrap()),
+            "@yarnpkg/cli-dist"
+        );
+        assert_eq!(
+            registry_package(&"4.0.2".parse().unwrap()),
+            "@yarnpkg/cli-dist"
+        );
+    }
 }
@@ -1,16 +1,19 @@
 //! Provides resolution of Yarn requirements into specific versions
 
 use super::super::registry::{
-    public_registry_index, PackageDetails, PackageIndex, RawPackageMetadata,
+    find_package_details, public_registry_index, PackageDetails, PackageIndex, RawPackageMetadata,
     NPM_ABBREVIATED_ACCEPT_HEADER,
 };
 use super::super::registry_fetch_error;
 use super::metadata::{RawYarnIndex, YarnIndex};
+use super::{BERRY_PACKAGE, CLASSIC_PACKAGE};
 use crate::error::{Context, ErrorKind, Fallible};
 use crate::hook::ToolHooks;
+use crate::inventory::yarn_versions;
 use crate::session::Session;
 use crate::style::progress_spinner;
-use crate::tool::Yarn;
+use crate::tool::offline::{is_offline, resolve_local};
+use crate::tool::{Updates, Yarn};
 use crate::version::{parse_version, VersionSpec, VersionTag};
 use attohttpc::header::ACCEPT;
 use attohttpc::Response;
@@ -18,16 +21,28 @@ use log::debug;
 use semver::{Version, VersionReq};
 
 pub fn resolve(matching: VersionSpec, session: &mut Session) -> Fallible<Version> {
+    Ok(resolve_yarn(matching, session)?.version)
+}
+
+/// Resolve a version requirement into the Yarn to fetch
+///
+/// If the version was found in the registry, its details are kept, so that the download can be
+/// verified without fetching the index again.
+pub(crate) fn resolve_yarn(matching: VersionSpec, session: &mut Session) -> Fallible<Yarn> {
     let hooks = session.hooks()?.yarn();
     match matching {
+        // Note: Apart from `latest`, tags are only recorded in the registry, so they can't match
+        matching if is_offline() => {
+            resolve_local("yarn", matching, yarn_versions()?, |_, _| false).map(Yarn::new)
+        }
         VersionSpec::Semver(requirement) => resolve_semver(requirement, hooks),
-        VersionSpec::Exact(version) => Ok(version),
+        VersionSpec::Exact(version) => Ok(Yarn::new(version)),
         VersionSpec::None => resolve_tag(VersionTag::Latest, hooks),
         VersionSpec::Tag(tag) => resolve_tag(tag, hooks),
     }
 }
 
-fn resolve_tag(tag: VersionTag, hooks: Option<&ToolHooks<Yarn>>) -> Fallible<Version> {
+fn resolve_tag(tag: VersionTag, hooks: Option<&ToolHooks<Yarn>>) -> Fallible<Yarn> {
     // This triage is complicated because we need to maintain the legacy behavior of hooks
     // First, if the tag is 'latest' and we have a 'latest' hook, we use the old behavior
     // Next, if the tag is 'latest' and we _do not_ have a 'latest' hook, we use the new behavior
@@ -43,7 +58,7 @@ fn resolve_tag(tag: VersionTag, hooks: Option<&ToolHooks<Yarn>>) -> Fallible<Ver
             }),
         ) => {
             debug!("Using yarn.latest hook to determine latest-version URL");
-            resolve_latest_legacy(hook.resolve("latest-version")?)
+            resolve_latest_legacy(hook.resolve("latest-version")?).map(Yarn::new)
         }
         (VersionTag::Latest, _) => resolve_custom_tag(VersionTag::Latest.to_string()),
         (tag, Some(&ToolHooks { index: Some(_), .. })) => Err(ErrorKind::YarnVersionNotFound {
@@ -54,7 +69,7 @@ fn resolve_tag(tag: VersionTag, hooks: Option<&ToolHooks<Yarn>>) -> Fallible<Ver
     }
 }
 
-fn resolve_semver(matching: VersionReq, hooks: Option<&ToolHooks<Yarn>>) -> Fallible<Version> {
+fn resolve_semver(matching: VersionReq, hooks: Option<&ToolHooks<Yarn>>) -> Fallible<Yarn> {
     // For semver, the triage is less complicated: The previous behavior _always_ used
     // the 'index' hook, so we can check for that to decide which behavior to use.
     if let Some(&ToolHooks {
@@ -63,14 +78,50 @@ fn resolve_semver(matching: VersionReq, hooks: Option<&ToolHooks<Yarn>>) -> Fall
     }) = hooks
     {
         debug!("Using yarn.index hook to determine yarn index URL");
-        resolve_semver_legacy(matching, hook.resolve("releases")?)
+        resolve_semver_legacy(matching, hook.resolve("releases")?).map(Yarn::new)
     } else {
         resolve_semver_from_registry(matching)
     }
 }
 
-fn fetch_yarn_index() -> Fallible<(String, PackageIndex)> {
-    let url = public_registry_index("yarn");
+/// Find the versions of Yarn that are newer than the given version
+///
+/// Since Yarn 2+ is published as a separate package, both registry packages are checked, unless
+/// the `yarn.index` hook provides the list of versions.
+pub fn check_updates(version: &Version, session: &mut Session) -> Fallible<Updates> {
+    if is_offline() {
+        debug!("Skipping Yarn update check in offline mode");
+        return Ok(Updates::default());
+    }
+
+    if let Some(&ToolHooks {
+        index: Some(ref hook),
+        ..
+    }) = session.hooks()?.yarn()
+    {
+        debug!("Using yarn.index hook to determine yarn index URL");
+        let index = fetch_legacy_index(&hook.resolve("releases")?)?;
+        return Ok(Updates::from_versions(
+            version,
+            index.entries.into_iter().map(|version| (version, false)),
+        ));
+    }
+
+    let (_, classic) = fetch_yarn_index(CLASSIC_PACKAGE)?;
+    let (_, berry) = fetch_yarn_index(BERRY_PACKAGE)?;
+    Ok(Updates::from_versions(
+        version,
+        classic
+            .entries
+            .into_iter()
+            .chain(berry.entries)
+            .map(|PackageDetails { version, .. }| (version, false)),
+    ))
+}
+
+/// Fetch the index of versions for the given Yarn registry package
+pub(super) fn fetch_yarn_index(package: &str) -> Fallible<(String, PackageIndex)> {
+    let url = public_registry_index(package);
     let spinner = progress_spinner(&format!("Fetching public registry: {}", url));
     let metadata: RawPackageMetadata = attohttpc::get(&url)
         .header(ACCEPT, NPM_ABBREVIATED_ACCEPT_HEADER)
@@ -83,16 +134,25 @@ fn fetch_yarn_index() -> Fallible<(String, PackageIndex)> {
     Ok((url, metadata.into()))
 }
 
-fn resolve_custom_tag(tag: String) -> Fallible<Version> {
-    let (url, mut index) = fetch_yarn_index()?;
+/// Resolve a tag against the registry
+///
+/// As with semver requirements, the Yarn 1 package is checked first, so `latest` keeps resolving
+/// to the newest Yarn 1 release, while tags that only the Yarn 2+ package publishes are found
+/// there.
+fn resolve_custom_tag(tag: String) -> Fallible<Yarn> {
+    for package in [CLASSIC_PACKAGE, BERRY_PACKAGE].iter() {
+        let (url, mut index) = fetch_yarn_index(package)?;
 
-    match index.tags.remove(&tag) {
-        Some(version) => {
+        if let Some(version) = index.tags.remove(&tag) {
             debug!("Found yarn@{} matching tag '{}' from {}", version, tag, url);
-            Ok(version)
+            return Ok(match find_package_details(index, &version) {
+                Some(details) => Yarn::from_details(details),
+                None => Yarn::new(version),
+            });
         }
-        None => Err(ErrorKind::YarnVersionNotFound { matching: tag }.into()),
     }
+
+    Err(ErrorKind::YarnVersionNotFound { matching: tag }.into())
 }
 
 fn resolve_latest_legacy(url: String) -> Fallible<Version> {
@@ -108,39 +168,49 @@ fn resolve_latest_legacy(url: String) -> Fallible<Version> {
     parse_version(response_text)
 }
 
-fn resolve_semver_from_registry(matching: VersionReq) -> Fallible<Version> {
-    let (url, index) = fetch_yarn_index()?;
+/// Resolve a semver requirement against the registry
+///
+/// Yarn 2+ is published as a separate package from Yarn 1, so we fall back to that package when
+/// none of the Yarn 1 versions match. This keeps ranges such as `^1` from needing a second
+/// request, while `3` or `^4.0` resolve against the newer releases.
+fn resolve_semver_from_registry(matching: VersionReq) -> Fallible<Yarn> {
+    for package in [CLASSIC_PACKAGE, BERRY_PACKAGE].iter() {
+        let (url, index) = fetch_yarn_index(package)?;
 
-    let details_opt = index
-        .entries
-        .into_iter()
-        .find(|PackageDetails { version, .. }| matching.matches(&version));
+        let details_opt = index
+            .entries
+            .into_iter()
+            .find(|PackageDetails { version, .. }| matching.matches(version));
 
-    match details_opt {
-        Some(details) => {
+        if let Some(details) = details_opt {
             debug!(
                 "Found yarn@{} matching requirement '{}' from {}",
                 details.version, matching, url
             );
-            Ok(details.version)
+            return Ok(Yarn::from_details(details));
         }
-        None => Err(ErrorKind::YarnVersionNotFound {
-            matching: matching.to_string(),
-        }
-        .into()),
     }
+
+    Err(ErrorKind::YarnVersionNotFound {
+        matching: matching.to_string(),
+    }
+    .into())
 }
 
-fn resolve_semver_legacy(matching: VersionReq, url: String) -> Fallible<Version> {
+/// Fetch the index of Yarn releases from a `yarn.index` hook, which uses the GitHub API format
+fn fetch_legacy_index(url: &str) -> Fallible<YarnIndex> {
     let spinner = progress_spinner(&format!("Fetching public registry: {}", url));
-    let releases: RawYarnIndex = attohttpc::get(&url)
+    let releases: RawYarnIndex = attohttpc::get(url)
         .send()
         .and_then(Response::error_for_status)
         .and_then(Response::json)
-        .with_context(registry_fetch_error("Yarn", &url))?;
-    let index = YarnIndex::from(releases);
-    let releases = index.entries;
+        .with_context(registry_fetch_error("Yarn", url))?;
     spinner.finish_and_clear();
+    Ok(YarnIndex::from(releases))
+}
+
+fn resolve_semver_legacy(matching: VersionReq, url: String) -> Fallible<Version> {
+    let releases = fetch_legacy_index(&url)?.entries;
     let version_opt = releases.into_iter().rev().find(|v| matching.matches(v));
 
     match version_opt {
@@ -3,8 +3,8 @@ use std::fs::write;
 
 use crate::error::{Context, ErrorKind, Fallible};
 use crate::fs::touch;
-use crate::layout::volta_home;
 use crate::platform::PlatformSpec;
+use crate::profile::active_profile;
 use lazycell::LazyCell;
 use log::debug;
 use readext::ReadExt;
@@ -42,8 +42,8 @@ pub struct Toolchain {
 
 impl Toolchain {
     fn current() -> Fallible<Toolchain> {
-        let path = volta_home()?.default_platform_file();
-        let src = touch(&path)
+        let path = active_profile()?.platform_file();
+        let src = touch(path)
             .and_then(|mut file| file.read_into_string())
             .with_context(|| ErrorKind::ReadPlatformError {
                 file: path.to_owned(),
@@ -75,6 +75,7 @@ impl Toolchain {
                 self.platform = Some(PlatformSpec {
                     node: node_version.clone(),
                     npm: None,
+                    pnpm: None,
                     yarn: None,
                 });
                 dirty = true;
@@ -88,6 +89,23 @@ impl Toolchain {
         Ok(())
     }
 
+    /// Set the active pnpm version in the default platform file.
+    pub fn set_active_pnpm(&mut self, pnpm: Option<Version>) -> Fallible<()> {
+        if let Some(platform) = self.platform.as_mut() {
+            if platform.pnpm != pnpm {
+                platform.pnpm = pnpm;
+                self.save()?;
+            }
+        } else if pnpm.is_some() {
+            return Err(ErrorKind::NoDefaultNodeVersion {
+                tool: "pnpm".into(),
+            }
+            .into());
+        }
+
+        Ok(())
+    }
+
     /// Set the active Yarn version in the default platform file.
     pub fn set_active_yarn(&mut self, yarn: Option<Version>) -> Fallible<()> {
         if let Some(platform) = self.platform.as_mut() {
@@ -120,13 +138,13 @@ impl Toolchain {
     }
 
     pub fn save(&self) -> Fallible<()> {
-        let path = volta_home()?.default_platform_file();
+        let path = active_profile()?.platform_file();
         let result = match &self.platform {
             Some(platform) => {
                 let src = serial::Platform::of(platform).into_json()?;
-                write(&path, src)
+                write(path, src)
             }
-            None => write(&path, "{}"),
+            None => write(path, "{}"),
         };
         result.with_context(|| ErrorKind::WritePlatformError {
             file: path.to_owned(),
@@ -19,6 +19,10 @@ pub struct Platform {
     pub node: Option<NodeVersion>,
     #[serde(default)]
     #[serde(with = "option_version_serde")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pnpm: Option<Version>,
+    #[serde(default)]
+    #[serde(with = "option_version_serde")]
     pub yarn: Option<Version>,
 }
 
@@ -29,6 +33,7 @@ impl Platform {
                 runtime: source.node.clone(),
                 npm: source.npm.clone(),
             }),
+            pnpm: source.pnpm.clone(),
             yarn: source.yarn.clone(),
         }
     }
@@ -54,10 +59,12 @@ impl TryFrom<String> for Platform {
 
 impl From<Platform> for Option<PlatformSpec> {
     fn from(platform: Platform) -> Option<PlatformSpec> {
+        let pnpm = platform.pnpm;
         let yarn = platform.yarn;
         platform.node.map(|node_version| PlatformSpec {
             node: node_version.runtime,
             npm: node_version.npm,
+            pnpm,
             yarn,
         })
     }
@@ -86,6 +93,7 @@ pub mod tests {
         let json_str = BASIC_JSON_STR.to_string();
         let platform = Platform::try_from(json_str).expect("could not parse JSON string");
         let expected_platform = Platform {
+            pnpm: None,
             yarn: Some(Version::parse("1.2.3").expect("could not parse version")),
             node: Some(NodeVersion {
                 runtime: Version::parse("4.5.6").expect("could not parse version"),
@@ -101,6 +109,7 @@ pub mod tests {
         let platform = Platform::try_from(json_str).expect("could not parse JSON string");
         let expected_platform = Platform {
             node: None,
+            pnpm: None,
             yarn: None,
         };
         assert_eq!(platform, expected_platform);
@@ -112,6 +121,7 @@ pub mod tests {
             yarn: Some(Version::parse("1.2.3").expect("could not parse version")),
             node: Version::parse("4.5.6").expect("could not parse version"),
             npm: Some(Version::parse("7.8.9").expect("could not parse version")),
+            pnpm: None,
         };
         let json_str = Platform::of(&platform_spec)
             .into_json()
@@ -119,4 +129,21 @@ pub mod tests {
         let expected_json_str = BASIC_JSON_STR.to_string();
         assert_eq!(json_str, expected_json_str);
     }
+
+    #[test]
+    fn test_pnpm_round_trip() {
+        let platform_spec = platform::PlatformSpec {
+            node: Version::parse("4.5.6").expect("could not parse version"),
+            npm: None,
+            pnpm: Some(Version::parse("8.6.0").expect("could not parse version")),
+            yarn: None,
+        };
+        let json_str = Platform::of(&platform_spec)
+            .into_json()
+            .expect("could not serialize platform to JSON");
+        let parsed: Option<platform::PlatformSpec> = Platform::try_from(json_str)
+            .expect("could not parse JSON string")
+            .into();
+        assert_eq!(parsed, Some(platform_spec));
+    }
 }
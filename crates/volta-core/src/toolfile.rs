@@ -0,0 +1,648 @@
+//! Provides the toolfile, a JSON file that declares a default toolchain, which `volta sync` makes
+//! the default toolchain match and `volta export` writes
+
+use std::collections::BTreeMap;
+use std::fs::{read_to_string, write};
+use std::path::Path;
+
+use crate::error::{Context, ErrorKind, Fallible};
+use crate::inventory::package_configs;
+use crate::platform::{CliPlatform, InheritOption, PlatformSpec};
+use crate::session::Session;
+use crate::style::success_prefix;
+use crate::sync::VoltaLock;
+use crate::tool::package::{self, normalize_source};
+use crate::tool::{node, npm, pnpm, yarn};
+use crate::tool::{
+    BundledNpm, Node, Npm, Package, PackageConfig, PackageManager, Pnpm, Tool, Yarn,
+};
+use crate::version::{VersionSpec, VersionTag};
+use log::info;
+use semver::Version;
+use serde::{Deserialize, Serialize};
+
+/// A declared default toolchain, with the versions of the default tools and global packages
+///
+/// Versions may be exact versions, ranges, or tags, the same as they are given to
+/// `volta install`.
+pub struct Toolfile {
+    node: Option<String>,
+    npm: Option<String>,
+    pnpm: Option<String>,
+    yarn: Option<String>,
+    packages: Vec<ToolfilePackage>,
+}
+
+/// A global package declared in a toolfile
+struct ToolfilePackage {
+    /// The name the package is installed as, which is the alias if there is one
+    install_name: String,
+    requested: Requested,
+    manager: PackageManager,
+    /// The Node version the package is installed and run with, in place of the default
+    node: Option<String>,
+    /// The npm version the package is installed and run with, in place of the default
+    npm: Option<String>,
+}
+
+#[derive(Clone)]
+enum Requested {
+    /// A package from the registry, along with the version to install, if any
+    Registry {
+        name: String,
+        version: Option<String>,
+    },
+    /// A git URL, tarball, or local directory
+    Source(String),
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct RawToolfile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    node: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    npm: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pnpm: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    yarn: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    packages: BTreeMap<String, RawPackage>,
+}
+
+/// A package in the toolfile, which is only a version for a package installed under its own name
+/// with npm, like `"typescript": "^5.1.6"`
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum RawPackage {
+    Version(String),
+    Details(RawPackageDetails),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawPackageDetails {
+    /// The name of the package, if it is installed under an alias
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    package: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    source: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    manager: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    node: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    npm: Option<String>,
+}
+
+impl Toolfile {
+    /// Read a toolfile
+    pub fn from_file(file: &Path) -> Fallible<Self> {
+        let contents = read_to_string(file).with_context(|| ErrorKind::ReadToolfileError {
+            file: file.to_path_buf(),
+        })?;
+
+        let parse_error = || ErrorKind::ParseToolfileError {
+            file: file.to_path_buf(),
+        };
+        let raw: RawToolfile = serde_json::from_str(&contents).with_context(parse_error)?;
+
+        let mut packages = Vec::new();
+        for (install_name, package) in raw.packages {
+            let (requested, manager, node, npm) = match package {
+                RawPackage::Version(version) => (
+                    Requested::Registry {
+                        name: install_name.clone(),
+                        version: Some(version),
+                    },
+                    PackageManager::Npm,
+                    None,
+                    None,
+                ),
+                RawPackage::Details(details) => {
+                    let manager = match details.manager {
+                        Some(manager) => manager.parse().map_err(|_| parse_error())?,
+                        None => PackageManager::Npm,
+                    };
+                    let requested = match details.source {
+                        Some(source) => Requested::Source(source),
+                        None => Requested::Registry {
+                            name: details.package.unwrap_or_else(|| install_name.clone()),
+                            version: details.version,
+                        },
+                    };
+                    (requested, manager, details.node, details.npm)
+                }
+            };
+
+            packages.push(ToolfilePackage {
+                install_name,
+                requested,
+                manager,
+                node,
+                npm,
+            });
+        }
+
+        Ok(Toolfile {
+            node: raw.node,
+            npm: raw.npm,
+            pnpm: raw.pnpm,
+            yarn: raw.yarn,
+            packages,
+        })
+    }
+
+    /// Declare the current default toolchain and global packages
+    ///
+    /// Packages that were installed with a version range are declared with that range, others
+    /// with the exact version that is installed. Packages that were installed with `--node` or
+    /// `--npm` are declared with the exact version of that tool, where npm may be `bundled`.
+    pub fn current(session: &mut Session) -> Fallible<Self> {
+        let platform = session.default_platform()?;
+        let version = |version: Option<&Version>| version.map(ToString::to_string);
+
+        let packages = package_configs()?
+            .into_iter()
+            .map(|config| {
+                let install_name = config.install_name().to_string();
+                let node = Some(config.platform.node.to_string()).filter(|_| config.overrides.node);
+                let npm = match &config.platform.npm {
+                    _ if !config.overrides.npm => None,
+                    Some(npm) => Some(npm.to_string()),
+                    None => Some("bundled".to_string()),
+                };
+                let requested = match config.source {
+                    Some(source) => Requested::Source(source),
+                    None => Requested::Registry {
+                        name: config.name,
+                        version: Some(match config.range {
+                            Some(range) => range,
+                            None => config.version.to_string(),
+                        }),
+                    },
+                };
+
+                ToolfilePackage {
+                    install_name,
+                    requested,
+                    manager: config.manager,
+                    node,
+                    npm,
+                }
+            })
+            .collect();
+
+        Ok(Toolfile {
+            node: version(platform.map(|platform| &platform.node)),
+            npm: version(platform.and_then(|platform| platform.npm.as_ref())),
+            pnpm: version(platform.and_then(|platform| platform.pnpm.as_ref())),
+            yarn: version(platform.and_then(|platform| platform.yarn.as_ref())),
+            packages,
+        })
+    }
+
+    /// Serialize the toolfile into JSON
+    pub fn to_json(&self) -> Fallible<String> {
+        let packages = self
+            .packages
+            .iter()
+            .map(|package| (package.install_name.clone(), package.to_raw()))
+            .collect();
+
+        let raw = RawToolfile {
+            node: self.node.clone(),
+            npm: self.npm.clone(),
+            pnpm: self.pnpm.clone(),
+            yarn: self.yarn.clone(),
+            packages,
+        };
+
+        serde_json::to_string_pretty(&raw).with_context(|| ErrorKind::StringifyToolfileError)
+    }
+
+    /// Write the toolfile as JSON
+    pub fn write(&self, file: &Path) -> Fallible<()> {
+        let contents = self.to_json()?;
+        write(file, contents).with_context(|| ErrorKind::WriteToolfileError {
+            file: file.to_path_buf(),
+        })
+    }
+
+    /// Make the default toolchain and global packages match the toolfile
+    ///
+    /// Tools and packages whose installed version already satisfies the declared version are
+    /// left as they are, while the others are installed. If npm, pnpm, or Yarn isn't declared,
+    /// it is removed from the default platform, so that npm goes back to the version bundled with
+    /// Node, and the global packages that aren't declared are uninstalled. A package declared
+    /// with a tag, like `latest`, is only installed if it isn't installed already.
+    pub fn sync(&self, session: &mut Session) -> Fallible<()> {
+        // Acquire a lock on the Volta directory, if possible, to prevent concurrent changes
+        let _lock = VoltaLock::acquire();
+
+        let changes = self.sync_tools(session)? + self.sync_packages(session)?;
+        if changes == 0 {
+            info!(
+                "{} Everything already matches the toolfile",
+                success_prefix()
+            );
+        } else {
+            info!(
+                "{} made {} {} to match the toolfile",
+                success_prefix(),
+                changes,
+                if changes == 1 { "change" } else { "changes" }
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Make the default platform match the toolfile, returning the number of tools changed
+    fn sync_tools(&self, session: &mut Session) -> Fallible<usize> {
+        let tools = [
+            (DefaultTool::Node, &self.node),
+            (DefaultTool::Npm, &self.npm),
+            (DefaultTool::Pnpm, &self.pnpm),
+            (DefaultTool::Yarn, &self.yarn),
+        ];
+        let mut changes = 0;
+
+        for &(tool, declared) in &tools {
+            // Note: The default Node can't be removed, so it is only changed if it is declared
+            if tool == DefaultTool::Node && declared.is_none() {
+                continue;
+            }
+
+            let current = tool.version_in(session.default_platform()?);
+            let target = match declared {
+                Some(declared) => {
+                    let spec: VersionSpec = declared.parse()?;
+                    if matches!(&current, Some(current) if satisfies(&spec, current)) {
+                        continue;
+                    }
+                    tool.resolve(spec, session)?
+                }
+                None => None,
+            };
+
+            if target == current {
+                continue;
+            }
+            match target {
+                Some(version) => tool.install(version, session)?,
+                None => tool.remove(session)?,
+            }
+            changes += 1;
+        }
+
+        Ok(changes)
+    }
+
+    /// Make the global packages match the toolfile, returning the number of packages changed
+    fn sync_packages(&self, session: &mut Session) -> Fallible<usize> {
+        let installed = package_configs()?;
+        let declared = self
+            .packages
+            .iter()
+            .map(ToolfilePackage::normalized)
+            .collect::<Fallible<Vec<_>>>()?;
+        let mut changes = 0;
+
+        // Uninstall the undeclared packages first, so their bins don't conflict with new ones
+        for config in &installed {
+            if !declared.iter().any(|package| package.declares(config)) {
+                package::uninstall(config.install_name())?;
+                changes += 1;
+            }
+        }
+
+        for package in &declared {
+            let mut satisfied = false;
+            for config in &installed {
+                if package.is_satisfied_by(config)? {
+                    satisfied = true;
+                    break;
+                }
+            }
+
+            if !satisfied {
+                Box::new(package.to_package(session)?).install(session)?;
+                changes += 1;
+            }
+        }
+
+        Ok(changes)
+    }
+}
+
+impl ToolfilePackage {
+    /// Resolve a local source to an absolute path, the same way it is recorded when installed
+    fn normalized(&self) -> Fallible<Self> {
+        let requested = match &self.requested {
+            Requested::Source(source) => Requested::Source(normalize_source(source.clone())?),
+            requested => requested.clone(),
+        };
+
+        Ok(ToolfilePackage {
+            install_name: self.install_name.clone(),
+            requested,
+            manager: self.manager,
+            node: self.node.clone(),
+            npm: self.npm.clone(),
+        })
+    }
+
+    /// Determine whether an installed package is the one declared, regardless of its version
+    fn declares(&self, config: &PackageConfig) -> bool {
+        match &self.requested {
+            Requested::Source(source) => config.source.as_ref() == Some(source),
+            Requested::Registry { .. } => config.install_name() == self.install_name,
+        }
+    }
+
+    /// Determine whether an installed package matches the declared package and version, and was
+    /// installed with the declared Node and npm
+    fn is_satisfied_by(&self, config: &PackageConfig) -> Fallible<bool> {
+        if config.manager != self.manager || !self.declares(config) {
+            return Ok(false);
+        }
+
+        let platform = &config.platform;
+        if !overrides_with(&self.node, config.overrides.node, Some(&platform.node))?
+            || !overrides_with(&self.npm, config.overrides.npm, platform.npm.as_ref())?
+        {
+            return Ok(false);
+        }
+
+        match &self.requested {
+            Requested::Source(_) => Ok(true),
+            Requested::Registry { name, version } => {
+                if config.source.is_some() || config.name != *name {
+                    return Ok(false);
+                }
+
+                Ok(match version {
+                    Some(version) => match version.parse()? {
+                        VersionSpec::Tag(_) => true,
+                        spec => satisfies(&spec, &config.version),
+                    },
+                    None => true,
+                })
+            }
+        }
+    }
+
+    /// Create the package to install in order to match the declared package
+    fn to_package(&self, session: &mut Session) -> Fallible<Package> {
+        let package = self.to_requested_package()?;
+
+        Ok(match self.resolve_platform(session)? {
+            Some(platform) => package.with_platform(platform),
+            None => package,
+        })
+    }
+
+    fn to_requested_package(&self) -> Fallible<Package> {
+        match &self.requested {
+            Requested::Source(source) => Package::from_source(source.clone(), self.manager),
+            Requested::Registry { name, version } => {
+                let version = match version {
+                    Some(version) => version.parse()?,
+                    None => VersionSpec::None,
+                };
+                let package = Package::with_manager(name.clone(), version, self.manager)?;
+
+                Ok(if *name == self.install_name {
+                    package
+                } else {
+                    package.with_alias(self.install_name.clone())
+                })
+            }
+        }
+    }
+
+    /// Resolve the declared Node and npm versions into the platform to install the package with,
+    /// the same way as `volta install --node` and `--npm`
+    fn resolve_platform(&self, session: &mut Session) -> Fallible<Option<CliPlatform>> {
+        if self.node.is_none() && self.npm.is_none() {
+            return Ok(None);
+        }
+
+        let node = match &self.node {
+            Some(version) => Some(node::resolve(version.parse()?, session)?),
+            None => None,
+        };
+        let npm = match &self.npm {
+            Some(version) => npm::resolve(version.parse()?, session)?.into(),
+            None => InheritOption::Inherit,
+        };
+
+        Ok(Some(CliPlatform {
+            node,
+            npm,
+            pnpm: InheritOption::Inherit,
+            yarn: InheritOption::Inherit,
+        }))
+    }
+
+    fn to_raw(&self) -> RawPackage {
+        let manager = match self.manager {
+            PackageManager::Npm => None,
+            manager => Some(manager.to_string()),
+        };
+
+        match &self.requested {
+            Requested::Registry {
+                name,
+                version: Some(version),
+            } if *name == self.install_name
+                && manager.is_none()
+                && self.node.is_none()
+                && self.npm.is_none() =>
+            {
+                RawPackage::Version(version.clone())
+            }
+            Requested::Registry { name, version } => RawPackage::Details(RawPackageDetails {
+                package: if *name == self.install_name {
+                    None
+                } else {
+                    Some(name.clone())
+                },
+                version: version.clone(),
+                source: None,
+                manager,
+                node: self.node.clone(),
+                npm: self.npm.clone(),
+            }),
+            Requested::Source(source) => RawPackage::Details(RawPackageDetails {
+                package: None,
+                version: None,
+                source: Some(source.clone()),
+                manager,
+                node: self.node.clone(),
+                npm: self.npm.clone(),
+            }),
+        }
+    }
+}
+
+/// The tools in the default platform
+#[derive(Clone, Copy, PartialEq)]
+enum DefaultTool {
+    Node,
+    Npm,
+    Pnpm,
+    Yarn,
+}
+
+impl DefaultTool {
+    fn name(self) -> &'static str {
+        match self {
+            DefaultTool::Node => "node",
+            DefaultTool::Npm => "npm",
+            DefaultTool::Pnpm => "pnpm",
+            DefaultTool::Yarn => "yarn",
+        }
+    }
+
+    fn version_in(self, platform: Option<&PlatformSpec>) -> Option<Version> {
+        let platform = platform?;
+        match self {
+            DefaultTool::Node => Some(platform.node.clone()),
+            DefaultTool::Npm => platform.npm.clone(),
+            DefaultTool::Pnpm => platform.pnpm.clone(),
+            DefaultTool::Yarn => platform.yarn.clone(),
+        }
+    }
+
+    /// Resolve a declared version, where `None` is the npm bundled with Node
+    fn resolve(self, spec: VersionSpec, session: &mut Session) -> Fallible<Option<Version>> {
+        match self {
+            DefaultTool::Node => node::resolve(spec, session).map(Some),
+            DefaultTool::Npm => npm::resolve(spec, session),
+            DefaultTool::Pnpm => pnpm::resolve(spec, session).map(Some),
+            DefaultTool::Yarn => yarn::resolve(spec, session).map(Some),
+        }
+    }
+
+    fn install(self, version: Version, session: &mut Session) -> Fallible<()> {
+        let tool: Box<dyn Tool> = match self {
+            DefaultTool::Node => Box::new(Node::new(version)),
+            DefaultTool::Npm => Box::new(Npm::new(version)),
+            DefaultTool::Pnpm => Box::new(Pnpm::new(version)),
+            DefaultTool::Yarn => Box::new(Yarn::new(version)),
+        };
+        tool.install(session)
+    }
+
+    /// Remove the tool from the default platform, which for npm means using the bundled version
+    fn remove(self, session: &mut Session) -> Fallible<()> {
+        let toolchain = session.toolchain_mut()?;
+        match self {
+            DefaultTool::Node => return Ok(()),
+            DefaultTool::Npm => return Box::new(BundledNpm).install(session),
+            DefaultTool::Pnpm => toolchain.set_active_pnpm(None)?,
+            DefaultTool::Yarn => toolchain.set_active_yarn(None)?,
+        }
+
+        info!(
+            "{} removed {} from the default toolchain",
+            success_prefix(),
+            self.name()
+        );
+        Ok(())
+    }
+}
+
+/// Determine whether an installed version satisfies a declared version, without resolving it
+///
+/// Tags can't be checked without resolving them, so they are never satisfied.
+fn satisfies(spec: &VersionSpec, version: &Version) -> bool {
+    match spec {
+        VersionSpec::None => true,
+        VersionSpec::Exact(exact) => exact == version,
+        VersionSpec::Semver(range) => range.matches(version),
+        VersionSpec::Tag(_) => false,
+    }
+}
+
+/// Determine whether a package was installed with the declared version of a tool, where a
+/// package that doesn't declare the tool must have been installed with the default version
+///
+/// The `bundled` npm is only satisfied by the npm bundled with Node, while other tags are
+/// satisfied by any version, the same as the package's own version.
+fn overrides_with(
+    declared: &Option<String>,
+    overridden: bool,
+    installed: Option<&Version>,
+) -> Fallible<bool> {
+    let declared = match declared {
+        Some(declared) => declared,
+        None => return Ok(!overridden),
+    };
+    if !overridden {
+        return Ok(false);
+    }
+
+    Ok(match (declared.parse()?, installed) {
+        (VersionSpec::Tag(VersionTag::Custom(tag)), installed) if tag == "bundled" => {
+            installed.is_none()
+        }
+        (VersionSpec::Tag(_), _) => true,
+        (spec, Some(installed)) => satisfies(&spec, installed),
+        (_, None) => false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn fixture_path(fixture: &str) -> PathBuf {
+        let mut cargo_manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        cargo_manifest_dir.push("fixtures");
+        cargo_manifest_dir.push("toolfile");
+        cargo_manifest_dir.push(fixture);
+        cargo_manifest_dir
+    }
+
+    #[test]
+    fn test_from_file() {
+        let toolfile = Toolfile::from_file(&fixture_path("tools.json")).unwrap();
+
+        assert_eq!(toolfile.node.as_deref(), Some("18.16.0"));
+        assert_eq!(toolfile.npm, None);
+        assert_eq!(toolfile.yarn.as_deref(), Some("1"));
+
+        let tsc4 = &toolfile.packages[2];
+        assert_eq!(tsc4.install_name, "tsc4");
+        assert!(matches!(
+            &tsc4.requested,
+            Requested::Registry { name, version: Some(version) }
+                if name == "typescript" && version == "4.9.5"
+        ));
+        assert_eq!(tsc4.node.as_deref(), Some("16.20.0"));
+        assert_eq!(tsc4.npm, None);
+        assert_eq!(toolfile.packages[3].manager, PackageManager::Pnpm);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let file = fixture_path("tools.json");
+        let toolfile = Toolfile::from_file(&file).unwrap();
+
+        assert_eq!(
+            toolfile.to_json().unwrap(),
+            read_to_string(&file).unwrap().trim_end()
+        );
+    }
+
+    #[test]
+    fn test_unknown_package_manager() {
+        assert!(Toolfile::from_file(&fixture_path("unknown-manager.json")).is_err());
+    }
+}
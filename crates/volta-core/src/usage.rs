@@ -0,0 +1,78 @@
+//! Provides tracking of when each version of Node, npm, pnpm, and Yarn was last used.
+//!
+//! Each tool version has its own usage file under `VOLTA_HOME/tools/usage`, containing the time
+//! it was last used as seconds since the Unix epoch. Since every version is recorded in a separate
+//! file and the record is a single small write, no lock is needed: a concurrent or interrupted
+//! write can at worst leave a file that can't be parsed, in which case the modification time of
+//! the file is used instead.
+
+use std::fs::{create_dir_all, metadata, read_to_string, write};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::layout::volta_home;
+use log::debug;
+use semver::Version;
+
+/// How often the use of a tool version is written to disk
+///
+/// Recording every use would mean a write for every shim invocation, so a use is only recorded
+/// if the previous record is older than this.
+const RECORD_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+fn usage_file(tool: &str, version: &Version) -> Option<PathBuf> {
+    volta_home()
+        .ok()
+        .map(|home| home.tool_usage_file(tool, &version.to_string()))
+}
+
+/// Record that a version of a tool was just used
+///
+/// Failures are only logged, since tracking usage should never prevent a tool from running.
+pub fn record_use(tool: &str, version: &Version) {
+    let file = match usage_file(tool, version) {
+        Some(file) => file,
+        None => return,
+    };
+
+    let now = SystemTime::now();
+    let recent = metadata(&file)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|modified| now.duration_since(modified).ok())
+        .filter(|elapsed| elapsed < &RECORD_INTERVAL)
+        .is_some();
+    if recent {
+        return;
+    }
+
+    let timestamp = now
+        .duration_since(UNIX_EPOCH)
+        .map(|since_epoch| since_epoch.as_secs())
+        .unwrap_or_default();
+    let result = file
+        .parent()
+        .map_or(Ok(()), create_dir_all)
+        .and_then(|_| write(&file, timestamp.to_string()));
+
+    if let Err(error) = result {
+        debug!(
+            "Could not record usage of {}@{} in {}: {}",
+            tool,
+            version,
+            file.display(),
+            error
+        );
+    }
+}
+
+/// Determine when a version of a tool was last used, if that has been recorded
+pub fn last_used(tool: &str, version: &Version) -> Option<SystemTime> {
+    let file = usage_file(tool, version)?;
+
+    read_to_string(&file)
+        .ok()
+        .and_then(|contents| contents.trim().parse().ok())
+        .map(|seconds| UNIX_EPOCH + Duration::from_secs(seconds))
+        .or_else(|| metadata(&file).and_then(|meta| meta.modified()).ok())
+}
@@ -6,7 +6,7 @@ use semver::{Version, VersionReq};
 
 mod serial;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 #[cfg_attr(test, derive(PartialEq))]
 pub enum VersionSpec {
     /// No version specified (default)
@@ -22,7 +22,7 @@ pub enum VersionSpec {
     Tag(VersionTag),
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 #[cfg_attr(test, derive(PartialEq))]
 pub enum VersionTag {
     /// The 'latest' tag, a special case that exists for all packages
@@ -183,6 +183,32 @@ pub mod option_version_serde {
     }
 }
 
+// custom serialization and de-serialization for Vec<Version>
+// because Version doesn't work with serde out of the box
+pub mod vec_version_serde {
+    use super::version_serde;
+    use semver::Version;
+    use serde::{self, Deserialize, Deserializer, Serializer};
+
+    #[derive(Deserialize)]
+    struct Wrapper(#[serde(deserialize_with = "version_serde::deserialize")] Version);
+
+    pub fn serialize<S>(versions: &[Version], s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        s.collect_seq(versions.iter().map(Version::to_string))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<Version>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let v = Vec::<Wrapper>::deserialize(deserializer)?;
+        Ok(v.into_iter().map(|Wrapper(version)| version).collect())
+    }
+}
+
 // custom deserialization for HashMap<String, Version>
 // because Version doesn't work with serde out of the box
 pub mod hashmap_version_serde {
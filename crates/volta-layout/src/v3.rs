@@ -28,6 +28,7 @@ layout! {
                 "packages": package_image_root_dir {}
             }
             "shared": shared_lib_root {}
+            "usage": usage_dir {}
             "user": default_toolchain_dir {
                 "bins": default_bin_dir {}
                 "packages": default_package_dir {}
@@ -90,6 +91,10 @@ impl VoltaHome {
     pub fn shared_lib_dir(&self, library: &str) -> PathBuf {
         path_buf!(self.shared_lib_root.clone(), library)
     }
+
+    pub fn tool_usage_file(&self, tool: &str, version: &str) -> PathBuf {
+        path_buf!(self.usage_dir.clone(), tool, version)
+    }
 }
 
 #[cfg(windows)]
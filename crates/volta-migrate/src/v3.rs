@@ -153,6 +153,9 @@ fn is_migrated_config(config_path: &Path) -> bool {
 /// - Binary config files are in the same location and the same format
 ///
 /// If any of those are violated, this migration may be invalid and need to be reworked / scrapped
+///
+/// Note: Since the V4 layout, the image is written directly to its versioned location, which the
+/// V4 migration then leaves in place.
 fn migrate_single_package(config: LegacyPackageConfig, session: &mut Session) -> Fallible<()> {
     let tool = Package::new(config.name, VersionSpec::Exact(config.version))?;
 
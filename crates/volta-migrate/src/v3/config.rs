@@ -42,6 +42,7 @@ impl From<LegacyPlatform> for PlatformSpec {
         PlatformSpec {
             node: config_platform.node.runtime,
             npm: config_platform.node.npm,
+            pnpm: None,
             yarn: config_platform.yarn,
         }
     }
@@ -0,0 +1,139 @@
+use std::convert::TryFrom;
+use std::fs::{symlink_metadata, File};
+use std::path::PathBuf;
+
+use crate::empty::Empty;
+use crate::v3::V3;
+use log::debug;
+use volta_core::error::{Context, ErrorKind, Fallible, VoltaError};
+use volta_core::fs::{remove_dir_if_exists, remove_file_if_exists, rename, symlink_dir};
+use volta_core::tool::PackageConfig;
+use volta_layout::{v3, v4};
+use walkdir::WalkDir;
+
+/// Represents a V4 Volta layout (used by Volta v1.1.0 and above)
+///
+/// Holds a reference to the V4 layout struct to support future migrations
+pub struct V4 {
+    pub home: v4::VoltaHome,
+}
+
+impl V4 {
+    pub fn new(home: PathBuf) -> Self {
+        V4 {
+            home: v4::VoltaHome::new(home),
+        }
+    }
+
+    /// Write the layout file to mark migration to V4 as complete
+    ///
+    /// Should only be called once all other migration steps are finished, so that we don't
+    /// accidentally mark an incomplete migration as completed
+    fn complete_migration(home: v4::VoltaHome) -> Fallible<Self> {
+        debug!("Writing layout marker file");
+        File::create(home.layout_file()).with_context(|| ErrorKind::CreateLayoutFileError {
+            file: home.layout_file().to_owned(),
+        })?;
+
+        Ok(V4 { home })
+    }
+}
+
+impl TryFrom<Empty> for V4 {
+    type Error = VoltaError;
+
+    fn try_from(old: Empty) -> Fallible<Self> {
+        debug!("New Volta installation detected, creating fresh layout");
+
+        let home = v4::VoltaHome::new(old.home);
+        home.create().with_context(|| ErrorKind::CreateDirError {
+            dir: home.root().to_owned(),
+        })?;
+
+        V4::complete_migration(home)
+    }
+}
+
+impl TryFrom<V3> for V4 {
+    type Error = VoltaError;
+
+    fn try_from(old: V3) -> Fallible<Self> {
+        debug!("Migrating from V3 layout");
+
+        let new_home = v4::VoltaHome::new(old.home.root().to_owned());
+        new_home
+            .create()
+            .with_context(|| ErrorKind::CreateDirError {
+                dir: new_home.root().to_owned(),
+            })?;
+
+        // Move the installed packages into images keyed by their version
+        shift_package_images(&old.home, &new_home)?;
+
+        // Complete the migration, writing the V4 layout file
+        let layout = V4::complete_migration(new_home)?;
+
+        // Remove the V3 layout file, since we're now on V4 (do this after writing the V4 file so that we know the migration succeeded)
+        remove_file_if_exists(old.home.layout_file())?;
+
+        Ok(layout)
+    }
+}
+
+/// Move every installed package image into a directory that includes the package version
+///
+/// In the V3 layout, package images were stored in /<package_name>/, so only one version of a
+/// package could be installed at a time. The V4 layout stores them in /<package_name>@<version>/
+fn shift_package_images(old_home: &v3::VoltaHome, new_home: &v4::VoltaHome) -> Fallible<()> {
+    let configs = WalkDir::new(old_home.default_package_dir())
+        .max_depth(2)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| PackageConfig::from_file(entry.path()).ok());
+
+    for config in configs {
+        shift_package_image(old_home, new_home, &config)?;
+    }
+
+    Ok(())
+}
+
+/// Move a single package image, if it is still in the V3 location, and update its shared link
+fn shift_package_image(
+    old_home: &v3::VoltaHome,
+    new_home: &v4::VoltaHome,
+    config: &PackageConfig,
+) -> Fallible<()> {
+    let old_image = old_home.package_image_dir(&config.name);
+    let mut old_source = config.manager.source_dir(old_image.clone());
+    old_source.push(&config.name);
+
+    // Note: If a previous migration was interrupted, the image may already have been moved
+    if !old_source.exists() {
+        return Ok(());
+    }
+
+    let version = config.version.to_string();
+    let new_image = new_home.package_image_dir(&config.name, &version);
+    remove_dir_if_exists(&new_image)?;
+    rename(&old_image, &new_image).with_context(|| ErrorKind::SetupToolImageError {
+        tool: config.name.clone(),
+        version,
+        dir: new_image.clone(),
+    })?;
+
+    let mut source = config.manager.source_dir(new_image);
+    source.push(&config.name);
+    let link = new_home.shared_lib_dir(&config.name);
+
+    // Only replace an existing link, which would otherwise point at the old image location
+    if symlink_metadata(&link).is_err() {
+        return Ok(());
+    }
+    remove_dir_if_exists(&link)?;
+
+    symlink_dir(source, link).with_context(|| ErrorKind::CreateSharedLinkError {
+        name: config.name.clone(),
+    })
+}
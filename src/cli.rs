@@ -36,6 +36,13 @@ pub(crate) struct Volta {
     )]
     pub(crate) quiet: bool,
 
+    #[structopt(
+        long = "offline",
+        help = "Resolves tools only from the local inventory, without accessing the network",
+        global = true
+    )]
+    pub(crate) offline: bool,
+
     #[structopt(
         short = "v",
         long = "version",
@@ -76,10 +83,48 @@ pub(crate) enum Subcommand {
     #[structopt(name = "uninstall", author = "", version = "")]
     Uninstall(command::Uninstall),
 
-    /// Pins your project's runtime or package manager
+    /// Reinstalls global packages with your default Node
+    #[structopt(name = "reinstall", author = "", version = "")]
+    Reinstall(command::Reinstall),
+
+    /// Upgrades your default tools and global packages to their newest compatible versions
+    #[structopt(name = "upgrade", author = "", version = "")]
+    Upgrade(command::Upgrade),
+
+    /// Makes your default tools and global packages match a toolfile
+    #[structopt(name = "sync", author = "", version = "")]
+    Sync(command::Sync),
+
+    /// Writes your default tools and global packages as a toolfile
+    #[structopt(name = "export", author = "", version = "")]
+    Export(command::Export),
+
+    /// Manages profiles, which each have their own default tools and global packages
+    #[structopt(
+        name = "profile",
+        author = "",
+        version = "",
+        raw(setting = "structopt::clap::AppSettings::SubcommandRequiredElseHelp")
+    )]
+    Profile(command::Profile),
+
+    /// Pins your project's runtime, package manager, or global packages
     #[structopt(name = "pin", author = "", version = "")]
     Pin(command::Pin),
 
+    /// Removes fetched tools that are no longer used by any platform
+    #[structopt(name = "prune", author = "", version = "")]
+    Prune(command::Prune),
+
+    /// Manages the cached tool archives and the cached Node index
+    #[structopt(
+        name = "cache",
+        author = "",
+        version = "",
+        raw(setting = "structopt::clap::AppSettings::SubcommandRequiredElseHelp")
+    )]
+    Cache(command::Cache),
+
     /// Displays the current toolchain
     #[structopt(name = "list", alias = "ls", author = "", version = "")]
     List(command::List),
@@ -122,7 +167,19 @@ otherwise, they will be written to `stdout`.
     #[structopt(name = "setup", author = "", version = "")]
     Setup(command::Setup),
 
-    /// Run a command with custom Node, npm, and/or Yarn versions
+    /// Checks the Volta installation and environment for problems
+    #[structopt(name = "doctor", author = "", version = "")]
+    Doctor(command::Doctor),
+
+    #[structopt(
+        name = "publish-events",
+        author = "",
+        version = "",
+        raw(setting = "structopt::clap::AppSettings::Hidden")
+    )]
+    PublishEvents(command::PublishEvents),
+
+    /// Run a command with custom Node, npm, pnpm, and/or Yarn versions
     #[structopt(name = "run", author = "", version = "")]
     #[structopt(raw(setting = "structopt::clap::AppSettings::AllowLeadingHyphen"))]
     #[structopt(raw(setting = "structopt::clap::AppSettings::TrailingVarArg"))]
@@ -135,13 +192,22 @@ impl Subcommand {
             Subcommand::Fetch(fetch) => fetch.run(session),
             Subcommand::Install(install) => install.run(session),
             Subcommand::Uninstall(uninstall) => uninstall.run(session),
+            Subcommand::Reinstall(reinstall) => reinstall.run(session),
+            Subcommand::Upgrade(upgrade) => upgrade.run(session),
+            Subcommand::Sync(sync) => sync.run(session),
+            Subcommand::Export(export) => export.run(session),
+            Subcommand::Profile(profile) => profile.run(session),
             Subcommand::Pin(pin) => pin.run(session),
+            Subcommand::Prune(prune) => prune.run(session),
+            Subcommand::Cache(cache) => cache.run(session),
             Subcommand::List(list) => list.run(session),
             Subcommand::Completions(completions) => completions.run(session),
             Subcommand::Which(which) => which.run(session),
             Subcommand::Use(r#use) => r#use.run(session),
             Subcommand::Setup(setup) => setup.run(session),
+            Subcommand::Doctor(doctor) => doctor.run(session),
             Subcommand::Run(run) => run.run(session),
+            Subcommand::PublishEvents(publish) => publish.run(session),
         }
     }
 }
@@ -0,0 +1,50 @@
+use structopt::StructOpt;
+
+use volta_core::error::{ExitCode, Fallible};
+use volta_core::session::{ActivityKind, Session};
+use volta_core::tool::cache;
+
+use crate::command::Command;
+
+#[derive(StructOpt)]
+pub(crate) enum Cache {
+    /// Lists the cached tool archives and the cached Node index
+    #[structopt(name = "list", alias = "ls", author = "", version = "")]
+    List,
+
+    /// Removes the cached tool archives, keeping the fetched tools
+    #[structopt(name = "clean", author = "", version = "")]
+    Clean {
+        /// Also remove the cached Node index, so it is fetched again when next needed
+        #[structopt(long = "index")]
+        index: bool,
+    },
+
+    /// Checks that the cached tool archives can be unpacked, removing any that are corrupt
+    #[structopt(name = "verify", author = "", version = "")]
+    Verify {
+        /// Also fetch the Node index again, even if the cached copy hasn't expired
+        #[structopt(long = "refresh-index")]
+        refresh_index: bool,
+    },
+
+    /// Shows the disk space used by the cached tool archives and the cached Node index
+    #[structopt(name = "size", author = "", version = "")]
+    Size,
+}
+
+impl Command for Cache {
+    fn run(self, session: &mut Session) -> Fallible<ExitCode> {
+        session.add_event_start(ActivityKind::Cache);
+
+        match self {
+            Cache::List => cache::list()?,
+            Cache::Clean { index } => cache::clean(index)?,
+            Cache::Verify { refresh_index } => cache::verify(session, refresh_index)?,
+            Cache::Size => cache::size()?,
+        }
+
+        session.add_event_end(ActivityKind::Cache, ExitCode::Success);
+        Ok(ExitCode::Success)
+    }
+}
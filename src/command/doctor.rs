@@ -0,0 +1,416 @@
+use std::env;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use log::{info, warn};
+use structopt::StructOpt;
+use which::which_in;
+
+use volta_core::error::{Context, ErrorKind, ExitCode, Fallible};
+use volta_core::fs::read_dir_eager;
+use volta_core::inventory::{node_available, npm_available, pnpm_available, yarn_available};
+use volta_core::layout::{env_paths, volta_home, volta_install};
+use volta_core::platform::System;
+use volta_core::profile::active_profile;
+use volta_core::session::{ActivityKind, Session};
+use volta_core::style::{note_prefix, success_prefix, tool_version};
+use volta_core::tool::{BinConfig, PackageConfig, PackageManager};
+
+use crate::command::Command;
+
+const SETUP_FIX: &str = "Run `volta setup`, then open a new terminal";
+
+/// The tools with shims, which other installations earlier in `PATH` would take over from
+const SHIMMED_TOOLS: [&str; 5] = ["node", "npm", "npx", "pnpm", "yarn"];
+
+#[derive(StructOpt)]
+pub(crate) struct Doctor {}
+
+/// A problem with the Volta installation or environment, along with a suggested fix
+struct Problem {
+    description: String,
+    fix: String,
+}
+
+impl Problem {
+    fn new<D: Into<String>, F: Into<String>>(description: D, fix: F) -> Self {
+        Problem {
+            description: description.into(),
+            fix: fix.into(),
+        }
+    }
+}
+
+impl Command for Doctor {
+    fn run(self, session: &mut Session) -> Fallible<ExitCode> {
+        session.add_event_start(ActivityKind::Doctor);
+
+        let mut problems = Vec::new();
+        check_volta_home(&mut problems);
+        check_path(&mut problems)?;
+        check_competing_tools(&mut problems)?;
+        check_shims(&mut problems)?;
+        check_layout(&mut problems)?;
+        check_default_platform(session, &mut problems)?;
+        check_packages(&mut problems)?;
+        check_hooks(session, &mut problems);
+
+        for problem in &problems {
+            warn!("{}", problem.description);
+            info!("{} {}", note_prefix(), problem.fix);
+        }
+
+        let exit_code = match problems.len() {
+            0 => {
+                info!("{} No problems found", success_prefix());
+                ExitCode::Success
+            }
+            count => {
+                info!(
+                    "Found {} {}",
+                    count,
+                    if count == 1 { "problem" } else { "problems" }
+                );
+                ExitCode::UnknownError
+            }
+        };
+
+        session.add_event_end(ActivityKind::Doctor, exit_code);
+        Ok(exit_code)
+    }
+}
+
+/// Check that `VOLTA_HOME` is set, which `volta setup` does on Unix
+fn check_volta_home(problems: &mut Vec<Problem>) {
+    // Note: On Windows, the installer doesn't set `VOLTA_HOME`, since the default is always used
+    if cfg!(unix) && env::var_os("VOLTA_HOME").is_none() {
+        problems.push(Problem::new("VOLTA_HOME is not set", SETUP_FIX));
+    }
+}
+
+/// The directories in `PATH`, in order
+fn path_dirs() -> Vec<PathBuf> {
+    match env::var_os("PATH") {
+        Some(path) => env::split_paths(&path).collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Check that every Volta directory is in `PATH`, with the shim directory first
+fn check_path(problems: &mut Vec<Problem>) -> Fallible<()> {
+    let path = path_dirs();
+    let shim_dir = volta_home()?.shim_dir();
+    let shim_position = path.iter().position(|dir| dir == shim_dir);
+
+    for dir in env_paths()? {
+        match path.iter().position(|entry| entry == &dir) {
+            None => problems.push(Problem::new(
+                format!("'{}' is not in PATH", dir.display()),
+                SETUP_FIX,
+            )),
+            Some(position) if matches!(shim_position, Some(shim) if position < shim) => problems
+                .push(Problem::new(
+                    format!(
+                        "'{}' comes before the shim directory '{}' in PATH",
+                        dir.display(),
+                        shim_dir.display()
+                    ),
+                    SETUP_FIX,
+                )),
+            Some(_) => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Check for tools outside of Volta that would be run instead of the shims
+///
+/// Other installations are only a problem if they come earlier in `PATH` than the shim
+/// directory, otherwise Volta only uses them when it has no version of its own to run.
+fn check_competing_tools(problems: &mut Vec<Problem>) -> Fallible<()> {
+    let cwd = env::current_dir().with_context(|| ErrorKind::CurrentDirError)?;
+    let path = path_dirs();
+    let shim_dir = volta_home()?.shim_dir();
+    let shim_position = path
+        .iter()
+        .position(|dir| dir == shim_dir)
+        .unwrap_or(path.len());
+
+    let system_path = System::path()?;
+    for dir in env::split_paths(&system_path) {
+        let position = path.iter().position(|entry| entry == &dir);
+        let before_shims = matches!(position, Some(position) if position < shim_position);
+
+        for &tool in SHIMMED_TOOLS.iter() {
+            let found = match which_in(tool, Some(&dir), &cwd) {
+                Ok(found) => found,
+                Err(_) => continue,
+            };
+
+            if before_shims {
+                problems.push(Problem::new(
+                    format!(
+                        "'{}' comes before the Volta shims in PATH, so it will be run instead of Volta's {}",
+                        found.display(),
+                        tool
+                    ),
+                    format!(
+                        "Move '{}' after '{}' in PATH, or uninstall that copy of {}",
+                        dir.display(),
+                        shim_dir.display(),
+                        tool
+                    ),
+                ));
+            } else if tool == "node" {
+                info!(
+                    "{} Found another Node at '{}', which is only used when Volta has no Node version to run",
+                    note_prefix(),
+                    found.display()
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Check that every shim links to the currently installed `volta-shim`
+fn check_shims(problems: &mut Vec<Problem>) -> Fallible<()> {
+    let shim_dir = volta_home()?.shim_dir();
+    if !shim_dir.exists() {
+        return Ok(());
+    }
+
+    let expected = volta_install()?.shim_executable();
+    let entries = read_dir_eager(shim_dir).with_context(|| ErrorKind::ReadDirError {
+        dir: shim_dir.to_owned(),
+    })?;
+
+    // Note: Only symlinks are shims, on Windows the directory also holds Git Bash scripts
+    for (entry, _) in entries.filter(|(_, metadata)| metadata.file_type().is_symlink()) {
+        let shim = entry.path();
+        match fs::read_link(&shim) {
+            Ok(target) if target == expected => {}
+            Ok(target) => problems.push(Problem::new(
+                format!(
+                    "The shim '{}' links to '{}' instead of '{}'",
+                    shim.display(),
+                    target.display(),
+                    expected.display()
+                ),
+                SETUP_FIX,
+            )),
+            Err(_) => problems.push(Problem::new(
+                format!(
+                    "Could not read the link target of the shim '{}'",
+                    shim.display()
+                ),
+                SETUP_FIX,
+            )),
+        }
+    }
+
+    Ok(())
+}
+
+/// Check that the Volta directory has been migrated to the current layout
+fn check_layout(problems: &mut Vec<Problem>) -> Fallible<()> {
+    let layout_file = volta_home()?.layout_file();
+    if !layout_file.exists() {
+        problems.push(Problem::new(
+            format!("The layout file '{}' is missing", layout_file.display()),
+            "Reinstall Volta to migrate the Volta directory to the current layout",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Check that the tools in the default platform have been fetched
+fn check_default_platform(session: &mut Session, problems: &mut Vec<Problem>) -> Fallible<()> {
+    let platform = match session.default_platform() {
+        Ok(Some(platform)) => platform.clone(),
+        Ok(None) => return Ok(()),
+        Err(error) => {
+            problems.push(Problem::new(
+                format!("Could not read the default platform: {}", error),
+                format!(
+                    "Remove '{}' and install your default tools again",
+                    active_profile()?.platform_file().display()
+                ),
+            ));
+            return Ok(());
+        }
+    };
+
+    let tools = vec![
+        (
+            "node",
+            Some(&platform.node),
+            node_available(&platform.node)?,
+        ),
+        (
+            "npm",
+            platform.npm.as_ref(),
+            platform.npm.as_ref().map_or(Ok(true), npm_available)?,
+        ),
+        (
+            "pnpm",
+            platform.pnpm.as_ref(),
+            platform.pnpm.as_ref().map_or(Ok(true), pnpm_available)?,
+        ),
+        (
+            "yarn",
+            platform.yarn.as_ref(),
+            platform.yarn.as_ref().map_or(Ok(true), yarn_available)?,
+        ),
+    ];
+
+    for (name, version, available) in tools {
+        if let (Some(version), false) = (version, available) {
+            let tool = tool_version(name, version);
+            problems.push(Problem::new(
+                format!("The default {} has not been fetched", tool),
+                format!("Run `volta install {}`", tool),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Check that the package and binary configs are consistent with each other and with the
+/// package images
+fn check_packages(problems: &mut Vec<Problem>) -> Fallible<()> {
+    let home = volta_home()?;
+    let profile = active_profile()?;
+    let mut packages = Vec::new();
+
+    for file in config_files(profile.package_dir())? {
+        match PackageConfig::from_file(&file) {
+            Ok(config) => packages.push(config),
+            Err(error) => problems.push(unreadable_config(&file, &error.to_string())),
+        }
+    }
+
+    for package in &packages {
+        let mut install = match (&package.source, &package.alias) {
+            (Some(source), _) => format!("volta install {}", source),
+            (None, Some(_)) => format!(
+                "volta install {}",
+                tool_version(&package.name, &package.version)
+            ),
+            (None, None) => format!("volta install {}", package.name),
+        };
+        if let Some(alias) = &package.alias {
+            install.push_str(&format!(" --as {}", alias));
+        }
+        if package.manager != PackageManager::Npm {
+            install.push_str(&format!(" --manager {}", package.manager));
+        }
+        let fix = format!("Run `{}`", install);
+        let version = package.version.to_string();
+        if !home.package_image_dir(&package.name, &version).exists() {
+            problems.push(Problem::new(
+                format!(
+                    "The image for package '{}' is missing",
+                    tool_version(&package.name, &package.version)
+                ),
+                fix.clone(),
+            ));
+        }
+
+        for bin in &package.bins {
+            if !profile.bin_config_file(bin).exists() {
+                problems.push(Problem::new(
+                    format!(
+                        "The binary '{}' from package '{}' has no config",
+                        bin, package.name
+                    ),
+                    fix.clone(),
+                ));
+            }
+        }
+    }
+
+    for file in config_files(profile.bin_dir())? {
+        let config = match BinConfig::from_file(&file) {
+            Ok(config) => config,
+            Err(error) => {
+                problems.push(unreadable_config(&file, &error.to_string()));
+                continue;
+            }
+        };
+
+        let provided = packages
+            .iter()
+            .any(|package| package.name == config.package && package.bins.contains(&config.name));
+        if !provided {
+            problems.push(Problem::new(
+                format!(
+                    "The binary '{}' refers to package '{}', which doesn't provide it",
+                    config.name, config.package
+                ),
+                format!(
+                    "Run `volta install {}`, or remove '{}'",
+                    config.package,
+                    file.display()
+                ),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Collect the JSON config files in a directory, which may not exist yet, including the configs
+/// of scoped packages in its `@scope` subdirectories
+fn config_files(dir: &Path) -> Fallible<Vec<PathBuf>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let entries = read_dir_eager(dir).with_context(|| ErrorKind::ReadDirError {
+        dir: dir.to_owned(),
+    })?;
+
+    let mut files = Vec::new();
+    for (entry, metadata) in entries {
+        let path = entry.path();
+        if metadata.is_dir() {
+            let scoped = read_dir_eager(&path)
+                .with_context(|| ErrorKind::ReadDirError { dir: path.clone() })?;
+            files.extend(
+                scoped
+                    .filter(|(_, metadata)| metadata.is_file())
+                    .map(|(entry, _)| entry.path()),
+            );
+        } else if metadata.is_file() {
+            files.push(path);
+        }
+    }
+
+    files.retain(|path| path.extension() == Some(OsStr::new("json")));
+    files.sort();
+
+    Ok(files)
+}
+
+fn unreadable_config(file: &Path, error: &str) -> Problem {
+    Problem::new(
+        format!("Could not read '{}': {}", file.display(), error),
+        format!("Remove '{}' and reinstall the package", file.display()),
+    )
+}
+
+/// Check that the user and project hooks can be loaded
+fn check_hooks(session: &mut Session, problems: &mut Vec<Problem>) {
+    if let Err(error) = session.hooks() {
+        problems.push(Problem::new(
+            format!("Could not load the hooks configuration: {}", error),
+            "Correct the hooks.json file mentioned above",
+        ));
+    }
+}
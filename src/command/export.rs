@@ -0,0 +1,31 @@
+use std::path::PathBuf;
+
+use structopt::StructOpt;
+
+use volta_core::error::{ExitCode, Fallible};
+use volta_core::session::{ActivityKind, Session};
+use volta_core::toolfile::Toolfile;
+
+use crate::command::Command;
+
+#[derive(StructOpt)]
+pub(crate) struct Export {
+    /// Write the toolfile to the given file, instead of printing it
+    #[structopt(long = "file", parse(from_os_str))]
+    file: Option<PathBuf>,
+}
+
+impl Command for Export {
+    fn run(self, session: &mut Session) -> Fallible<ExitCode> {
+        session.add_event_start(ActivityKind::Export);
+
+        let toolfile = Toolfile::current(session)?;
+        match &self.file {
+            Some(file) => toolfile.write(file)?,
+            None => println!("{}", toolfile.to_json()?),
+        }
+
+        session.add_event_end(ActivityKind::Export, ExitCode::Success);
+        Ok(ExitCode::Success)
+    }
+}
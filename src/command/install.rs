@@ -1,27 +1,145 @@
 use structopt::StructOpt;
 
-use volta_core::error::{ExitCode, Fallible};
+use volta_core::error::{ErrorKind, ExitCode, Fallible};
+use volta_core::platform::{CliPlatform, InheritOption};
 use volta_core::session::{ActivityKind, Session};
-use volta_core::tool::Spec;
+use volta_core::settings::Settings;
+use volta_core::tool::{node, npm, package, Package, PackageManager, Spec, Tool};
 
 use crate::command::Command;
 
 #[derive(StructOpt)]
 pub(crate) struct Install {
     /// Tools to install, like `node`, `yarn@latest` or `your-package@^14.4.3`.
+    ///
+    /// Packages can also be installed from a git URL, a tarball, or a local directory, like
+    /// `github:org/tool` or `./my-cli`.
     #[structopt(name = "tool[@version]", required = true, min_values = 1)]
     tools: Vec<String>,
+
+    /// Install a package under an alias, alongside other installed versions of it.
+    ///
+    /// The package's executables are exposed under versioned names, like `tsc4` and `tsserver4`
+    /// for `volta install typescript@4 --as tsc4`.
+    #[structopt(long = "as", value_name = "alias")]
+    alias: Option<String>,
+
+    /// The package manager used to install packages.
+    ///
+    /// Defaults to the `packageManager` in `$VOLTA_HOME/settings.json`, or npm if there is none.
+    #[structopt(
+        long = "manager",
+        value_name = "manager",
+        raw(possible_values = r#"&["npm", "pnpm", "yarn"]"#)
+    )]
+    manager: Option<PackageManager>,
+
+    /// Install and run packages with a specific Node version, instead of the default.
+    #[structopt(long = "node", value_name = "version")]
+    node: Option<String>,
+
+    /// Install and run packages with a specific npm version, instead of the default.
+    #[structopt(long = "npm", value_name = "version")]
+    npm: Option<String>,
+
+    /// Reinstall every global package with the newly installed Node.
+    #[structopt(long = "migrate-packages")]
+    migrate_packages: bool,
 }
 
 impl Command for Install {
     fn run(self, session: &mut Session) -> Fallible<ExitCode> {
         session.add_event_start(ActivityKind::Install);
 
-        for tool in Spec::from_strings(&self.tools, "install")? {
-            tool.resolve(session)?.install(session)?;
+        let tools = Spec::from_strings(&self.tools, "install")?;
+
+        if self.alias.is_some() && !matches!(tools.as_slice(), [spec] if is_package(spec)) {
+            return Err(ErrorKind::InstallAliasRequiresPackage.into());
+        }
+
+        let platform = self.parse_platform(session)?;
+        if platform.is_some() && !tools.iter().any(is_package) {
+            return Err(ErrorKind::InstallPlatformRequiresPackage.into());
+        }
+
+        if self.migrate_packages && !tools.iter().any(|spec| matches!(spec, Spec::Node(_))) {
+            return Err(ErrorKind::InstallMigrateRequiresNode.into());
+        }
+
+        for tool in tools {
+            let package = match tool {
+                Spec::Package(name, version) => {
+                    Package::with_manager(name, version, self.package_manager()?)?
+                }
+                Spec::PackageSource(source) => {
+                    Package::from_source(source, self.package_manager()?)?
+                }
+                tool => {
+                    tool.resolve(session)?.install(session)?;
+                    continue;
+                }
+            };
+
+            let package = match &self.alias {
+                Some(alias) => package.with_alias(alias.clone()),
+                None => package,
+            };
+            let package = match &platform {
+                Some(platform) => package.with_platform(platform.clone()),
+                None => package,
+            };
+            Box::new(package).install(session)?;
+        }
+
+        if self.migrate_packages {
+            package::reinstall(session, &[], true)?;
         }
 
         session.add_event_end(ActivityKind::Install, ExitCode::Success);
         Ok(ExitCode::Success)
     }
 }
+
+impl Install {
+    /// The package manager to install packages with, from the options or the user settings
+    fn package_manager(&self) -> Fallible<PackageManager> {
+        match self.manager {
+            Some(manager) => Ok(manager),
+            None => Ok(Settings::current()?.package_manager()),
+        }
+    }
+
+    /// Builds a CliPlatform from the Node and npm options, if either was given
+    ///
+    /// Will resolve a semver / tag version if necessary
+    fn parse_platform(&self, session: &mut Session) -> Fallible<Option<CliPlatform>> {
+        if self.node.is_none() && self.npm.is_none() {
+            return Ok(None);
+        }
+
+        let node = self
+            .node
+            .as_ref()
+            .map(|version| node::resolve(version.parse()?, session))
+            .transpose()?;
+
+        let npm = match &self.npm {
+            None => InheritOption::Inherit,
+            Some(version) => match npm::resolve(version.parse()?, session)? {
+                None => InheritOption::None,
+                Some(npm) => InheritOption::Some(npm),
+            },
+        };
+
+        Ok(Some(CliPlatform {
+            node,
+            npm,
+            pnpm: InheritOption::Inherit,
+            yarn: InheritOption::Inherit,
+        }))
+    }
+}
+
+fn is_package(spec: &Spec) -> bool {
+    matches!(spec, Spec::Package(..) | Spec::PackageSource(_))
+}
@@ -1,8 +1,9 @@
 //! Define the "human" format style for list commands.
 
 use std::collections::BTreeMap;
+use std::time::SystemTime;
 
-use super::{Node, Package, PackageManager, PackageManagerKind, Toolchain};
+use super::{Node, Outdated, Package, PackageManager, PackageManagerKind, Toolchain};
 use lazy_static::lazy_static;
 use textwrap::{HyphenSplitter, Wrapper};
 use volta_core::style::{text_width, tool_version, MAX_WIDTH};
@@ -42,6 +43,7 @@ pub(super) fn format(toolchain: &Toolchain) -> Option<String> {
             name,
             host_packages,
         } => display_tool(name, host_packages),
+        Toolchain::Outdated(items) => display_outdated(items),
     })
 }
 
@@ -220,6 +222,47 @@ See `volta help install` for details and more options.",
     }
 }
 
+/// Format the items of a `Toolchain::Outdated`.
+fn display_outdated(items: &[Outdated]) -> String {
+    if items.is_empty() {
+        String::from("⚡️ Your default and project tools are up to date!")
+    } else {
+        format!(
+            "⚡️ Newer versions available in your toolchain:\n\n{}",
+            items
+                .iter()
+                .map(format_outdated)
+                .collect::<Vec<String>>()
+                .join("\n")
+        )
+    }
+}
+
+/// Format a single item of a `Toolchain::Outdated` and its newer versions.
+fn format_outdated(item: &Outdated) -> String {
+    let updates = &item.updates;
+    let newer = [
+        ("latest in major", &updates.latest_in_major),
+        ("latest LTS", &updates.latest_lts),
+        ("latest", &updates.latest),
+    ]
+    .iter()
+    .filter_map(|(label, version)| {
+        version
+            .as_ref()
+            .map(|version| format!("{}{}: v{}", INDENTATION, label, version))
+    })
+    .collect::<Vec<String>>()
+    .join("\n");
+
+    WRAPPER.fill(&format!(
+        "{}{}\n{}",
+        tool_version(&item.name, &item.version),
+        item.source,
+        newer
+    ))
+}
+
 /// Format a list of `Toolchain::Package`s without detail information
 fn format_tool_list(packages: &[Package]) -> String {
     packages
@@ -255,7 +298,27 @@ fn format_runtime_list(runtimes: &[Node]) -> String {
 
 /// format a single version of `Toolchain::Node`.
 fn format_runtime(runtime: &Node) -> String {
-    format!("v{}{}", runtime.version, runtime.source)
+    format!(
+        "v{}{}{}{}",
+        runtime.version,
+        runtime.lts_tag(),
+        runtime.source,
+        format_last_used(runtime.last_used)
+    )
+}
+
+/// format when a tool version was last used, if that is known.
+fn format_last_used(last_used: Option<SystemTime>) -> String {
+    let days = last_used
+        .and_then(|time| SystemTime::now().duration_since(time).ok())
+        .map(|elapsed| elapsed.as_secs() / (24 * 60 * 60));
+
+    match days {
+        None => String::new(),
+        Some(0) => String::from(" (last used today)"),
+        Some(1) => String::from(" (last used yesterday)"),
+        Some(days) => format!(" (last used {} days ago)", days),
+    }
 }
 
 /// format a list of `Toolchain::PackageManager`s in condensed form
@@ -303,7 +366,12 @@ fn format_package_manager_list_verbose(package_managers: &[PackageManager]) -> S
 
 /// format a single `Toolchain::PackageManager`.
 fn format_package_manager(package_manager: &PackageManager) -> String {
-    format!("v{}{}", package_manager.version, package_manager.source)
+    format!(
+        "v{}{}{}",
+        package_manager.version,
+        package_manager.source,
+        format_last_used(package_manager.last_used)
+    )
 }
 
 /// format the title for a kind of package manager
@@ -312,6 +380,7 @@ fn format_package_manager(package_manager: &PackageManager) -> String {
 fn format_package_manager_kind(kind: PackageManagerKind) -> String {
     match kind {
         PackageManagerKind::Npm => "npm".into(),
+        PackageManagerKind::Pnpm => "pnpm".into(),
         PackageManagerKind::Yarn => "Yarn".into(),
     }
 }
@@ -332,7 +401,7 @@ fn format_package(package: &Package) -> String {
     match package {
         Package::Default {
             details,
-            node,
+            platform,
             tools,
             ..
         } => {
@@ -345,7 +414,7 @@ fn format_package(package: &Package) -> String {
             let binaries = WRAPPER.fill(&format!("binary tools: {}", tools));
             let platform_detail = WRAPPER.fill(&format!(
                 "runtime: {}\npackage manager: {}",
-                tool_version("node", &node),
+                tool_version("node", &platform.node),
                 // TODO: Should be updated when we support installing with custom package_managers,
                 // whether Yarn or non-built-in versions of npm
                 "npm@built-in"
@@ -391,6 +460,7 @@ mod tests {
 
     use lazy_static::lazy_static;
     use semver::Version;
+    use volta_core::platform::PlatformSpec;
 
     use super::*;
 
@@ -432,6 +502,8 @@ See options for more detailed reports by running `volta list --help`.";
             let runtime = Some(Box::new(Node {
                 source: Source::Default,
                 version: NODE_12.clone(),
+                lts: None,
+                last_used: None,
             }));
             let package_managers = vec![];
             let packages = vec![];
@@ -454,6 +526,8 @@ See options for more detailed reports by running `volta list --help`.";
             let runtime = Some(Box::new(Node {
                 source: Source::Project(PROJECT_PATH.clone()),
                 version: NODE_12.clone(),
+                lts: None,
+                last_used: None,
             }));
             let package_managers = vec![];
             let packages = vec![];
@@ -477,11 +551,14 @@ See options for more detailed reports by running `volta list --help`.";
             let runtime = Some(Box::new(Node {
                 source: Source::Default,
                 version: NODE_12.clone(),
+                lts: None,
+                last_used: None,
             }));
             let package_managers = vec![PackageManager {
                 kind: PackageManagerKind::Npm,
                 source: Source::Default,
                 version: NPM_VERSION.clone(),
+                last_used: None,
             }];
             let packages = vec![];
 
@@ -504,11 +581,14 @@ See options for more detailed reports by running `volta list --help`.";
             let runtime = Some(Box::new(Node {
                 source: Source::Default,
                 version: NODE_12.clone(),
+                lts: None,
+                last_used: None,
             }));
             let package_managers = vec![PackageManager {
                 kind: PackageManagerKind::Yarn,
                 source: Source::Default,
                 version: YARN_VERSION.clone(),
+                last_used: None,
             }];
             let packages = vec![];
 
@@ -531,11 +611,14 @@ See options for more detailed reports by running `volta list --help`.";
             let runtime = Some(Box::new(Node {
                 source: Source::Default,
                 version: NODE_12.clone(),
+                lts: None,
+                last_used: None,
             }));
             let package_managers = vec![PackageManager {
                 kind: PackageManagerKind::Npm,
                 source: Source::Project(PROJECT_PATH.clone()),
                 version: NPM_VERSION.clone(),
+                last_used: None,
             }];
             let packages = vec![];
 
@@ -558,11 +641,14 @@ See options for more detailed reports by running `volta list --help`.";
             let runtime = Some(Box::new(Node {
                 source: Source::Default,
                 version: NODE_12.clone(),
+                lts: None,
+                last_used: None,
             }));
             let package_managers = vec![PackageManager {
                 kind: PackageManagerKind::Yarn,
                 source: Source::Project(PROJECT_PATH.clone()),
                 version: YARN_VERSION.clone(),
+                last_used: None,
             }];
             let packages = vec![];
 
@@ -585,11 +671,14 @@ See options for more detailed reports by running `volta list --help`.";
             let runtime = Some(Box::new(Node {
                 source: Source::Project(PROJECT_PATH.clone()),
                 version: NODE_12.clone(),
+                lts: None,
+                last_used: None,
             }));
             let package_managers = vec![PackageManager {
                 kind: PackageManagerKind::Npm,
                 source: Source::Project(PROJECT_PATH.clone()),
                 version: NPM_VERSION.clone(),
+                last_used: None,
             }];
             let packages = vec![];
 
@@ -612,11 +701,14 @@ See options for more detailed reports by running `volta list --help`.";
             let runtime = Some(Box::new(Node {
                 source: Source::Project(PROJECT_PATH.clone()),
                 version: NODE_12.clone(),
+                lts: None,
+                last_used: None,
             }));
             let package_managers = vec![PackageManager {
                 kind: PackageManagerKind::Yarn,
                 source: Source::Project(PROJECT_PATH.clone()),
                 version: YARN_VERSION.clone(),
+                last_used: None,
             }];
             let packages = vec![];
 
@@ -640,17 +732,21 @@ See options for more detailed reports by running `volta list --help`.";
             let runtime = Some(Box::new(Node {
                 source: Source::Default,
                 version: NODE_12.clone(),
+                lts: None,
+                last_used: None,
             }));
             let package_managers = vec![
                 PackageManager {
                     kind: PackageManagerKind::Npm,
                     source: Source::Default,
                     version: NPM_VERSION.clone(),
+                    last_used: None,
                 },
                 PackageManager {
                     kind: PackageManagerKind::Yarn,
                     source: Source::Default,
                     version: YARN_VERSION.clone(),
+                    last_used: None,
                 },
             ];
             let packages = vec![];
@@ -675,17 +771,21 @@ See options for more detailed reports by running `volta list --help`.";
             let runtime = Some(Box::new(Node {
                 source: Source::Project(PROJECT_PATH.clone()),
                 version: NODE_12.clone(),
+                lts: None,
+                last_used: None,
             }));
             let package_managers = vec![
                 PackageManager {
                     kind: PackageManagerKind::Npm,
                     source: Source::Project(PROJECT_PATH.clone()),
                     version: NPM_VERSION.clone(),
+                    last_used: None,
                 },
                 PackageManager {
                     kind: PackageManagerKind::Yarn,
                     source: Source::Project(PROJECT_PATH.clone()),
                     version: YARN_VERSION.clone(),
+                    last_used: None,
                 },
             ];
             let packages = vec![];
@@ -710,17 +810,21 @@ See options for more detailed reports by running `volta list --help`.";
             let runtime = Some(Box::new(Node {
                 source: Source::Default,
                 version: NODE_12.clone(),
+                lts: None,
+                last_used: None,
             }));
             let package_managers = vec![
                 PackageManager {
                     kind: PackageManagerKind::Npm,
                     source: Source::Project(PROJECT_PATH.clone()),
                     version: NPM_VERSION.clone(),
+                    last_used: None,
                 },
                 PackageManager {
                     kind: PackageManagerKind::Yarn,
                     source: Source::Default,
                     version: YARN_VERSION.clone(),
+                    last_used: None,
                 },
             ];
             let packages = vec![];
@@ -747,17 +851,21 @@ See options for more detailed reports by running `volta list --help`.";
             let runtime = Some(Box::new(Node {
                 source: Source::Project(PROJECT_PATH.clone()),
                 version: NODE_12.clone(),
+                lts: None,
+                last_used: None,
             }));
             let package_managers = vec![
                 PackageManager {
                     kind: PackageManagerKind::Npm,
                     source: Source::Project(PROJECT_PATH.clone()),
                     version: NPM_VERSION.clone(),
+                    last_used: None,
                 },
                 PackageManager {
                     kind: PackageManagerKind::Yarn,
                     source: Source::Project(PROJECT_PATH.clone()),
                     version: YARN_VERSION.clone(),
+                    last_used: None,
                 },
             ];
             let packages = vec![
@@ -766,7 +874,12 @@ See options for more detailed reports by running `volta list --help`.";
                         name: "create-react-app".to_string(),
                         version: Version::from((3, 0, 1)),
                     },
-                    node: NODE_12.clone(),
+                    platform: PlatformSpec {
+                        node: NODE_12.clone(),
+                        npm: None,
+                        pnpm: None,
+                        yarn: None,
+                    },
                     tools: vec!["create-react-app".to_string()],
                 },
                 Package::Default {
@@ -774,7 +887,12 @@ See options for more detailed reports by running `volta list --help`.";
                         name: "typescript".to_string(),
                         version: Version::from((3, 4, 3)),
                     },
-                    node: NODE_12.clone(),
+                    platform: PlatformSpec {
+                        node: NODE_12.clone(),
+                        npm: None,
+                        pnpm: None,
+                        yarn: None,
+                    },
                     tools: vec!["tsc".to_string(), "tsserver".to_string()],
                 },
             ];
@@ -801,17 +919,21 @@ See options for more detailed reports by running `volta list --help`.";
             let runtime = Some(Box::new(Node {
                 source: Source::Project(PROJECT_PATH.clone()),
                 version: NODE_12.clone(),
+                lts: None,
+                last_used: None,
             }));
             let package_managers = vec![
                 PackageManager {
                     kind: PackageManagerKind::Npm,
                     source: Source::Project(PROJECT_PATH.clone()),
                     version: NPM_VERSION.clone(),
+                    last_used: None,
                 },
                 PackageManager {
                     kind: PackageManagerKind::Yarn,
                     source: Source::Project(PROJECT_PATH.clone()),
                     version: YARN_VERSION.clone(),
+                    last_used: None,
                 },
             ];
             let packages = vec![
@@ -825,7 +947,12 @@ See options for more detailed reports by running `volta list --help`.";
                         name: "typescript".to_string(),
                         version: Version::from((3, 4, 3)),
                     },
-                    node: NODE_12.clone(),
+                    platform: PlatformSpec {
+                        node: NODE_12.clone(),
+                        npm: None,
+                        pnpm: None,
+                        yarn: None,
+                    },
                     tools: vec!["tsc".to_string(), "tsserver".to_string()],
                 },
             ];
@@ -838,6 +965,8 @@ See options for more detailed reports by running `volta list --help`.";
     }
 
     mod node {
+        use std::time::Duration;
+
         use super::super::*;
         use super::*;
         use crate::command::list::Source;
@@ -858,6 +987,24 @@ See options for more detailed reports by running `volta list --help`.";
             let runtimes = [Node {
                 source: Source::Default,
                 version: NODE_10.clone(),
+                lts: None,
+                last_used: None,
+            }];
+
+            assert_eq!(display_node(&runtimes).as_str(), expected);
+        }
+
+        #[test]
+        fn single_last_used() {
+            let expected = "⚡️ Node runtimes in your toolchain:
+
+    v10.15.3 (default) (last used 3 days ago)";
+            let three_days = Duration::from_secs(3 * 24 * 60 * 60 + 60);
+            let runtimes = [Node {
+                source: Source::Default,
+                version: NODE_10.clone(),
+                lts: None,
+                last_used: Some(SystemTime::now() - three_days),
             }];
 
             assert_eq!(display_node(&runtimes).as_str(), expected);
@@ -872,6 +1019,8 @@ See options for more detailed reports by running `volta list --help`.";
             let runtimes = [Node {
                 source: Source::Project(PROJECT_PATH.clone()),
                 version: NODE_12.clone(),
+                lts: None,
+                last_used: None,
             }];
 
             assert_eq!(display_node(&runtimes).as_str(), expected);
@@ -886,6 +1035,24 @@ See options for more detailed reports by running `volta list --help`.";
             let runtimes = [Node {
                 source: Source::None,
                 version: NODE_11.clone(),
+                lts: None,
+                last_used: None,
+            }];
+
+            assert_eq!(display_node(&runtimes).as_str(), expected);
+        }
+
+        #[test]
+        fn single_lts() {
+            let expected = "⚡️ Node runtimes in your toolchain:
+
+    v10.15.3 (lts/dubnium) (default)";
+
+            let runtimes = [Node {
+                source: Source::Default,
+                version: NODE_10.clone(),
+                lts: Some("dubnium".into()),
+                last_used: None,
             }];
 
             assert_eq!(display_node(&runtimes).as_str(), expected);
@@ -903,14 +1070,20 @@ See options for more detailed reports by running `volta list --help`.";
                 Node {
                     source: Source::Project(PROJECT_PATH.clone()),
                     version: NODE_12.clone(),
+                    lts: None,
+                    last_used: None,
                 },
                 Node {
                     source: Source::None,
                     version: NODE_11.clone(),
+                    lts: None,
+                    last_used: None,
                 },
                 Node {
                     source: Source::Default,
                     version: NODE_10.clone(),
+                    lts: None,
+                    last_used: None,
                 },
             ];
 
@@ -949,6 +1122,19 @@ See `volta help install` for details and more options.";
             );
         }
 
+        #[test]
+        fn none_installed_pnpm() {
+            let expected = "⚡️ No pnpm versions installed.
+
+You can install a pnpm version by running `volta install pnpm`.
+See `volta help install` for details and more options.";
+
+            assert_eq!(
+                display_package_managers(PackageManagerKind::Pnpm, &[]),
+                expected
+            );
+        }
+
         #[test]
         fn single_default_npm() {
             let expected = "⚡️ Custom npm versions in your toolchain:
@@ -959,6 +1145,7 @@ See `volta help install` for details and more options.";
                 kind: PackageManagerKind::Npm,
                 source: Source::Default,
                 version: NPM_VERSION.clone(),
+                last_used: None,
             }];
 
             assert_eq!(
@@ -977,6 +1164,7 @@ See `volta help install` for details and more options.";
                 kind: PackageManagerKind::Yarn,
                 source: Source::Default,
                 version: YARN_VERSION.clone(),
+                last_used: None,
             }];
 
             assert_eq!(
@@ -995,6 +1183,7 @@ See `volta help install` for details and more options.";
                 kind: PackageManagerKind::Npm,
                 source: Source::Project(PROJECT_PATH.clone()),
                 version: NPM_VERSION.clone(),
+                last_used: None,
             }];
 
             assert_eq!(
@@ -1013,6 +1202,7 @@ See `volta help install` for details and more options.";
                 kind: PackageManagerKind::Yarn,
                 source: Source::Project(PROJECT_PATH.clone()),
                 version: YARN_VERSION.clone(),
+                last_used: None,
             }];
 
             assert_eq!(
@@ -1031,6 +1221,7 @@ See `volta help install` for details and more options.";
                 kind: PackageManagerKind::Npm,
                 source: Source::None,
                 version: NPM_VERSION.clone(),
+                last_used: None,
             }];
 
             assert_eq!(
@@ -1049,6 +1240,7 @@ See `volta help install` for details and more options.";
                 kind: PackageManagerKind::Yarn,
                 source: Source::None,
                 version: YARN_VERSION.clone(),
+                last_used: None,
             }];
 
             assert_eq!(
@@ -1070,16 +1262,19 @@ See `volta help install` for details and more options.";
                     kind: PackageManagerKind::Npm,
                     source: Source::None,
                     version: Version::from((5, 6, 0)),
+                    last_used: None,
                 },
                 PackageManager {
                     kind: PackageManagerKind::Npm,
                     source: Source::Default,
                     version: NPM_VERSION.clone(),
+                    last_used: None,
                 },
                 PackageManager {
                     kind: PackageManagerKind::Npm,
                     source: Source::Project(PROJECT_PATH.clone()),
                     version: Version::from((6, 14, 2)),
+                    last_used: None,
                 },
             ];
 
@@ -1102,16 +1297,19 @@ See `volta help install` for details and more options.";
                     kind: PackageManagerKind::Yarn,
                     source: Source::None,
                     version: Version::from((1, 3, 0)),
+                    last_used: None,
                 },
                 PackageManager {
                     kind: PackageManagerKind::Yarn,
                     source: Source::Default,
                     version: YARN_VERSION.clone(),
+                    last_used: None,
                 },
                 PackageManager {
                     kind: PackageManagerKind::Yarn,
                     source: Source::Project(PROJECT_PATH.clone()),
                     version: Version::from((1, 17, 0)),
+                    last_used: None,
                 },
             ];
 
@@ -1152,7 +1350,12 @@ See `volta help install` for details and more options.";
                     name: "ember-cli".to_string(),
                     version: Version::from((3, 10, 1)),
                 },
-                node: NODE_12.clone(),
+                platform: PlatformSpec {
+                    node: NODE_12.clone(),
+                    npm: None,
+                    pnpm: None,
+                    yarn: None,
+                },
                 tools: vec!["ember".to_string()],
             }];
 
@@ -1234,7 +1437,12 @@ See `volta help install` for details and more options.";
                         name: "ember-cli".to_string(),
                         version: Version::from((3, 10, 1)),
                     },
-                    node: NODE_12.clone(),
+                    platform: PlatformSpec {
+                        node: NODE_12.clone(),
+                        npm: None,
+                        pnpm: None,
+                        yarn: None,
+                    },
                     tools: vec!["ember".to_string()],
                 },
                 Package::Project {
@@ -1278,7 +1486,12 @@ See `volta help install` for details and more options.";
                     name: "ember-cli".to_string(),
                     version: Version::from((3, 10, 1)),
                 },
-                node: NODE_12.clone(),
+                platform: PlatformSpec {
+                    node: NODE_12.clone(),
+                    npm: None,
+                    pnpm: None,
+                    yarn: None,
+                },
                 tools: vec!["ember".to_string()],
             }];
 
@@ -1360,7 +1573,12 @@ See `volta help install` for details and more options.";
                         name: "ember-cli".to_string(),
                         version: Version::from((3, 10, 1)),
                     },
-                    node: NODE_12.clone(),
+                    platform: PlatformSpec {
+                        node: NODE_12.clone(),
+                        npm: None,
+                        pnpm: None,
+                        yarn: None,
+                    },
                     tools: vec!["ember".to_string()],
                 },
                 Package::Project {
@@ -1412,14 +1630,20 @@ See `volta help install` for details and more options.";
                 Node {
                     source: Source::Project(PROJECT_PATH.clone()),
                     version: NODE_12.clone(),
+                    lts: None,
+                    last_used: None,
                 },
                 Node {
                     source: Source::None,
                     version: NODE_11.clone(),
+                    lts: None,
+                    last_used: None,
                 },
                 Node {
                     source: Source::Default,
                     version: NODE_10.clone(),
+                    lts: None,
+                    last_used: None,
                 },
             ];
 
@@ -1428,16 +1652,19 @@ See `volta help install` for details and more options.";
                     kind: PackageManagerKind::Npm,
                     source: Source::Default,
                     version: NPM_VERSION.clone(),
+                    last_used: None,
                 },
                 PackageManager {
                     kind: PackageManagerKind::Npm,
                     source: Source::Project(PROJECT_PATH.clone()),
                     version: Version::from((6, 12, 0)),
+                    last_used: None,
                 },
                 PackageManager {
                     kind: PackageManagerKind::Npm,
                     source: Source::None,
                     version: Version::from((5, 6, 0)),
+                    last_used: None,
                 },
             ];
 
@@ -1470,14 +1697,20 @@ See `volta help install` for details and more options.";
                 Node {
                     source: Source::Project(PROJECT_PATH.clone()),
                     version: NODE_12.clone(),
+                    lts: None,
+                    last_used: None,
                 },
                 Node {
                     source: Source::None,
                     version: NODE_11.clone(),
+                    lts: None,
+                    last_used: None,
                 },
                 Node {
                     source: Source::Default,
                     version: NODE_10.clone(),
+                    lts: None,
+                    last_used: None,
                 },
             ];
 
@@ -1486,16 +1719,19 @@ See `volta help install` for details and more options.";
                     kind: PackageManagerKind::Yarn,
                     source: Source::Default,
                     version: YARN_VERSION.clone(),
+                    last_used: None,
                 },
                 PackageManager {
                     kind: PackageManagerKind::Yarn,
                     source: Source::Project(PROJECT_PATH.clone()),
                     version: Version::from((1, 17, 0)),
+                    last_used: None,
                 },
                 PackageManager {
                     kind: PackageManagerKind::Yarn,
                     source: Source::None,
                     version: Version::from((1, 4, 0)),
+                    last_used: None,
                 },
             ];
 
@@ -1545,14 +1781,20 @@ See `volta help install` for details and more options.";
                 Node {
                     source: Source::Project(PROJECT_PATH.clone()),
                     version: NODE_12.clone(),
+                    lts: None,
+                    last_used: None,
                 },
                 Node {
                     source: Source::None,
                     version: NODE_11.clone(),
+                    lts: None,
+                    last_used: None,
                 },
                 Node {
                     source: Source::Default,
                     version: NODE_10.clone(),
+                    lts: None,
+                    last_used: None,
                 },
             ];
 
@@ -1561,31 +1803,37 @@ See `volta help install` for details and more options.";
                     kind: PackageManagerKind::Npm,
                     source: Source::Default,
                     version: NPM_VERSION.clone(),
+                    last_used: None,
                 },
                 PackageManager {
                     kind: PackageManagerKind::Npm,
                     source: Source::Project(PROJECT_PATH.clone()),
                     version: Version::from((6, 12, 0)),
+                    last_used: None,
                 },
                 PackageManager {
                     kind: PackageManagerKind::Npm,
                     source: Source::None,
                     version: Version::from((5, 6, 0)),
+                    last_used: None,
                 },
                 PackageManager {
                     kind: PackageManagerKind::Yarn,
                     source: Source::Default,
                     version: YARN_VERSION.clone(),
+                    last_used: None,
                 },
                 PackageManager {
                     kind: PackageManagerKind::Yarn,
                     source: Source::Project(PROJECT_PATH.clone()),
                     version: Version::from((1, 17, 0)),
+                    last_used: None,
                 },
                 PackageManager {
                     kind: PackageManagerKind::Yarn,
                     source: Source::None,
                     version: Version::from((1, 4, 0)),
+                    last_used: None,
                 },
             ];
 
@@ -1595,7 +1843,12 @@ See `volta help install` for details and more options.";
                         name: "typescript".to_string(),
                         version: Version::from((3, 4, 3)),
                     },
-                    node: NODE_12.clone(),
+                    platform: PlatformSpec {
+                        node: NODE_12.clone(),
+                        npm: None,
+                        pnpm: None,
+                        yarn: None,
+                    },
                     tools: vec!["tsc".to_string(), "tsserver".to_string()],
                 },
                 Package::Project {
@@ -1613,7 +1866,12 @@ See `volta help install` for details and more options.";
                         name: "ember-cli".to_string(),
                         version: Version::from((3, 8, 2)),
                     },
-                    node: NODE_12.clone(),
+                    platform: PlatformSpec {
+                        node: NODE_12.clone(),
+                        npm: None,
+                        pnpm: None,
+                        yarn: None,
+                    },
                     tools: vec!["ember".to_string()],
                 },
             ];
@@ -1623,4 +1881,55 @@ See `volta help install` for details and more options.";
             );
         }
     }
+
+    mod outdated {
+        use super::*;
+        use crate::command::list::{Outdated, Source};
+        use volta_core::tool::Updates;
+
+        #[test]
+        fn none() {
+            assert_eq!(
+                display_outdated(&[]).as_str(),
+                "⚡️ Your default and project tools are up to date!"
+            );
+        }
+
+        #[test]
+        fn some() {
+            let expected = "⚡️ Newer versions available in your toolchain:
+
+    node@12.2.0 (default)
+        latest in major: v12.22.1
+        latest LTS: v14.17.0
+        latest: v16.3.0
+    typescript@3.4.1 (default)
+        latest: v4.3.2";
+
+            let items = vec![
+                Outdated {
+                    name: "node".into(),
+                    version: NODE_12.clone(),
+                    source: Source::Default,
+                    updates: Updates {
+                        latest_in_major: Some(Version::from((12, 22, 1))),
+                        latest_lts: Some(Version::from((14, 17, 0))),
+                        latest: Some(Version::from((16, 3, 0))),
+                    },
+                },
+                Outdated {
+                    name: "typescript".into(),
+                    version: Version::from((3, 4, 1)),
+                    source: Source::Default,
+                    updates: Updates {
+                        latest_in_major: None,
+                        latest_lts: None,
+                        latest: Some(Version::from((4, 3, 2))),
+                    },
+                },
+            ];
+
+            assert_eq!(display_outdated(&items), expected);
+        }
+    }
 }
@@ -0,0 +1,383 @@
+//! Define the "json" format style for list commands.
+//!
+//! Unlike the `human` and `plain` formats, this output is meant to be consumed
+//! by other tools, so its shape is versioned: any breaking change to the
+//! document must bump `SCHEMA_VERSION`. New fields may be added without a bump.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+
+use volta_core::platform::PlatformSpec;
+
+use super::{Node, Outdated, Package, PackageManager, Source, Toolchain};
+
+/// The version of the JSON document produced by `volta list --format json`.
+const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Document {
+    schema_version: u32,
+    runtimes: Vec<JsonRuntime>,
+    package_managers: Vec<JsonPackageManager>,
+    packages: Vec<JsonPackage>,
+    outdated: Vec<JsonOutdated>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum JsonSource {
+    Project { path: String },
+    Default,
+    None,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonRuntime {
+    name: &'static str,
+    version: String,
+    lts: Option<String>,
+    source: JsonSource,
+    /// When the version was last used, in seconds since the Unix epoch.
+    last_used: Option<u64>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonPackageManager {
+    name: String,
+    version: String,
+    source: JsonSource,
+    /// When the version was last used, in seconds since the Unix epoch.
+    last_used: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct JsonPackage {
+    name: String,
+    /// The installed version, or `None` when the version comes from a project.
+    version: Option<String>,
+    source: JsonSource,
+    bins: Vec<String>,
+    /// The platform the package was installed against, if it is installed.
+    platform: Option<JsonPlatform>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonOutdated {
+    name: String,
+    version: String,
+    source: JsonSource,
+    latest_in_major: Option<String>,
+    latest_lts: Option<String>,
+    latest: Option<String>,
+}
+
+#[derive(Serialize)]
+struct JsonPlatform {
+    node: String,
+    npm: Option<String>,
+    pnpm: Option<String>,
+    yarn: Option<String>,
+}
+
+pub(super) fn format(toolchain: &Toolchain) -> Option<String> {
+    let mut outdated = vec![];
+    let (runtimes, package_managers, packages) = match toolchain {
+        Toolchain::Node(runtimes) => (describe_runtimes(runtimes), vec![], vec![]),
+        Toolchain::PackageManagers { managers, .. } => {
+            (vec![], describe_package_managers(managers), vec![])
+        }
+        Toolchain::Packages(packages) => (vec![], vec![], describe_packages(packages)),
+        Toolchain::Tool { host_packages, .. } => (vec![], vec![], describe_packages(host_packages)),
+        Toolchain::Active {
+            runtime,
+            package_managers,
+            packages,
+        } => (
+            runtime
+                .as_ref()
+                .map(|runtime| describe_runtimes(&[(**runtime).clone()]))
+                .unwrap_or_default(),
+            describe_package_managers(package_managers),
+            describe_packages(packages),
+        ),
+        Toolchain::All {
+            runtimes,
+            package_managers,
+            packages,
+        } => (
+            describe_runtimes(runtimes),
+            describe_package_managers(package_managers),
+            describe_packages(packages),
+        ),
+        Toolchain::Outdated(items) => {
+            outdated = describe_outdated(items);
+            (vec![], vec![], vec![])
+        }
+    };
+
+    let document = Document {
+        schema_version: SCHEMA_VERSION,
+        runtimes,
+        package_managers,
+        packages,
+        outdated,
+    };
+
+    serde_json::to_string_pretty(&document).ok()
+}
+
+fn describe_source(source: &Source) -> JsonSource {
+    match source {
+        Source::Project(path) => JsonSource::Project {
+            path: path.display().to_string(),
+        },
+        Source::Default => JsonSource::Default,
+        Source::None => JsonSource::None,
+    }
+}
+
+fn describe_time(time: Option<SystemTime>) -> Option<u64> {
+    time.and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|since_epoch| since_epoch.as_secs())
+}
+
+fn describe_runtimes(runtimes: &[Node]) -> Vec<JsonRuntime> {
+    runtimes
+        .iter()
+        .map(|runtime| JsonRuntime {
+            name: "node",
+            version: runtime.version.to_string(),
+            lts: runtime.lts.clone(),
+            source: describe_source(&runtime.source),
+            last_used: describe_time(runtime.last_used),
+        })
+        .collect()
+}
+
+fn describe_package_managers(package_managers: &[PackageManager]) -> Vec<JsonPackageManager> {
+    package_managers
+        .iter()
+        .map(|package_manager| JsonPackageManager {
+            name: package_manager.kind.to_string(),
+            version: package_manager.version.to_string(),
+            source: describe_source(&package_manager.source),
+            last_used: describe_time(package_manager.last_used),
+        })
+        .collect()
+}
+
+fn describe_packages(packages: &[Package]) -> Vec<JsonPackage> {
+    packages.iter().map(describe_package).collect()
+}
+
+fn describe_package(package: &Package) -> JsonPackage {
+    match package {
+        Package::Default {
+            details,
+            platform,
+            tools,
+        } => JsonPackage {
+            name: details.name.clone(),
+            version: Some(details.version.to_string()),
+            source: JsonSource::Default,
+            bins: tools.clone(),
+            platform: Some(describe_platform(platform)),
+        },
+        Package::Project { name, tools, path } => JsonPackage {
+            name: name.clone(),
+            version: None,
+            source: describe_source(&Source::Project(path.clone())),
+            bins: tools.clone(),
+            platform: None,
+        },
+        Package::Fetched(details) => JsonPackage {
+            name: details.name.clone(),
+            version: Some(details.version.to_string()),
+            source: JsonSource::None,
+            bins: vec![],
+            platform: None,
+        },
+    }
+}
+
+fn describe_outdated(items: &[Outdated]) -> Vec<JsonOutdated> {
+    items
+        .iter()
+        .map(|item| JsonOutdated {
+            name: item.name.clone(),
+            version: item.version.to_string(),
+            source: describe_source(&item.source),
+            latest_in_major: item
+                .updates
+                .latest_in_major
+                .as_ref()
+                .map(ToString::to_string),
+            latest_lts: item.updates.latest_lts.as_ref().map(ToString::to_string),
+            latest: item.updates.latest.as_ref().map(ToString::to_string),
+        })
+        .collect()
+}
+
+fn describe_platform(platform: &PlatformSpec) -> JsonPlatform {
+    JsonPlatform {
+        node: platform.node.to_string(),
+        npm: platform.npm.as_ref().map(ToString::to_string),
+        pnpm: platform.pnpm.as_ref().map(ToString::to_string),
+        yarn: platform.yarn.as_ref().map(ToString::to_string),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use lazy_static::lazy_static;
+    use semver::Version;
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::command::list::{PackageDetails, PackageManagerKind};
+
+    lazy_static! {
+        static ref NODE_VERSION: Version = Version::from((12, 4, 0));
+        static ref TYPESCRIPT_VERSION: Version = Version::from((3, 4, 1));
+        static ref YARN_VERSION: Version = Version::from((1, 16, 0));
+        static ref PROJECT_PATH: PathBuf = PathBuf::from("/a/b/c");
+    }
+
+    fn parse(toolchain: &Toolchain) -> Value {
+        let output = format(toolchain).expect("JSON output is always produced");
+        serde_json::from_str(&output).expect("output is valid JSON")
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(
+            parse(&Toolchain::Packages(vec![])),
+            json!({
+                "schemaVersion": 1,
+                "runtimes": [],
+                "packageManagers": [],
+                "packages": [],
+                "outdated": [],
+            })
+        );
+    }
+
+    #[test]
+    fn runtimes() {
+        let toolchain = Toolchain::Node(vec![
+            Node {
+                source: Source::Project(PROJECT_PATH.clone()),
+                version: NODE_VERSION.clone(),
+                lts: Some("erbium".into()),
+                last_used: None,
+            },
+            Node {
+                source: Source::None,
+                version: Version::from((8, 2, 4)),
+                lts: None,
+                last_used: None,
+            },
+        ]);
+
+        assert_eq!(
+            parse(&toolchain)["runtimes"],
+            json!([
+                {
+                    "name": "node",
+                    "version": "12.4.0",
+                    "lts": "erbium",
+                    "source": { "type": "project", "path": "/a/b/c" },
+                    "lastUsed": null,
+                },
+                {
+                    "name": "node",
+                    "version": "8.2.4",
+                    "lts": null,
+                    "source": { "type": "none" },
+                    "lastUsed": null,
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn package_managers() {
+        let toolchain = Toolchain::PackageManagers {
+            kind: PackageManagerKind::Yarn,
+            managers: vec![PackageManager {
+                kind: PackageManagerKind::Yarn,
+                source: Source::Default,
+                version: YARN_VERSION.clone(),
+                last_used: None,
+            }],
+        };
+
+        assert_eq!(
+            parse(&toolchain)["packageManagers"],
+            json!([
+                {
+                    "name": "yarn",
+                    "version": "1.16.0",
+                    "source": { "type": "default" },
+                    "lastUsed": null,
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn packages() {
+        let toolchain = Toolchain::Packages(vec![
+            Package::Default {
+                details: PackageDetails {
+                    name: "typescript".into(),
+                    version: TYPESCRIPT_VERSION.clone(),
+                },
+                platform: PlatformSpec {
+                    node: NODE_VERSION.clone(),
+                    npm: None,
+                    pnpm: None,
+                    yarn: Some(YARN_VERSION.clone()),
+                },
+                tools: vec!["tsc".into(), "tsserver".into()],
+            },
+            Package::Project {
+                name: "ember-cli".into(),
+                tools: vec!["ember".into()],
+                path: PROJECT_PATH.join("package.json"),
+            },
+        ]);
+
+        assert_eq!(
+            parse(&toolchain)["packages"],
+            json!([
+                {
+                    "name": "typescript",
+                    "version": "3.4.1",
+                    "source": { "type": "default" },
+                    "bins": ["tsc", "tsserver"],
+                    "platform": {
+                        "node": "12.4.0",
+                        "npm": null,
+                        "pnpm": null,
+                        "yarn": "1.16.0",
+                    },
+                },
+                {
+                    "name": "ember-cli",
+                    "version": null,
+                    "source": { "type": "project", "path": "/a/b/c/package.json" },
+                    "bins": ["ember"],
+                    "platform": null,
+                },
+            ])
+        );
+    }
+}
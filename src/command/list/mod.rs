@@ -1,8 +1,9 @@
 mod human;
+mod json;
 mod plain;
 mod toolchain;
 
-use std::{fmt, path::PathBuf, str::FromStr};
+use std::{fmt, path::PathBuf, str::FromStr, time::SystemTime};
 
 use semver::Version;
 use structopt::StructOpt;
@@ -11,13 +12,15 @@ use crate::command::Command;
 use toolchain::Toolchain;
 use volta_core::error::{ExitCode, Fallible};
 use volta_core::inventory::package_configs;
+use volta_core::platform::PlatformSpec;
 use volta_core::project::Project;
 use volta_core::session::{ActivityKind, Session};
-use volta_core::tool::PackageConfig;
+use volta_core::tool::{PackageConfig, Updates};
 
 #[derive(Copy, Clone, PartialEq)]
 enum Format {
     Human,
+    Json,
     Plain,
 }
 
@@ -27,6 +30,7 @@ impl FromStr for Format {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "human" => Ok(Format::Human),
+            "json" => Ok(Format::Json),
             "plain" => Ok(Format::Plain),
             _ => Err("No".into()),
         }
@@ -41,7 +45,8 @@ impl FromStr for Format {
 #[derive(Clone, PartialEq, Debug)]
 enum Source {
     /// The item is from a project. The wrapped `PathBuf` is the path to the
-    /// project's `package.json`.
+    /// project's `package.json`, or to the `.nvmrc` or `.node-version` file
+    /// that pins the project's Node version.
     Project(PathBuf),
 
     /// The item is the user's default.
@@ -87,8 +92,8 @@ struct PackageDetails {
 enum Package {
     Default {
         details: PackageDetails,
-        /// The version of Node the package is installed against.
-        node: Version,
+        /// The platform the package is installed against.
+        platform: PlatformSpec,
         /// The names of the tools associated with the package.
         tools: Vec<String>,
     },
@@ -111,7 +116,7 @@ impl Package {
         match source {
             Source::Default => Package::Default {
                 details,
-                node: config.platform.node.clone(),
+                platform: config.platform.clone(),
                 tools: config.bins.clone(),
             },
             Source::Project(path) => Package::Project {
@@ -149,11 +154,26 @@ impl Package {
 struct Node {
     pub source: Source,
     pub version: Version,
+    /// The codename of the LTS line, if the version is an LTS release.
+    pub lts: Option<String>,
+    /// When the version was last used, if that has been recorded.
+    pub last_used: Option<SystemTime>,
+}
+
+impl Node {
+    /// The LTS line of the version, formatted as a tag (e.g. ` (lts/gallium)`).
+    fn lts_tag(&self) -> String {
+        match &self.lts {
+            Some(codename) => format!(" (lts/{})", codename),
+            None => String::new(),
+        }
+    }
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 enum PackageManagerKind {
     Npm,
+    Pnpm,
     Yarn,
 }
 
@@ -164,6 +184,7 @@ impl fmt::Display for PackageManagerKind {
             "{}",
             match self {
                 PackageManagerKind::Npm => "npm",
+                PackageManagerKind::Pnpm => "pnpm",
                 PackageManagerKind::Yarn => "yarn",
             }
         )
@@ -175,6 +196,18 @@ struct PackageManager {
     kind: PackageManagerKind,
     source: Source,
     version: Version,
+    /// When the version was last used, if that has been recorded.
+    last_used: Option<SystemTime>,
+}
+
+/// An item in the toolchain for which newer versions are available.
+struct Outdated {
+    /// The name of the tool or package.
+    name: String,
+    /// The version currently in use.
+    version: Version,
+    source: Source,
+    updates: Updates,
 }
 
 /// How (if at all) should the list query be narrowed?
@@ -201,14 +234,18 @@ pub(crate) struct List {
     // `Option<Subcommand>` with `impl FromStr for Subcommand` for `StructOpt`
     // because StructOpt does not currently support custom parsing for enum
     // variants (as detailed in commit 5f9214ae).
-    /// The tool to lookup - `all`, `node`, `yarn`, or the name of a package or binary.
+    /// The tool to lookup - `all`, `node`, `npm`, `pnpm`, `yarn`, or the name of a package or binary.
     #[structopt(name = "tool")]
     subcommand: Option<String>,
 
     /// Specify the output format.
     ///
-    /// Defaults to `human` for TTYs, `plain` otherwise.
-    #[structopt(long = "format", raw(possible_values = r#"&["human", "plain"]"#))]
+    /// Defaults to `human` for TTYs, `plain` otherwise. Use `json` for a
+    /// versioned, machine-readable document.
+    #[structopt(
+        long = "format",
+        raw(possible_values = r#"&["human", "json", "plain"]"#)
+    )]
     format: Option<Format>,
 
     /// Show the currently-active tool(s).
@@ -220,6 +257,13 @@ pub(crate) struct List {
     /// Show your default tool(s).
     #[structopt(long = "default", short = "d", conflicts_with = "current")]
     default: bool,
+
+    /// Show only the default and project tool(s) with newer versions available.
+    ///
+    /// For each tool, reports the newest version in the same major version, the
+    /// newest LTS version (for Node), and the newest version overall.
+    #[structopt(long = "outdated")]
+    outdated: bool,
 }
 
 /// Which tool should we look up?
@@ -233,6 +277,9 @@ enum Subcommand {
     /// Show locally cached npm versions.
     Npm,
 
+    /// Show locally cached pnpm versions.
+    Pnpm,
+
     /// Show locally cached Yarn versions.
     Yarn,
 
@@ -246,6 +293,7 @@ impl From<&str> for Subcommand {
             "all" => Subcommand::All,
             "node" => Subcommand::Node,
             "npm" => Subcommand::Npm,
+            "pnpm" => Subcommand::Pnpm,
             "yarn" => Subcommand::Yarn,
             s => Subcommand::PackageOrTool { name: s.into() },
         }
@@ -278,6 +326,7 @@ impl Command for List {
         let default_platform = session.default_platform()?;
         let format = match self.output_format() {
             Format::Human => human::format,
+            Format::Json => json::format,
             Format::Plain => plain::format,
         };
 
@@ -294,12 +343,19 @@ impl Command for List {
             Some(Subcommand::All) => Toolchain::all(project, default_platform)?,
             Some(Subcommand::Node) => Toolchain::node(project, default_platform, &filter)?,
             Some(Subcommand::Npm) => Toolchain::npm(project, default_platform, &filter)?,
+            Some(Subcommand::Pnpm) => Toolchain::pnpm(project, default_platform, &filter)?,
             Some(Subcommand::Yarn) => Toolchain::yarn(project, default_platform, &filter)?,
             Some(Subcommand::PackageOrTool { name }) => {
                 Toolchain::package_or_tool(&name, project, &filter)?
             }
         };
 
+        let toolchain = if self.outdated {
+            toolchain.outdated(session)?
+        } else {
+            toolchain
+        };
+
         if let Some(string) = format(&toolchain) {
             println!("{}", string)
         };
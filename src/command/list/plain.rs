@@ -4,7 +4,7 @@ use semver::Version;
 
 use volta_core::style::tool_version;
 
-use super::{Node, Package, PackageManager, Source, Toolchain};
+use super::{Node, Outdated, Package, PackageManager, Source, Toolchain};
 
 pub(super) fn format(toolchain: &Toolchain) -> Option<String> {
     let (runtimes, package_managers, packages) = match toolchain {
@@ -37,6 +37,7 @@ pub(super) fn format(toolchain: &Toolchain) -> Option<String> {
             describe_package_managers(&package_managers),
             describe_packages(&packages),
         ),
+        Toolchain::Outdated(items) => return describe_outdated(items),
     };
 
     match (runtimes, package_managers, packages) {
@@ -64,7 +65,7 @@ fn describe_runtimes(runtimes: &[Node]) -> Option<String> {
         Some(
             runtimes
                 .iter()
-                .map(|runtime| display_node(&runtime.source, &runtime.version))
+                .map(display_node)
                 .collect::<Vec<String>>()
                 .join("\n"),
         )
@@ -107,8 +108,27 @@ fn describe_tool_set(name: &str, hosts: &[Package]) -> String {
         .join("\n")
 }
 
-fn display_node(source: &Source, version: &Version) -> String {
-    format!("runtime {}{}", tool_version("node", version), source)
+fn describe_outdated(items: &[Outdated]) -> Option<String> {
+    if items.is_empty() {
+        None
+    } else {
+        Some(
+            items
+                .iter()
+                .map(display_outdated)
+                .collect::<Vec<String>>()
+                .join("\n"),
+        )
+    }
+}
+
+fn display_node(runtime: &Node) -> String {
+    format!(
+        "runtime {}{}{}",
+        tool_version("node", &runtime.version),
+        runtime.lts_tag(),
+        runtime.source
+    )
 }
 
 fn display_package_manager(package_manager: &PackageManager) -> String {
@@ -119,6 +139,22 @@ fn display_package_manager(package_manager: &PackageManager) -> String {
     )
 }
 
+fn display_outdated(item: &Outdated) -> String {
+    let newer = |version: &Option<Version>| match version {
+        Some(version) => version.to_string(),
+        None => String::from("none"),
+    };
+
+    format!(
+        "outdated {} / {} {} {}{}",
+        tool_version(&item.name, &item.version),
+        tool_version("in-major", newer(&item.updates.latest_in_major)),
+        tool_version("lts", newer(&item.updates.latest_lts)),
+        tool_version("latest", newer(&item.updates.latest)),
+        item.source
+    )
+}
+
 fn package_source(package: &Package) -> String {
     match package {
         Package::Default { .. } => String::from(" (default)"),
@@ -131,7 +167,7 @@ fn display_package(package: &Package) -> String {
     match package {
         Package::Default {
             details,
-            node,
+            platform,
             tools,
             ..
         } => {
@@ -144,7 +180,7 @@ fn display_package(package: &Package) -> String {
                 "package {} /{}/ {} {}{}",
                 tool_version(&details.name, &details.version),
                 tools,
-                tool_version("node", &node),
+                tool_version("node", &platform.node),
                 // Should be updated when we support installing with custom package_managers,
                 // whether Yarn or non-built-in versions of npm
                 "npm@built-in",
@@ -175,11 +211,13 @@ fn display_package(package: &Package) -> String {
 
 fn display_tool(name: &str, host: &Package) -> Option<String> {
     match host {
-        Package::Default { details, node, .. } => Some(format!(
+        Package::Default {
+            details, platform, ..
+        } => Some(format!(
             "tool {} / {} / {} {}{}",
             name,
             tool_version(&details.name, &details.version),
-            tool_version("node", &node),
+            tool_version("node", &platform.node),
             "npm@built-in",
             package_source(&host)
         )),
@@ -205,6 +243,7 @@ mod tests {
 
     use lazy_static::lazy_static;
     use semver::Version;
+    use volta_core::platform::PlatformSpec;
 
     use crate::command::list::PackageDetails;
 
@@ -212,6 +251,7 @@ mod tests {
         static ref NODE_VERSION: Version = Version::from((12, 4, 0));
         static ref TYPESCRIPT_VERSION: Version = Version::from((3, 4, 1));
         static ref NPM_VERSION: Version = Version::from((6, 13, 4));
+        static ref PNPM_VERSION: Version = Version::from((8, 6, 0));
         static ref YARN_VERSION: Version = Version::from((1, 16, 0));
         static ref PROJECT_PATH: PathBuf = PathBuf::from("/a/b/c");
     }
@@ -222,28 +262,54 @@ mod tests {
 
         #[test]
         fn default() {
-            let source = Source::Default;
+            let runtime = Node {
+                source: Source::Default,
+                version: NODE_VERSION.clone(),
+                lts: None,
+                last_used: None,
+            };
             assert_eq!(
-                display_node(&source, &NODE_VERSION).as_str(),
+                display_node(&runtime).as_str(),
                 "runtime node@12.4.0 (default)"
             );
         }
 
         #[test]
         fn project() {
-            let source = Source::Project(PROJECT_PATH.clone());
+            let runtime = Node {
+                source: Source::Project(PROJECT_PATH.clone()),
+                version: NODE_VERSION.clone(),
+                lts: None,
+                last_used: None,
+            };
             assert_eq!(
-                display_node(&source, &NODE_VERSION).as_str(),
+                display_node(&runtime).as_str(),
                 "runtime node@12.4.0 (current @ /a/b/c)"
             );
         }
 
         #[test]
         fn installed_not_set() {
-            let source = Source::None;
+            let runtime = Node {
+                source: Source::None,
+                version: NODE_VERSION.clone(),
+                lts: None,
+                last_used: None,
+            };
+            assert_eq!(display_node(&runtime).as_str(), "runtime node@12.4.0");
+        }
+
+        #[test]
+        fn lts() {
+            let runtime = Node {
+                source: Source::Default,
+                version: NODE_VERSION.clone(),
+                lts: Some("erbium".into()),
+                last_used: None,
+            };
             assert_eq!(
-                display_node(&source, &NODE_VERSION).as_str(),
-                "runtime node@12.4.0"
+                display_node(&runtime).as_str(),
+                "runtime node@12.4.0 (lts/erbium) (default)"
             );
         }
     }
@@ -260,6 +326,7 @@ mod tests {
                     kind: PackageManagerKind::Npm,
                     source: Source::Default,
                     version: NPM_VERSION.clone(),
+                    last_used: None,
                 })
                 .as_str(),
                 "package-manager npm@6.13.4 (default)"
@@ -273,6 +340,7 @@ mod tests {
                     kind: PackageManagerKind::Npm,
                     source: Source::Project(PROJECT_PATH.clone()),
                     version: NPM_VERSION.clone(),
+                    last_used: None,
                 })
                 .as_str(),
                 "package-manager npm@6.13.4 (current @ /a/b/c)"
@@ -286,6 +354,7 @@ mod tests {
                     kind: PackageManagerKind::Npm,
                     source: Source::None,
                     version: NPM_VERSION.clone(),
+                    last_used: None,
                 })
                 .as_str(),
                 "package-manager npm@6.13.4"
@@ -293,6 +362,40 @@ mod tests {
         }
     }
 
+    mod pnpm {
+        use super::super::*;
+        use super::*;
+        use crate::command::list::*;
+
+        #[test]
+        fn default() {
+            assert_eq!(
+                display_package_manager(&PackageManager {
+                    kind: PackageManagerKind::Pnpm,
+                    source: Source::Default,
+                    version: PNPM_VERSION.clone(),
+                    last_used: None,
+                })
+                .as_str(),
+                "package-manager pnpm@8.6.0 (default)"
+            );
+        }
+
+        #[test]
+        fn project() {
+            assert_eq!(
+                display_package_manager(&PackageManager {
+                    kind: PackageManagerKind::Pnpm,
+                    source: Source::Project(PROJECT_PATH.clone()),
+                    version: PNPM_VERSION.clone(),
+                    last_used: None,
+                })
+                .as_str(),
+                "package-manager pnpm@8.6.0 (current @ /a/b/c)"
+            );
+        }
+    }
+
     mod yarn {
         use super::super::*;
         use super::*;
@@ -305,6 +408,7 @@ mod tests {
                     kind: PackageManagerKind::Yarn,
                     source: Source::Default,
                     version: YARN_VERSION.clone(),
+                    last_used: None,
                 })
                 .as_str(),
                 "package-manager yarn@1.16.0 (default)"
@@ -317,7 +421,8 @@ mod tests {
                 display_package_manager(&PackageManager {
                     kind: PackageManagerKind::Yarn,
                     source: Source::Project(PROJECT_PATH.clone()),
-                    version: YARN_VERSION.clone()
+                    version: YARN_VERSION.clone(),
+                    last_used: None,
                 })
                 .as_str(),
                 "package-manager yarn@1.16.0 (current @ /a/b/c)"
@@ -330,7 +435,8 @@ mod tests {
                 display_package_manager(&PackageManager {
                     kind: PackageManagerKind::Yarn,
                     source: Source::None,
-                    version: YARN_VERSION.clone()
+                    version: YARN_VERSION.clone(),
+                    last_used: None,
                 })
                 .as_str(),
                 "package-manager yarn@1.16.0"
@@ -350,7 +456,12 @@ mod tests {
                         name: "typescript".into(),
                         version: TYPESCRIPT_VERSION.clone(),
                     },
-                    node: NODE_VERSION.clone(),
+                    platform: PlatformSpec {
+                        node: NODE_VERSION.clone(),
+                        npm: None,
+                        pnpm: None,
+                        yarn: None
+                    },
                     tools: vec!["tsc".into(), "tsserver".into()]
                 }])
                 .expect("Should always return a `String` if given a non-empty set")
@@ -387,7 +498,7 @@ mod tests {
                             name: "ember-cli".into(),
                             version: Version::from((3, 10, 0)),
                         },
-                        node: NODE_VERSION.clone(),
+                        platform: PlatformSpec { node: NODE_VERSION.clone(), npm: None, pnpm: None, yarn: None },
                         tools: vec!["ember".into()],
                     },
                     Package::Fetched(PackageDetails {
@@ -431,7 +542,12 @@ mod tests {
                             name: "typescript".into(),
                             version: TYPESCRIPT_VERSION.clone(),
                         },
-                        node: NODE_VERSION.clone(),
+                        platform: PlatformSpec {
+                            node: NODE_VERSION.clone(),
+                            npm: None,
+                            pnpm: None,
+                            yarn: None
+                        },
                         tools: vec!["tsc".into(), "tsserver".into()],
                     }
                 )
@@ -473,6 +589,50 @@ mod tests {
         }
     }
 
+    mod outdated {
+        use super::super::*;
+        use super::*;
+        use volta_core::tool::Updates;
+
+        #[test]
+        fn all_updates() {
+            let item = Outdated {
+                name: "node".into(),
+                version: NODE_VERSION.clone(),
+                source: Source::Default,
+                updates: Updates {
+                    latest_in_major: Some(Version::from((12, 22, 1))),
+                    latest_lts: Some(Version::from((14, 17, 0))),
+                    latest: Some(Version::from((16, 3, 0))),
+                },
+            };
+
+            assert_eq!(
+                display_outdated(&item).as_str(),
+                "outdated node@12.4.0 / in-major@12.22.1 lts@14.17.0 latest@16.3.0 (default)"
+            );
+        }
+
+        #[test]
+        fn partial_updates() {
+            let item = Outdated {
+                name: "yarn".into(),
+                version: YARN_VERSION.clone(),
+                source: Source::Project(PROJECT_PATH.clone()),
+                updates: Updates {
+                    latest_in_major: None,
+                    latest_lts: None,
+                    latest: Some(Version::from((3, 6, 1))),
+                },
+            };
+
+            assert_eq!(
+                display_outdated(&item).as_str(),
+                "outdated yarn@1.16.0 / in-major@none lts@none latest@3.6.1 (current @ /a/b/c)"
+            );
+        }
+    }
+
     mod toolchain {
         use super::super::*;
         use super::*;
@@ -485,11 +645,15 @@ mod tests {
                     runtimes: vec![
                         Node {
                             source: Source::Default,
-                            version: NODE_VERSION.clone()
+                            version: NODE_VERSION.clone(),
+                            lts: None,
+                            last_used: None,
                         },
                         Node {
                             source: Source::None,
-                            version: Version::from((8, 2, 4))
+                            version: Version::from((8, 2, 4)),
+                            lts: None,
+                            last_used: None,
                         }
                     ],
                     package_managers: vec![
@@ -497,21 +661,25 @@ mod tests {
                             kind: PackageManagerKind::Npm,
                             source: Source::Project(PROJECT_PATH.clone()),
                             version: NPM_VERSION.clone(),
+                            last_used: None,
                         },
                         PackageManager {
                             kind: PackageManagerKind::Npm,
                             source: Source::Default,
-                            version: Version::from((5, 10, 0))
+                            version: Version::from((5, 10, 0)),
+                            last_used: None,
                         },
                         PackageManager {
                             kind: PackageManagerKind::Yarn,
                             source: Source::Project(PROJECT_PATH.clone()),
-                            version: YARN_VERSION.clone()
+                            version: YARN_VERSION.clone(),
+                            last_used: None,
                         },
                         PackageManager {
                             kind: PackageManagerKind::Yarn,
                             source: Source::Default,
-                            version: Version::from((1, 17, 0))
+                            version: Version::from((1, 17, 0)),
+                            last_used: None,
                         }
                     ],
                     packages: vec![
@@ -520,7 +688,12 @@ mod tests {
                                 name: "ember-cli".into(),
                                 version: Version::from((3, 10, 2)),
                             },
-                            node: NODE_VERSION.clone(),
+                            platform: PlatformSpec {
+                                node: NODE_VERSION.clone(),
+                                npm: None,
+                                pnpm: None,
+                                yarn: None
+                            },
                             tools: vec!["ember".into()]
                         },
                         Package::Project {
@@ -533,7 +706,12 @@ mod tests {
                                 name: "typescript".into(),
                                 version: TYPESCRIPT_VERSION.clone(),
                             },
-                            node: NODE_VERSION.clone(),
+                            platform: PlatformSpec {
+                                node: NODE_VERSION.clone(),
+                                npm: None,
+                                pnpm: None,
+                                yarn: None
+                            },
                             tools: vec!["tsc".into(), "tsserver".into()]
                         }
                     ]
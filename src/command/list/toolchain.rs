@@ -1,11 +1,20 @@
-use super::{Filter, Node, Package, PackageManager, Source};
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use super::{Filter, Node, Outdated, Package, PackageManager, Source};
 use crate::command::list::PackageManagerKind;
+use log::debug;
 use semver::Version;
 use volta_core::error::Fallible;
-use volta_core::inventory::{node_versions, npm_versions, package_configs, yarn_versions};
+use volta_core::inventory::{
+    node_versions, npm_versions, package_configs, pnpm_versions, yarn_versions,
+};
 use volta_core::platform::PlatformSpec;
 use volta_core::project::Project;
-use volta_core::tool::PackageConfig;
+use volta_core::session::Session;
+use volta_core::tool::node::cached_lts_codenames;
+use volta_core::tool::{node, npm, package, pnpm, yarn, PackageConfig};
+use volta_core::usage::last_used;
 
 pub(super) enum Toolchain {
     Node(Vec<Node>),
@@ -28,6 +37,7 @@ pub(super) enum Toolchain {
         package_managers: Vec<PackageManager>,
         packages: Vec<Package>,
     },
+    Outdated(Vec<Outdated>),
 }
 
 /// Lightweight rule for which item to get the `Source` for.
@@ -36,6 +46,8 @@ enum Lookup {
     Runtime,
     /// Look up the npm package manager
     Npm,
+    /// Look up the pnpm package manager
+    Pnpm,
     /// Look up the Yarn package manager
     Yarn,
 }
@@ -45,10 +57,33 @@ impl Lookup {
         move |spec| match self {
             Lookup::Runtime => Some(spec.node.clone()),
             Lookup::Npm => spec.npm.clone(),
+            Lookup::Pnpm => spec.pnpm.clone(),
             Lookup::Yarn => spec.yarn.clone(),
         }
     }
 
+    /// When the given version of this kind of tool was last used, if known
+    fn last_used(&self, version: &Version) -> Option<SystemTime> {
+        let tool = match self {
+            Lookup::Runtime => "node",
+            Lookup::Npm => "npm",
+            Lookup::Pnpm => "pnpm",
+            Lookup::Yarn => "yarn",
+        };
+        last_used(tool, version)
+    }
+
+    /// The project file that the version of this kind of tool comes from
+    fn project_file(&self, project: &Project) -> PathBuf {
+        match self {
+            Lookup::Runtime => project
+                .node_version_file()
+                .unwrap_or_else(|| project.manifest_file())
+                .to_owned(),
+            _ => project.manifest_file().to_owned(),
+        }
+    }
+
     fn version_source(
         self,
         project: Option<&Project>,
@@ -57,11 +92,11 @@ impl Lookup {
     ) -> Source {
         project
             .and_then(|proj| {
-                proj.platform()
+                project_platform(proj)
                     .and_then(self.version_from_spec())
                     .and_then(|project_version| {
                         if &project_version == version {
-                            Some(Source::Project(proj.manifest_file().to_owned()))
+                            Some(Source::Project(self.project_file(proj)))
                         } else {
                             None
                         }
@@ -89,9 +124,9 @@ impl Lookup {
     ) -> Option<(Source, Version)> {
         project
             .and_then(|proj| {
-                proj.platform()
+                project_platform(proj)
                     .and_then(self.version_from_spec())
-                    .map(|version| (Source::Project(proj.manifest_file().to_owned()), version))
+                    .map(|version| (Source::Project(self.project_file(proj)), version))
             })
             .or_else(|| {
                 default
@@ -101,6 +136,20 @@ impl Lookup {
     }
 }
 
+/// Read the platform of a project, treating one that can't be read as unset
+///
+/// Listing the toolchain shouldn't fail because of a broken project, so the error is only shown
+/// in the debug output.
+fn project_platform(project: &Project) -> Option<&PlatformSpec> {
+    project.platform().unwrap_or_else(|error| {
+        debug!(
+            "Unable to read the project platform, ignoring it\n{}",
+            error
+        );
+        None
+    })
+}
+
 /// Look up the `Source` for a tool with a given name.
 fn tool_source(name: &str, project: Option<&Project>) -> Fallible<Source> {
     match project {
@@ -120,22 +169,41 @@ impl Toolchain {
         project: Option<&Project>,
         default_platform: Option<&PlatformSpec>,
     ) -> Fallible<Toolchain> {
-        let runtime = Lookup::Runtime
-            .active_tool(project, default_platform)
-            .map(|(source, version)| Box::new(Node { source, version }));
+        let codenames = cached_lts_codenames();
+        let runtime =
+            Lookup::Runtime
+                .active_tool(project, default_platform)
+                .map(|(source, version)| {
+                    Box::new(Node {
+                        source,
+                        lts: codenames.get(&version).cloned(),
+                        last_used: Lookup::Runtime.last_used(&version),
+                        version,
+                    })
+                });
 
         let package_managers =
             Lookup::Npm
                 .active_tool(project, default_platform)
                 .map(|(source, version)| PackageManager {
                     kind: PackageManagerKind::Npm,
+                    last_used: Lookup::Npm.last_used(&version),
                     source,
                     version,
                 })
                 .into_iter()
+                .chain(Lookup::Pnpm.active_tool(project, default_platform).map(
+                    |(source, version)| PackageManager {
+                        kind: PackageManagerKind::Pnpm,
+                        last_used: Lookup::Pnpm.last_used(&version),
+                        source,
+                        version,
+                    },
+                ))
                 .chain(Lookup::Yarn.active_tool(project, default_platform).map(
                     |(source, version)| PackageManager {
                         kind: PackageManagerKind::Yarn,
+                        last_used: Lookup::Yarn.last_used(&version),
                         source,
                         version,
                     },
@@ -155,10 +223,13 @@ impl Toolchain {
         project: Option<&Project>,
         default_platform: Option<&PlatformSpec>,
     ) -> Fallible<Toolchain> {
+        let codenames = cached_lts_codenames();
         let runtimes = node_versions()?
             .iter()
             .map(|version| Node {
                 source: Lookup::Runtime.version_source(project, default_platform, version),
+                lts: codenames.get(version).cloned(),
+                last_used: Lookup::Runtime.last_used(version),
                 version: version.clone(),
             })
             .collect();
@@ -167,11 +238,19 @@ impl Toolchain {
             .iter()
             .map(|version| PackageManager {
                 kind: PackageManagerKind::Npm,
+                last_used: Lookup::Npm.last_used(version),
                 source: Lookup::Npm.version_source(project, default_platform, version),
                 version: version.clone(),
             })
+            .chain(pnpm_versions()?.iter().map(|version| PackageManager {
+                kind: PackageManagerKind::Pnpm,
+                last_used: Lookup::Pnpm.last_used(version),
+                source: Lookup::Pnpm.version_source(project, default_platform, version),
+                version: version.clone(),
+            }))
             .chain(yarn_versions()?.iter().map(|version| PackageManager {
                 kind: PackageManagerKind::Yarn,
+                last_used: Lookup::Yarn.last_used(version),
                 source: Lookup::Yarn.version_source(project, default_platform, version),
                 version: version.clone(),
             }))
@@ -191,13 +270,20 @@ impl Toolchain {
         default_platform: Option<&PlatformSpec>,
         filter: &Filter,
     ) -> Fallible<Toolchain> {
+        let codenames = cached_lts_codenames();
         let runtimes = node_versions()?
             .iter()
             .filter_map(|version| {
                 let source = Lookup::Runtime.version_source(project, default_platform, version);
                 if source.allowed_with(filter) {
+                    let lts = codenames.get(version).cloned();
                     let version = version.clone();
-                    Some(Node { source, version })
+                    Some(Node {
+                        source,
+                        lts,
+                        last_used: Lookup::Runtime.last_used(&version),
+                        version,
+                    })
                 } else {
                     None
                 }
@@ -219,6 +305,7 @@ impl Toolchain {
                 if source.allowed_with(filter) {
                     Some(PackageManager {
                         kind: PackageManagerKind::Npm,
+                        last_used: Lookup::Npm.last_used(version),
                         source,
                         version: version.clone(),
                     })
@@ -234,6 +321,34 @@ impl Toolchain {
         })
     }
 
+    pub(super) fn pnpm(
+        project: Option<&Project>,
+        default_platform: Option<&PlatformSpec>,
+        filter: &Filter,
+    ) -> Fallible<Toolchain> {
+        let managers = pnpm_versions()?
+            .iter()
+            .filter_map(|version| {
+                let source = Lookup::Pnpm.version_source(project, default_platform, version);
+                if source.allowed_with(filter) {
+                    Some(PackageManager {
+                        kind: PackageManagerKind::Pnpm,
+                        last_used: Lookup::Pnpm.last_used(version),
+                        source,
+                        version: version.clone(),
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        Ok(Toolchain::PackageManagers {
+            kind: PackageManagerKind::Pnpm,
+            managers,
+        })
+    }
+
     pub(super) fn yarn(
         project: Option<&Project>,
         default_platform: Option<&PlatformSpec>,
@@ -246,6 +361,7 @@ impl Toolchain {
                 if source.allowed_with(filter) {
                     Some(PackageManager {
                         kind: PackageManagerKind::Yarn,
+                        last_used: Lookup::Yarn.last_used(version),
                         source,
                         version: version.clone(),
                     })
@@ -362,4 +478,77 @@ impl Toolchain {
 
         Ok(toolchain)
     }
+
+    /// Narrow the toolchain down to the default and project items that have
+    /// newer versions available.
+    pub(super) fn outdated(self, session: &mut Session) -> Fallible<Toolchain> {
+        let (runtimes, package_managers, packages) = match self {
+            Toolchain::Node(runtimes) => (runtimes, vec![], vec![]),
+            Toolchain::PackageManagers { managers, .. } => (vec![], managers, vec![]),
+            Toolchain::Packages(packages) => (vec![], vec![], packages),
+            Toolchain::Tool { host_packages, .. } => (vec![], vec![], host_packages),
+            Toolchain::Active {
+                runtime,
+                package_managers,
+                packages,
+            } => (
+                runtime.map(|runtime| vec![*runtime]).unwrap_or_default(),
+                package_managers,
+                packages,
+            ),
+            Toolchain::All {
+                runtimes,
+                package_managers,
+                packages,
+            } => (runtimes, package_managers, packages),
+            Toolchain::Outdated(items) => return Ok(Toolchain::Outdated(items)),
+        };
+
+        let mut items = Vec::new();
+
+        for runtime in runtimes {
+            if runtime.source != Source::None {
+                let updates = node::check_updates(&runtime.version, session)?;
+                items.push(Outdated {
+                    name: "node".into(),
+                    version: runtime.version,
+                    source: runtime.source,
+                    updates,
+                });
+            }
+        }
+
+        for manager in package_managers {
+            if manager.source != Source::None {
+                let updates = match manager.kind {
+                    PackageManagerKind::Npm => npm::check_updates(&manager.version, session)?,
+                    PackageManagerKind::Pnpm => pnpm::check_updates(&manager.version, session)?,
+                    PackageManagerKind::Yarn => yarn::check_updates(&manager.version, session)?,
+                };
+                items.push(Outdated {
+                    name: manager.kind.to_string(),
+                    version: manager.version,
+                    source: manager.source,
+                    updates,
+                });
+            }
+        }
+
+        // Project packages are skipped, since the version in use is determined by
+        // the project's own dependencies rather than by Volta.
+        for package in packages {
+            if let Package::Default { details, .. } = package {
+                let updates = package::check_updates(&details.name, &details.version)?;
+                items.push(Outdated {
+                    name: details.name,
+                    version: details.version,
+                    source: Source::Default,
+                    updates,
+                });
+            }
+        }
+
+        items.retain(|item| item.updates.is_outdated());
+        Ok(Toolchain::Outdated(items))
+    }
 }
@@ -8,7 +8,7 @@ use crate::command::Command;
 
 #[derive(StructOpt)]
 pub(crate) struct Pin {
-    /// Tools to pin, like `node@lts` or `yarn@^1.14`.
+    /// Tools to pin, like `node@lts`, `yarn@^1.14`, or `typescript@4.9`.
     #[structopt(name = "tool[@version]", required = true, min_values = 1)]
     tools: Vec<String>,
 }
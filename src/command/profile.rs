@@ -0,0 +1,54 @@
+use structopt::StructOpt;
+
+use volta_core::error::{ExitCode, Fallible};
+use volta_core::profile;
+use volta_core::session::{ActivityKind, Session};
+
+use crate::command::Command;
+
+#[derive(StructOpt)]
+pub(crate) enum Profile {
+    /// Creates a profile with its own default tools and global packages
+    #[structopt(name = "create", author = "", version = "")]
+    Create {
+        /// The name of the profile
+        #[structopt(name = "name")]
+        name: String,
+    },
+
+    /// Switches your default tools and global packages to those of a profile
+    #[structopt(name = "use", author = "", version = "")]
+    Use {
+        /// The name of the profile
+        #[structopt(name = "name")]
+        name: String,
+    },
+
+    /// Lists the profiles, marking the one in use
+    #[structopt(name = "list", alias = "ls", author = "", version = "")]
+    List,
+
+    /// Deletes a profile, along with its default tools and global packages
+    #[structopt(name = "delete", author = "", version = "")]
+    Delete {
+        /// The name of the profile
+        #[structopt(name = "name")]
+        name: String,
+    },
+}
+
+impl Command for Profile {
+    fn run(self, session: &mut Session) -> Fallible<ExitCode> {
+        session.add_event_start(ActivityKind::Profile);
+
+        match self {
+            Profile::Create { name } => profile::create(&name)?,
+            Profile::Use { name } => profile::use_profile(&name)?,
+            Profile::List => profile::list()?,
+            Profile::Delete { name } => profile::delete(&name)?,
+        }
+
+        session.add_event_end(ActivityKind::Profile, ExitCode::Success);
+        Ok(ExitCode::Success)
+    }
+}
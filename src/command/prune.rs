@@ -0,0 +1,49 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use structopt::StructOpt;
+
+use volta_core::error::{ExitCode, Fallible};
+use volta_core::session::{ActivityKind, Session};
+use volta_core::tool;
+
+use crate::command::Command;
+
+#[derive(StructOpt)]
+pub(crate) struct Prune {
+    /// Show what would be removed, without removing anything
+    #[structopt(long = "dry-run")]
+    dry_run: bool,
+
+    /// A project directory whose pinned tools should be kept (may be given multiple times)
+    #[structopt(long = "project", parse(from_os_str), number_of_values = 1)]
+    projects: Vec<PathBuf>,
+
+    /// Don't keep the tools pinned by the project in the current directory
+    #[structopt(long = "skip-current-project")]
+    skip_current_project: bool,
+
+    /// Also keep unused versions that have been run within the given number of days
+    #[structopt(long = "unused-for", value_name = "days")]
+    unused_for: Option<u64>,
+}
+
+impl Command for Prune {
+    fn run(self, session: &mut Session) -> Fallible<ExitCode> {
+        session.add_event_start(ActivityKind::Prune);
+
+        let unused_for = self
+            .unused_for
+            .map(|days| Duration::from_secs(days * 24 * 60 * 60));
+        tool::prune(
+            session,
+            &self.projects,
+            self.skip_current_project,
+            unused_for,
+            self.dry_run,
+        )?;
+
+        session.add_event_end(ActivityKind::Prune, ExitCode::Success);
+        Ok(ExitCode::Success)
+    }
+}
@@ -0,0 +1,30 @@
+use std::io::{self, Read};
+
+use log::debug;
+use structopt::StructOpt;
+
+use volta_core::error::{ExitCode, Fallible};
+use volta_core::event::send_events;
+use volta_core::session::Session;
+
+use crate::command::Command;
+
+#[derive(StructOpt)]
+pub(crate) struct PublishEvents {
+    /// The URL to send the events to
+    url: String,
+}
+
+impl Command for PublishEvents {
+    // Reads the events of another Volta process as JSON from stdin, so that process doesn't have
+    // to wait for the request to finish
+    fn run(self, _session: &mut Session) -> Fallible<ExitCode> {
+        let mut events = String::new();
+        match io::stdin().read_to_string(&mut events) {
+            Ok(_) => send_events(&self.url, &events),
+            Err(error) => debug!("Unable to read the events to publish\n{}", error),
+        }
+
+        Ok(ExitCode::Success)
+    }
+}
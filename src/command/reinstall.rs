@@ -0,0 +1,29 @@
+use structopt::StructOpt;
+
+use volta_core::error::{ExitCode, Fallible};
+use volta_core::session::{ActivityKind, Session};
+use volta_core::tool::package;
+
+use crate::command::Command;
+
+#[derive(StructOpt)]
+pub(crate) struct Reinstall {
+    /// Reinstall every global package
+    #[structopt(long = "all", conflicts_with = "package")]
+    all: bool,
+
+    /// The global packages to reinstall with the default Node, like `typescript`
+    #[structopt(name = "package", required_unless = "all")]
+    packages: Vec<String>,
+}
+
+impl Command for Reinstall {
+    fn run(self, session: &mut Session) -> Fallible<ExitCode> {
+        session.add_event_start(ActivityKind::Reinstall);
+
+        package::reinstall(session, &self.packages, self.all)?;
+
+        session.add_event_end(ActivityKind::Reinstall, ExitCode::Success);
+        Ok(ExitCode::Success)
+    }
+}
@@ -9,7 +9,7 @@ use volta_core::error::{report_error, ExitCode, Fallible};
 use volta_core::platform::{CliPlatform, InheritOption};
 use volta_core::run::execute_tool;
 use volta_core::session::{ActivityKind, Session};
-use volta_core::tool::{node, npm, yarn};
+use volta_core::tool::{node, npm, pnpm, yarn};
 
 #[derive(Debug, StructOpt)]
 pub(crate) struct Run {
@@ -25,6 +25,14 @@ pub(crate) struct Run {
     #[structopt(long = "bundled-npm", conflicts_with = "npm")]
     bundled_npm: bool,
 
+    /// Set the custom pnpm version
+    #[structopt(long = "pnpm", value_name = "version", conflicts_with = "no_pnpm")]
+    pnpm: Option<String>,
+
+    /// Disables pnpm
+    #[structopt(long = "no-pnpm", conflicts_with = "pnpm")]
+    no_pnpm: bool,
+
     /// Set the custom Yarn version
     #[structopt(long = "yarn", value_name = "version", conflicts_with = "no_yarn")]
     yarn: Option<String>,
@@ -92,6 +100,14 @@ impl Run {
             },
         };
 
+        let pnpm = match (self.no_pnpm, &self.pnpm) {
+            (true, _) => InheritOption::None,
+            (false, None) => InheritOption::Inherit,
+            (false, Some(version)) => {
+                InheritOption::Some(pnpm::resolve(version.parse()?, session)?)
+            }
+        };
+
         let yarn = match (self.no_yarn, &self.yarn) {
             (true, _) => InheritOption::None,
             (false, None) => InheritOption::Inherit,
@@ -100,7 +116,12 @@ impl Run {
             }
         };
 
-        Ok(CliPlatform { node, npm, yarn })
+        Ok(CliPlatform {
+            node,
+            npm,
+            pnpm,
+            yarn,
+        })
     }
 
     /// Convert the environment variable settings passed to the command line into a map
@@ -5,6 +5,7 @@ use volta_core::layout::volta_home;
 use volta_core::session::{ActivityKind, Session};
 use volta_core::shim::regenerate_shims_for_dir;
 use volta_core::style::success_prefix;
+use volta_core::tool::package::install_pinned_packages;
 
 use crate::command::Command;
 
@@ -17,6 +18,7 @@ impl Command for Setup {
 
         os::setup_environment()?;
         regenerate_shims_for_dir(volta_home()?.shim_dir())?;
+        install_pinned_packages(session)?;
 
         info!(
             "{} Setup complete. Open a new terminal to start using Volta!",
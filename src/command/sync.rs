@@ -0,0 +1,27 @@
+use std::path::PathBuf;
+
+use structopt::StructOpt;
+
+use volta_core::error::{ExitCode, Fallible};
+use volta_core::session::{ActivityKind, Session};
+use volta_core::toolfile::Toolfile;
+
+use crate::command::Command;
+
+#[derive(StructOpt)]
+pub(crate) struct Sync {
+    /// The toolfile declaring the default tools and global packages, like one from `volta export`
+    #[structopt(long = "file", parse(from_os_str))]
+    file: PathBuf,
+}
+
+impl Command for Sync {
+    fn run(self, session: &mut Session) -> Fallible<ExitCode> {
+        session.add_event_start(ActivityKind::Sync);
+
+        Toolfile::from_file(&self.file)?.sync(session)?;
+
+        session.add_event_end(ActivityKind::Sync, ExitCode::Success);
+        Ok(ExitCode::Success)
+    }
+}
@@ -3,24 +3,26 @@ use structopt::StructOpt;
 use volta_core::error::{ExitCode, Fallible};
 use volta_core::session::{ActivityKind, Session};
 use volta_core::tool;
-use volta_core::version::VersionSpec;
 
 use crate::command::Command;
 
 #[derive(StructOpt)]
 pub(crate) struct Uninstall {
-    /// The tool to uninstall, e.g. `node`, `npm`, `yarn`, or <package>
+    /// The tool to uninstall, e.g. `node@14.17.0`, `npm@6.14.13`, `pnpm@8.6.0`, `yarn@1.22.10`, or <package>
     tool: String,
+
+    /// Uninstall the tool even if it is used by the default platform or an installed package
+    #[structopt(long = "force")]
+    force: bool,
 }
 
 impl Command for Uninstall {
     fn run(self, session: &mut Session) -> Fallible<ExitCode> {
         session.add_event_start(ActivityKind::Uninstall);
 
-        let version = VersionSpec::default();
-        let tool = tool::Spec::from_str_and_version(&self.tool, version);
+        let tool = tool::Spec::try_from_str(&self.tool)?;
 
-        tool.uninstall()?;
+        tool.uninstall(session, self.force)?;
 
         session.add_event_end(ActivityKind::Uninstall, ExitCode::Success);
         Ok(ExitCode::Success)
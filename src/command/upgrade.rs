@@ -0,0 +1,31 @@
+use structopt::StructOpt;
+
+use volta_core::error::{ExitCode, Fallible};
+use volta_core::session::{ActivityKind, Session};
+use volta_core::tool;
+
+use crate::command::Command;
+
+#[derive(StructOpt)]
+pub(crate) struct Upgrade {
+    /// Upgrade every global package, as well as the default tools
+    #[structopt(long = "all")]
+    all: bool,
+
+    /// The default tools or global packages to upgrade, like `node`, `yarn`, or `typescript`
+    ///
+    /// Without any tools, the default Node, npm, pnpm, and Yarn are upgraded.
+    #[structopt(name = "tool")]
+    tools: Vec<String>,
+}
+
+impl Command for Upgrade {
+    fn run(self, session: &mut Session) -> Fallible<ExitCode> {
+        session.add_event_start(ActivityKind::Upgrade);
+
+        tool::upgrade(session, &self.tools, self.all)?;
+
+        session.add_event_end(ActivityKind::Upgrade, ExitCode::Success);
+        Ok(ExitCode::Success)
+    }
+}
@@ -7,6 +7,7 @@ use structopt::StructOpt;
 use volta_core::error::report_error;
 use volta_core::log::{LogContext, LogVerbosity, Logger};
 use volta_core::session::{ActivityKind, Session};
+use volta_core::tool::enable_offline;
 
 mod common;
 use common::{ensure_layout, Error};
@@ -24,6 +25,10 @@ pub fn main() {
     };
     Logger::init(LogContext::Volta, verbosity).expect("Only a single logger should be initialized");
 
+    if volta.offline {
+        enable_offline();
+    }
+
     let mut session = Session::init();
     session.add_event_start(ActivityKind::Volta);
 
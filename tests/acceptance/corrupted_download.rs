@@ -1,10 +1,13 @@
-use crate::support::sandbox::{sandbox, DistroMetadata, NodeFixture, YarnFixture};
+use crate::support::sandbox::{
+    sandbox, DistroMetadata, NodeFixture, NpmFixture, Sandbox, YarnBerryFixture, YarnFixture,
+};
 use hamcrest2::assert_that;
 use hamcrest2::prelude::*;
 use semver::Version;
 use test_support::matchers::execs;
 
 use volta_core::error::ExitCode;
+use volta_core::tool::Node;
 
 const NODE_VERSION_INFO: &str = r#"[
 {"version":"v10.99.1040","npm":"6.2.26","lts": "Dubnium","files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]},
@@ -29,8 +32,8 @@ const YARN_VERSION_INFO: &str = r#"{
     "name":"yarn",
     "dist-tags": { "latest": "1.2.42" },
     "versions": {
-        "0.0.1": { "version":"0.0.1", "dist": { "shasum":"", "tarball":"" }},
-        "1.2.42": { "version":"1.2.42", "dist": { "shasum:"", "tarball":"" }}
+        "0.0.1": { "version":"0.0.1", "dist": { "shasum":"58f67f3039445f9b802b1a891545f894d4578851", "tarball":"" }},
+        "1.2.42": { "version":"1.2.42", "dist": { "shasum":"a04472b4febb093141993a289b7253bfffba11db", "tarball":"" }}
     }
 }"#;
 
@@ -47,6 +50,56 @@ const YARN_VERSION_FIXTURES: [DistroMetadata; 2] = [
     },
 ];
 
+const YARN_BERRY_VERSION_INFO: &str = r#"{
+    "name":"@yarnpkg/cli-dist",
+    "dist-tags": { "latest":"3.12.56" },
+    "versions": {
+        "3.12.56": { "version":"3.12.56", "dist": { "shasum":"edfd25d0c2c5c326831c0766c59563e089ba1a29", "integrity":"sha512-MYOblZiL0kn4AZhTm+A/GDrxDRNbPd3j4z0xyePgd5pqzfvUBCzyTe/bnzdv5QdBLfBc3pMImzodUdsDlfErIA==", "tarball":"" }}
+    }
+}"#;
+
+const YARN_BERRY_VERSION_FIXTURES: [DistroMetadata; 1] = [DistroMetadata {
+    version: "3.12.56",
+    compressed_size: 257,
+    uncompressed_size: Some(0x0028_0000),
+}];
+
+// Note: Generated by running `corepack use yarn@3.12.56` against the fixture tarball, which hashes
+// `bin/yarn.js` rather than the tarball itself
+const PACKAGE_JSON_WITH_YARN_BERRY_HASH: &str = r#"{
+    "name": "yarn-berry-hash",
+    "packageManager": "yarn@3.12.56+sha512.ed6e4a78099a53c546b5254a9f1c7c988edbc2b1eaf09a28aca29ee452df719c05785b1632cfcd5c14eb2dfb4c6c3278d4414ddec496d73bf843a46553f3e756"
+}"#;
+
+// Note: This is the SHA-512 of the fixture tarball, which Corepack doesn't use for Yarn 2+
+const PACKAGE_JSON_WITH_YARN_BERRY_TARBALL_HASH: &str = r#"{
+    "name": "yarn-berry-tarball-hash",
+    "packageManager": "yarn@3.12.56+sha512.31839b95988bd249f80198539be03f183af10d135b3ddde3e33d31c9e3e0779a6acdfbd4042cf24defdb9f376fe507412df05cde93089b3a1d51db0395f12b20"
+}"#;
+
+// Note: The integrity for 8.1.5 is deliberately the value for a different tarball
+const NPM_VERSION_INFO: &str = r#"{
+    "name":"npm",
+    "dist-tags": { "latest": "8.1.5" },
+    "versions": {
+        "1.2.3": { "version":"1.2.3", "dist": { "shasum":"10d224f266a6fda6cc0acfc60a7ee41e68c70f64", "integrity":"sha512-2ghUiRd0O0ScNRUUzLiOwuDj3hDT/8Um4hCtVh3yFpWuFAcACPU2VShRzNjyVNDp7q/15L6MK+5JFMlhCq1PTQ==", "tarball":"" }},
+        "8.1.5": { "version":"8.1.5", "dist": { "shasum":"90d46be79e04806f4ed7a7a4b8599ea02f2a20eb", "integrity":"sha512-2ghUiRd0O0ScNRUUzLiOwuDj3hDT/8Um4hCtVh3yFpWuFAcACPU2VShRzNjyVNDp7q/15L6MK+5JFMlhCq1PTQ==", "tarball":"" }}
+    }
+}"#;
+
+const NPM_VERSION_FIXTURES: [DistroMetadata; 2] = [
+    DistroMetadata {
+        version: "1.2.3",
+        compressed_size: 239,
+        uncompressed_size: Some(0x0028_0000),
+    },
+    DistroMetadata {
+        version: "8.1.5",
+        compressed_size: 239,
+        uncompressed_size: Some(0x0028_0000),
+    },
+];
+
 #[test]
 fn install_corrupted_node_leaves_inventory_unchanged() {
     let s = sandbox()
@@ -77,6 +130,64 @@ fn install_valid_node_saves_to_inventory() {
     assert!(s.node_inventory_archive_exists(&Version::new(10, 99, 1040)));
 }
 
+#[test]
+fn install_node_replaces_corrupted_cached_archive() {
+    let version = Version::new(10, 99, 1040);
+    let cached_archive = format!(
+        ".volta/tools/inventory/node/{}",
+        Node::archive_filename(&version)
+    );
+    let s = sandbox()
+        .node_available_versions(NODE_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .file(&cached_archive, "corrupted contents")
+        .build();
+
+    assert_that!(
+        s.volta("install node@10.99.1040"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    assert!(Sandbox::path_exists(".volta/tools/image/node/10.99.1040"));
+    assert!(s.node_inventory_archive_exists(&version));
+}
+
+#[test]
+fn install_node_replaces_tampered_cached_archive() {
+    let version = Version::new(10, 99, 1040);
+    let s = sandbox()
+        .node_available_versions(NODE_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .build();
+
+    assert_that!(
+        s.volta("fetch node@10.99.1040"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+    s.remove_path(".volta/tools/image/node/10.99.1040");
+
+    // Note: Data after the end of the archive is ignored when unpacking, but changes the checksum
+    s.append_to_file(
+        &format!(
+            ".volta/tools/inventory/node/{}",
+            Node::archive_filename(&version)
+        ),
+        "tampered",
+    );
+
+    assert_that!(
+        s.volta("install --verbose node@10.99.1040"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains(
+                "[..]Cached archive at '[..]' does not match the published checksum, removing"
+            )
+    );
+
+    assert!(Sandbox::path_exists(".volta/tools/image/node/10.99.1040"));
+    assert!(s.node_inventory_archive_exists(&version));
+}
+
 #[test]
 fn install_corrupted_yarn_leaves_inventory_unchanged() {
     let s = sandbox()
@@ -111,3 +222,108 @@ fn install_valid_yarn_saves_to_inventory() {
 
     assert!(s.yarn_inventory_archive_exists("1.2.42"));
 }
+
+#[test]
+fn fetch_npm_with_mismatched_integrity_leaves_image_unchanged() {
+    let s = sandbox()
+        .npm_available_versions(NPM_VERSION_INFO)
+        .distro_mocks::<NpmFixture>(&NPM_VERSION_FIXTURES)
+        .build();
+
+    assert_that!(
+        s.volta("fetch npm@8.1.5"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains("[..]Checksum verification failed for npm@8.1.5")
+    );
+
+    assert!(!Sandbox::path_exists(".volta/tools/image/npm/8.1.5"));
+    assert!(!Sandbox::path_exists(
+        ".volta/tools/inventory/npm/npm-8.1.5.tgz"
+    ));
+}
+
+#[test]
+fn fetch_npm_with_valid_integrity_saves_to_inventory() {
+    let s = sandbox()
+        .npm_available_versions(NPM_VERSION_INFO)
+        .distro_mocks::<NpmFixture>(&NPM_VERSION_FIXTURES)
+        .build();
+
+    assert_that!(
+        s.volta("fetch npm@1.2.3"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    assert!(Sandbox::path_exists(".volta/tools/image/npm/1.2.3"));
+    assert!(Sandbox::path_exists(
+        ".volta/tools/inventory/npm/npm-1.2.3.tgz"
+    ));
+}
+
+#[test]
+fn fetch_npm_replaces_tampered_cached_tarball() {
+    let s = sandbox()
+        .npm_available_versions(NPM_VERSION_INFO)
+        .distro_mocks::<NpmFixture>(&NPM_VERSION_FIXTURES)
+        .build();
+
+    assert_that!(
+        s.volta("fetch npm@1.2.3"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+    s.remove_path(".volta/tools/image/npm/1.2.3");
+
+    // Note: Data after the end of the archive is ignored when unpacking, but changes the integrity
+    s.append_to_file(".volta/tools/inventory/npm/npm-1.2.3.tgz", "tampered");
+
+    assert_that!(
+        s.volta("fetch --verbose npm@1.2.3"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains("[..]Cached archive at '[..]' is not valid, removing")
+    );
+
+    assert!(Sandbox::path_exists(".volta/tools/image/npm/1.2.3"));
+    assert!(Sandbox::path_exists(
+        ".volta/tools/inventory/npm/npm-1.2.3.tgz"
+    ));
+}
+
+#[test]
+fn fetch_yarn_berry_with_corepack_hash_saves_to_inventory() {
+    let s = sandbox()
+        .package_json(PACKAGE_JSON_WITH_YARN_BERRY_HASH)
+        .yarn_berry_available_versions(YARN_BERRY_VERSION_INFO)
+        .distro_mocks::<YarnBerryFixture>(&YARN_BERRY_VERSION_FIXTURES)
+        .build();
+
+    assert_that!(
+        s.volta("fetch yarn@3.12.56"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    assert!(Sandbox::path_exists(
+        ".volta/tools/image/yarn/3.12.56/bin/yarn.js"
+    ));
+    assert!(s.yarn_inventory_archive_exists("3.12.56"));
+}
+
+#[test]
+fn fetch_yarn_berry_with_tarball_hash_leaves_image_unchanged() {
+    let s = sandbox()
+        .package_json(PACKAGE_JSON_WITH_YARN_BERRY_TARBALL_HASH)
+        .yarn_berry_available_versions(YARN_BERRY_VERSION_INFO)
+        .distro_mocks::<YarnBerryFixture>(&YARN_BERRY_VERSION_FIXTURES)
+        .build();
+
+    assert_that!(
+        s.volta("fetch yarn@3.12.56"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains("[..]Checksum verification failed for yarn@3.12.56")
+    );
+
+    assert!(!Sandbox::path_exists(".volta/tools/image/yarn/3.12.56"));
+    assert!(!s.yarn_inventory_archive_exists("3.12.56"));
+}
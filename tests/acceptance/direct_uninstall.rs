@@ -106,6 +106,14 @@ cfg_if::cfg_if! {
     }
 }
 
+const YARN_VERSION_INFO: &str = r#"{
+    "name":"yarn",
+    "dist-tags": { "latest":"1.2.42" },
+    "versions": {
+        "1.2.42": { "version":"1.2.42", "dist": { "shasum":"a04472b4febb093141993a289b7253bfffba11db", "tarball":"" }}
+    }
+}"#;
+
 const YARN_VERSION_FIXTURES: [DistroMetadata; 1] = [DistroMetadata {
     version: "1.2.42",
     compressed_size: 174,
@@ -295,6 +303,7 @@ fn yarn_remove_without_packages_skips_volta_logic() {
     let s = sandbox()
         .platform(&platform_with_node_yarn("10.99.1040", "1.2.42"))
         .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .yarn_available_versions(YARN_VERSION_INFO)
         .distro_mocks::<YarnFixture>(&YARN_VERSION_FIXTURES)
         .env("VOLTA_LOGLEVEL", "info")
         .build();
@@ -3,7 +3,7 @@ use std::path::PathBuf;
 use crate::support::sandbox::sandbox;
 use hamcrest2::assert_that;
 use hamcrest2::prelude::*;
-use mockito::mock;
+use mockito::{mock, Matcher};
 use test_support::matchers::execs;
 use volta_core::error::ExitCode;
 
@@ -95,6 +95,20 @@ fn yarn_hooks_json() -> String {
     )
 }
 
+fn events_hooks_json() -> String {
+    format!(
+        r#"
+{{
+    "events": {{
+        "publish": {{
+            "url": "{0}/events"
+        }}
+    }}
+}}"#,
+        mockito::SERVER_URL
+    )
+}
+
 #[test]
 fn redirects_download() {
     let s = sandbox().default_hooks(&default_hooks_json()).build();
@@ -103,8 +117,8 @@ fn redirects_download() {
         s.volta("install node@1.2.3"),
         execs()
             .with_status(ExitCode::NetworkError as i32)
-            .with_stderr_contains("[..]Could not download node@1.2.3")
-            .with_stderr_contains("[..]/hook/default/node/1.2.3")
+            .with_stderr_contains("[..]Could not download checksums for node@1.2.3")
+            .with_stderr_contains("[..]/hook/default/node/SHASUMS256.txt")
     );
 }
 
@@ -131,8 +145,8 @@ fn merges_project_and_default_hooks() {
         s.volta("install node@10.12.1"),
         execs()
             .with_status(ExitCode::NetworkError as i32)
-            .with_stderr_contains("[..]Could not download node@10.12.1")
-            .with_stderr_contains("[..]/hook/default/node/10.12.1")
+            .with_stderr_contains("[..]Could not download checksums for node@10.12.1")
+            .with_stderr_contains("[..]/hook/default/node/SHASUMS256.txt")
     );
 }
 
@@ -175,8 +189,8 @@ fn merges_workspace_hooks() {
         s.volta("install node@11.11.2"),
         execs()
             .with_status(ExitCode::NetworkError as i32)
-            .with_stderr_contains("[..]Could not download node@11.11.2")
-            .with_stderr_contains("[..]/hook/default/node/11.11.2")
+            .with_stderr_contains("[..]Could not download checksums for node@11.11.2")
+            .with_stderr_contains("[..]/hook/default/node/SHASUMS256.txt")
     );
 }
 
@@ -251,3 +265,70 @@ fn yarn_semver_with_hook_uses_old_format() {
             .with_stderr_contains("[..]Could not download yarn@3.9.2")
     );
 }
+
+#[test]
+fn yarn_upgrade_with_hook_uses_old_format() {
+    let s = sandbox()
+        .platform(
+            r#"{
+  "node": {
+    "runtime": "10.11.12",
+    "npm": null
+  },
+  "yarn": "3.1.0"
+}"#,
+        )
+        .default_hooks(&yarn_hooks_json())
+        .env("VOLTA_LOGLEVEL", "debug")
+        .build();
+    let _mock = mock("GET", "/yarn-old/index")
+        .with_status(200)
+        .with_header("Content-Type", "application/json")
+        .with_body(
+            r#"[
+    {"tag_name":"v3.1.0","assets":[{"name":"yarn-v3.1.0.tar.gz"}]},
+    {"tag_name":"v3.9.2","assets":[{"name":"yarn-v3.9.2.tar.gz"}]},
+    {"tag_name":"v4.1.1","assets":[{"name":"yarn-v4.1.1.tar.gz"}]}
+]"#,
+        )
+        .create();
+
+    assert_that!(
+        s.volta("upgrade yarn"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains("[..]Using yarn.index hook to determine yarn index URL")
+            .with_stderr_contains("[..]Could not download yarn@3.9.2")
+    );
+}
+
+#[test]
+fn publishes_events_to_url() {
+    let s = sandbox().default_hooks(&events_hooks_json()).build();
+    let events_mock = mock("POST", "/events")
+        .match_header(
+            "content-type",
+            Matcher::Regex("^application/json".to_string()),
+        )
+        .match_body(Matcher::Regex(r#""name":"volta""#.to_string()))
+        .with_status(200)
+        .create();
+
+    assert_that!(
+        s.volta("list"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    events_mock.assert();
+}
+
+#[test]
+fn failed_publish_to_url_does_not_fail_command() {
+    let s = sandbox().default_hooks(&events_hooks_json()).build();
+    let _mock = mock("POST", "/events").with_status(500).create();
+
+    assert_that!(
+        s.volta("list"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+}
@@ -14,9 +14,16 @@ cfg_if! {
         mod run_shim_directly;
         mod verbose_errors;
         mod volta_bypass;
+        mod volta_cache;
+        mod volta_doctor;
         mod volta_install;
         mod volta_pin;
+        mod volta_profile;
+        mod volta_prune;
+        mod volta_reinstall;
         mod volta_run;
+        mod volta_sync;
         mod volta_uninstall;
+        mod volta_upgrade;
     }
 }
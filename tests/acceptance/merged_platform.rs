@@ -1,4 +1,6 @@
-use crate::support::sandbox::{sandbox, DistroMetadata, NodeFixture, NpmFixture, YarnFixture};
+use crate::support::sandbox::{
+    sandbox, DistroMetadata, NodeFixture, NpmFixture, PnpmFixture, YarnFixture,
+};
 use hamcrest2::assert_that;
 use hamcrest2::prelude::*;
 use test_support::matchers::execs;
@@ -28,6 +30,34 @@ const PACKAGE_JSON_WITH_YARN: &str = r#"{
     }
 }"#;
 
+const PACKAGE_JSON_WITH_PNPM: &str = r#"{
+    "name": "with-pnpm",
+    "volta": {
+        "node": "10.99.1040",
+        "pnpm": "8.6.12"
+    }
+}"#;
+
+const PACKAGE_JSON_WITHOUT_NODE: &str = r#"{
+    "name": "without-node",
+    "volta": {
+        "npm": "4.5.6"
+    }
+}"#;
+
+const PACKAGE_JSON_WITH_PACKAGE_MANAGER: &str = r#"{
+    "name": "with-package-manager",
+    "packageManager": "yarn@1.12.99+sha1.cc1a5558d673700822f42bcadcc50b8898b3e26f",
+    "volta": {
+        "node": "10.99.1040"
+    }
+}"#;
+
+const PACKAGE_JSON_PACKAGE_MANAGER_ONLY: &str = r#"{
+    "name": "package-manager-only",
+    "packageManager": "yarn@1.12.99+sha1.cc1a5558d673700822f42bcadcc50b8898b3e26f"
+}"#;
+
 const PLATFORM_NODE_ONLY: &str = r#"{
     "node":{
         "runtime":"9.27.6",
@@ -50,6 +80,14 @@ const PLATFORM_WITH_YARN: &str = r#"{
     "yarn": "1.7.71"
 }"#;
 
+const PLATFORM_WITH_PNPM: &str = r#"{
+    "node":{
+        "runtime":"9.27.6",
+        "npm":null
+    },
+    "pnpm": "7.7.1"
+}"#;
+
 cfg_if::cfg_if! {
     if #[cfg(target_os = "macos")] {
         const NODE_VERSION_FIXTURES: [DistroMetadata; 2] = [
@@ -95,6 +133,17 @@ cfg_if::cfg_if! {
     }
 }
 
+const NPM_VERSION_INFO: &str = r#"
+{
+    "name":"npm",
+    "dist-tags": { "latest":"4.5.6" },
+    "versions": {
+        "1.2.3": { "version":"1.2.3", "dist": { "shasum":"10d224f266a6fda6cc0acfc60a7ee41e68c70f64", "tarball":"" }},
+        "4.5.6": { "version":"4.5.6", "dist": { "shasum":"459724a7681468c76bb3e45cb1ee3deb30462f6d", "tarball":"" }}
+    }
+}
+"#;
+
 const NPM_VERSION_FIXTURES: [DistroMetadata; 2] = [
     DistroMetadata {
         version: "1.2.3",
@@ -108,6 +157,15 @@ const NPM_VERSION_FIXTURES: [DistroMetadata; 2] = [
     },
 ];
 
+const YARN_VERSION_INFO: &str = r#"{
+    "name":"yarn",
+    "dist-tags": { "latest":"1.12.99" },
+    "versions": {
+        "1.7.71": { "version":"1.7.71", "dist": { "shasum":"090cc91fdeb93933518daf741acfb505106dc711", "tarball":"" }},
+        "1.12.99": { "version":"1.12.99", "dist": { "shasum":"cc1a5558d673700822f42bcadcc50b8898b3e26f", "tarball":"" }}
+    }
+}"#;
+
 const YARN_VERSION_FIXTURES: [DistroMetadata; 2] = [
     DistroMetadata {
         version: "1.12.99",
@@ -127,6 +185,7 @@ fn uses_project_npm_if_available() {
         .platform(PLATFORM_WITH_NPM)
         .package_json(PACKAGE_JSON_WITH_NPM)
         .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .npm_available_versions(NPM_VERSION_INFO)
         .distro_mocks::<NpmFixture>(&NPM_VERSION_FIXTURES)
         .env("VOLTA_LOGLEVEL", "debug")
         .build();
@@ -140,12 +199,34 @@ fn uses_project_npm_if_available() {
     );
 }
 
+#[test]
+fn uses_nvmrc_node_in_project_without_node() {
+    let s = sandbox()
+        .platform(PLATFORM_WITH_NPM)
+        .package_json(PACKAGE_JSON_WITHOUT_NODE)
+        .project_file(".nvmrc", "v10.99.1040\n")
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .npm_available_versions(NPM_VERSION_INFO)
+        .distro_mocks::<NpmFixture>(&NPM_VERSION_FIXTURES)
+        .env("VOLTA_LOGLEVEL", "debug")
+        .build();
+
+    assert_that!(
+        s.npm("--version"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains("[..]Node: 10.99.1040 from version file configuration")
+            .with_stderr_contains("[..]npm: 4.5.6 from project configuration")
+    );
+}
+
 #[test]
 fn uses_bundled_npm_in_project_without_npm() {
     let s = sandbox()
         .platform(PLATFORM_WITH_NPM)
         .package_json(PACKAGE_JSON_NODE_ONLY)
         .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .npm_available_versions(NPM_VERSION_INFO)
         .distro_mocks::<NpmFixture>(&NPM_VERSION_FIXTURES)
         .env("VOLTA_LOGLEVEL", "debug")
         .build();
@@ -164,6 +245,7 @@ fn uses_default_npm_outside_project() {
     let s = sandbox()
         .platform(PLATFORM_WITH_NPM)
         .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .npm_available_versions(NPM_VERSION_INFO)
         .distro_mocks::<NpmFixture>(&NPM_VERSION_FIXTURES)
         .env("VOLTA_LOGLEVEL", "debug")
         .build();
@@ -182,6 +264,7 @@ fn uses_bundled_npm_outside_project() {
     let s = sandbox()
         .platform(PLATFORM_NODE_ONLY)
         .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .npm_available_versions(NPM_VERSION_INFO)
         .distro_mocks::<NpmFixture>(&NPM_VERSION_FIXTURES)
         .env("VOLTA_LOGLEVEL", "debug")
         .build();
@@ -201,6 +284,7 @@ fn uses_project_yarn_if_available() {
         .platform(PLATFORM_WITH_YARN)
         .package_json(PACKAGE_JSON_WITH_YARN)
         .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .yarn_available_versions(YARN_VERSION_INFO)
         .distro_mocks::<YarnFixture>(&YARN_VERSION_FIXTURES)
         .env("VOLTA_LOGLEVEL", "debug")
         .build();
@@ -215,12 +299,52 @@ fn uses_project_yarn_if_available() {
     );
 }
 
+#[test]
+fn uses_package_manager_yarn_if_available() {
+    let s = sandbox()
+        .platform(PLATFORM_WITH_YARN)
+        .package_json(PACKAGE_JSON_WITH_PACKAGE_MANAGER)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .yarn_available_versions(YARN_VERSION_INFO)
+        .distro_mocks::<YarnFixture>(&YARN_VERSION_FIXTURES)
+        .env("VOLTA_LOGLEVEL", "debug")
+        .build();
+
+    assert_that!(
+        s.yarn("--version"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains("[..]Yarn: 1.12.99 from project configuration")
+    );
+}
+
+#[test]
+fn uses_package_manager_yarn_in_project_without_node() {
+    let s = sandbox()
+        .platform(PLATFORM_WITH_YARN)
+        .package_json(PACKAGE_JSON_PACKAGE_MANAGER_ONLY)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .yarn_available_versions(YARN_VERSION_INFO)
+        .distro_mocks::<YarnFixture>(&YARN_VERSION_FIXTURES)
+        .env("VOLTA_LOGLEVEL", "debug")
+        .build();
+
+    assert_that!(
+        s.yarn("--version"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains("[..]Node: 9.27.6 from default configuration")
+            .with_stderr_contains("[..]Yarn: 1.12.99 from project configuration")
+    );
+}
+
 #[test]
 fn uses_default_yarn_in_project_without_yarn() {
     let s = sandbox()
         .platform(PLATFORM_WITH_YARN)
         .package_json(PACKAGE_JSON_NODE_ONLY)
         .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .yarn_available_versions(YARN_VERSION_INFO)
         .distro_mocks::<YarnFixture>(&YARN_VERSION_FIXTURES)
         .env("VOLTA_LOGLEVEL", "debug")
         .build();
@@ -240,6 +364,7 @@ fn uses_default_yarn_outside_project() {
     let s = sandbox()
         .platform(PLATFORM_WITH_YARN)
         .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .yarn_available_versions(YARN_VERSION_INFO)
         .distro_mocks::<YarnFixture>(&YARN_VERSION_FIXTURES)
         .env("VOLTA_LOGLEVEL", "debug")
         .build();
@@ -254,6 +379,100 @@ fn uses_default_yarn_outside_project() {
     );
 }
 
+const PNPM_VERSION_INFO: &str = r#"{
+    "name":"pnpm",
+    "dist-tags": { "latest":"8.6.12" },
+    "versions": {
+        "7.7.1": { "version":"7.7.1", "dist": { "shasum":"85cd6f772e9501c7f7865f9a8de8ccb273ffec16", "integrity":"sha512-1saveQpUyq8ASrVW1HgD6g8v+J0VGCzgaOeouHS34XPvuOft6klQAsgfKkkPoKGt/5tZsuNRXrELTYtnqsQCkw==", "tarball":"" }},
+        "8.6.12": { "version":"8.6.12", "dist": { "shasum":"4f4089acc760845fb62839e12017cadbfcb204e0", "integrity":"sha512-zMC3A8XTompoNUqjxcNJeSXlUQmKF/7XecffNcAjEuwaRIj4c7bvth7VYZ/BUtm4Bb2vPN7Hp8Fj96+Od14JIQ==", "tarball":"" }}
+    }
+}"#;
+
+const PNPM_VERSION_FIXTURES: [DistroMetadata; 2] = [
+    DistroMetadata {
+        version: "7.7.1",
+        compressed_size: 199,
+        uncompressed_size: Some(0x0028_0000),
+    },
+    DistroMetadata {
+        version: "8.6.12",
+        compressed_size: 200,
+        uncompressed_size: Some(0x0028_0000),
+    },
+];
+
+#[test]
+fn uses_project_pnpm_if_available() {
+    let s = sandbox()
+        .platform(PLATFORM_WITH_PNPM)
+        .package_json(PACKAGE_JSON_WITH_PNPM)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .pnpm_available_versions(PNPM_VERSION_INFO)
+        .distro_mocks::<PnpmFixture>(&PNPM_VERSION_FIXTURES)
+        .env("VOLTA_LOGLEVEL", "debug")
+        .build();
+
+    assert_that!(
+        s.pnpm("--version"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_does_not_contain("[..]pnpm is not available.")
+            .with_stderr_contains("[..]pnpm: 8.6.12 from project configuration")
+    );
+}
+
+#[test]
+fn uses_default_pnpm_in_project_without_pnpm() {
+    let s = sandbox()
+        .platform(PLATFORM_WITH_PNPM)
+        .package_json(PACKAGE_JSON_NODE_ONLY)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .pnpm_available_versions(PNPM_VERSION_INFO)
+        .distro_mocks::<PnpmFixture>(&PNPM_VERSION_FIXTURES)
+        .env("VOLTA_LOGLEVEL", "debug")
+        .build();
+
+    assert_that!(
+        s.pnpm("--version"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains("[..]pnpm: 7.7.1 from default configuration")
+    );
+}
+
+#[test]
+fn uses_default_pnpm_outside_project() {
+    let s = sandbox()
+        .platform(PLATFORM_WITH_PNPM)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .pnpm_available_versions(PNPM_VERSION_INFO)
+        .distro_mocks::<PnpmFixture>(&PNPM_VERSION_FIXTURES)
+        .env("VOLTA_LOGLEVEL", "debug")
+        .build();
+
+    assert_that!(
+        s.pnpm("--version"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains("[..]pnpm: 7.7.1 from default configuration")
+    );
+}
+
+#[test]
+fn throws_pnpm_project_error_in_project() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .package_json(PACKAGE_JSON_NODE_ONLY)
+        .build();
+
+    assert_that!(
+        s.pnpm("--version"),
+        execs()
+            .with_status(ExitCode::ExecutionFailure as i32)
+            .with_stderr_contains("[..]No pnpm version found in this project.")
+    );
+}
+
 #[test]
 fn throws_project_error_in_project() {
     let s = sandbox()
@@ -269,6 +488,27 @@ fn throws_project_error_in_project() {
     );
 }
 
+#[test]
+fn uses_yarn_path_in_project_without_yarn() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .package_json(PACKAGE_JSON_NODE_ONLY)
+        .project_file(".yarnrc.yml", "yarnPath: .yarn/releases/yarn-3.6.1.cjs\n")
+        .project_file(".yarn/releases/yarn-3.6.1.cjs", "")
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .env("VOLTA_LOGLEVEL", "debug")
+        .build();
+
+    assert_that!(
+        s.yarn("--version"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_does_not_contain("[..]No Yarn version found in this project.")
+            .with_stderr_contains("[..]Using yarnPath '[..]yarn-3.6.1.cjs' from .yarnrc.yml")
+            .with_stderr_contains("[..]Node: 10.99.1040 from project configuration")
+    );
+}
+
 #[test]
 fn throws_default_error_outside_project() {
     let s = sandbox().platform(PLATFORM_NODE_ONLY).build();
@@ -30,7 +30,7 @@ fn empty_volta_home_is_created() {
     assert!(Sandbox::path_exists(".volta/tools/user"));
 
     // Layout file should now exist
-    assert!(Sandbox::path_exists(".volta/layout.v3"));
+    assert!(Sandbox::path_exists(".volta/layout.v4"));
 
     // shims should all be created
     // NOTE: this doesn't work in Windows, because the default shims are stored separately
@@ -73,7 +73,8 @@ fn legacy_v0_volta_home_is_upgraded() {
     // Most recent layout file should exist, others should not
     assert!(!Sandbox::path_exists(".volta/layout.v1"));
     assert!(!Sandbox::path_exists(".volta/layout.v2"));
-    assert!(Sandbox::path_exists(".volta/layout.v3"));
+    assert!(!Sandbox::path_exists(".volta/layout.v3"));
+    assert!(Sandbox::path_exists(".volta/layout.v4"));
 
     // shims should all be created
     // NOTE: this doesn't work in Windows, because the default shims are stored separately
@@ -141,7 +142,8 @@ fn tagged_v1_volta_home_is_upgraded() {
     // Most recent layout file should exist, others should not
     assert!(!Sandbox::path_exists(".volta/layout.v1"));
     assert!(!Sandbox::path_exists(".volta/layout.v2"));
-    assert!(Sandbox::path_exists(".volta/layout.v3"));
+    assert!(!Sandbox::path_exists(".volta/layout.v3"));
+    assert!(Sandbox::path_exists(".volta/layout.v4"));
 
     // shims should all be created
     // NOTE: this doesn't work in Windows, because the default shims are stored separately
@@ -205,13 +207,57 @@ fn tagged_v1_to_v2_keeps_migrated_node_images() {
     ));
 }
 
+#[cfg(unix)]
+const COWSAY_PACKAGE_CONFIG: &str = r#"{
+  "name": "cowsay",
+  "version": "1.4.0",
+  "platform": {
+    "node": "11.10.1",
+    "npm": "6.7.0",
+    "yarn": null
+  },
+  "bins": [
+    "cowsay"
+  ],
+  "manager": "Npm"
+}"#;
+
+// Note: On Windows, npm uses a different directory structure within the package image
+#[cfg(unix)]
 #[test]
-fn current_v3_volta_home_is_unchanged() {
-    let s = sandbox().layout_file("v3").build();
+fn tagged_v3_volta_home_moves_package_images() {
+    let s = sandbox()
+        .layout_file("v3")
+        .package_config("cowsay", COWSAY_PACKAGE_CONFIG)
+        .file(
+            ".volta/tools/image/packages/cowsay/lib/node_modules/cowsay/package.json",
+            r#"{"name":"cowsay","version":"1.4.0"}"#,
+        )
+        .build();
+
+    assert_that!(s.volta("--version"), execs().with_status(0));
+
+    // The package image is now keyed by its version
+    assert!(!Sandbox::path_exists(".volta/tools/image/packages/cowsay"));
+    assert!(Sandbox::path_exists(
+        ".volta/tools/image/packages/cowsay@1.4.0/lib/node_modules/cowsay/package.json"
+    ));
+
+    // The pnpm directories, which aren't part of the V3 layout, are created
+    assert!(Sandbox::path_exists(".volta/tools/image/pnpm"));
+    assert!(Sandbox::path_exists(".volta/tools/inventory/pnpm"));
+
+    assert!(!Sandbox::path_exists(".volta/layout.v3"));
+    assert!(Sandbox::path_exists(".volta/layout.v4"));
+}
+
+#[test]
+fn current_v4_volta_home_is_unchanged() {
+    let s = sandbox().layout_file("v4").build();
 
     // directories that are already created by the test framework
     assert!(Sandbox::path_exists(".volta"));
-    assert!(Sandbox::path_exists(".volta/layout.v3"));
+    assert!(Sandbox::path_exists(".volta/layout.v4"));
     assert!(Sandbox::path_exists(".volta/cache/node"));
     assert!(Sandbox::path_exists(".volta/tmp"));
     assert!(Sandbox::path_exists(".volta/tools/inventory/node"));
@@ -222,7 +268,7 @@ fn current_v3_volta_home_is_unchanged() {
 
     // everything should be the same as before running the command
     assert!(Sandbox::path_exists(".volta"));
-    assert!(Sandbox::path_exists(".volta/layout.v3"));
+    assert!(Sandbox::path_exists(".volta/layout.v4"));
     assert!(Sandbox::path_exists(".volta/cache/node"));
     assert!(Sandbox::path_exists(".volta/tmp"));
     assert!(Sandbox::path_exists(".volta/tools/inventory/node"));
@@ -9,7 +9,7 @@ use hyperx::header::HttpDate;
 use mockito::{self, mock, Matcher};
 use semver::Version;
 use test_support::{self, ok_or_panic, paths, paths::PathExt, process::ProcessBuilder};
-use volta_core::fs::symlink_file;
+use volta_core::fs::{set_executable, symlink_file};
 use volta_core::tool::{Node, Yarn, NODE_DISTRO_ARCH, NODE_DISTRO_EXTENSION, NODE_DISTRO_OS};
 
 // version cache for node and yarn
@@ -113,12 +113,18 @@ pub struct SandboxBuilder {
     files: Vec<FileBuilder>,
     caches: Vec<CacheBuilder>,
     path_dirs: Vec<PathBuf>,
+    executables: Vec<PathBuf>,
 }
 
 pub trait DistroFixture: From<DistroMetadata> {
     fn server_path(&self) -> String;
     fn fixture_path(&self) -> String;
     fn metadata(&self) -> &DistroMetadata;
+
+    /// The server path and fixture path of the checksum file published with the distro, if any
+    fn checksum_paths(&self) -> Option<(String, String)> {
+        None
+    }
 }
 
 #[derive(Clone)]
@@ -136,10 +142,18 @@ pub struct NpmFixture {
     pub metadata: DistroMetadata,
 }
 
+pub struct PnpmFixture {
+    pub metadata: DistroMetadata,
+}
+
 pub struct YarnFixture {
     pub metadata: DistroMetadata,
 }
 
+pub struct YarnBerryFixture {
+    pub metadata: DistroMetadata,
+}
+
 impl From<DistroMetadata> for NodeFixture {
     fn from(metadata: DistroMetadata) -> Self {
         Self { metadata }
@@ -152,12 +166,24 @@ impl From<DistroMetadata> for NpmFixture {
     }
 }
 
+impl From<DistroMetadata> for PnpmFixture {
+    fn from(metadata: DistroMetadata) -> Self {
+        Self { metadata }
+    }
+}
+
 impl From<DistroMetadata> for YarnFixture {
     fn from(metadata: DistroMetadata) -> Self {
         Self { metadata }
     }
 }
 
+impl From<DistroMetadata> for YarnBerryFixture {
+    fn from(metadata: DistroMetadata) -> Self {
+        Self { metadata }
+    }
+}
+
 impl DistroFixture for NodeFixture {
     fn server_path(&self) -> String {
         let version = &self.metadata.version;
@@ -178,6 +204,14 @@ impl DistroFixture for NodeFixture {
     fn metadata(&self) -> &DistroMetadata {
         &self.metadata
     }
+
+    fn checksum_paths(&self) -> Option<(String, String)> {
+        let version = &self.metadata.version;
+        Some((
+            format!("/v{}/SHASUMS256.txt", version),
+            format!("tests/fixtures/node-v{}-SHASUMS256.txt", version),
+        ))
+    }
 }
 
 impl DistroFixture for NpmFixture {
@@ -194,6 +228,20 @@ impl DistroFixture for NpmFixture {
     }
 }
 
+impl DistroFixture for PnpmFixture {
+    fn server_path(&self) -> String {
+        format!("/pnpm/-/pnpm-{}.tgz", self.metadata.version)
+    }
+
+    fn fixture_path(&self) -> String {
+        format!("tests/fixtures/pnpm-{}.tgz", self.metadata.version)
+    }
+
+    fn metadata(&self) -> &DistroMetadata {
+        &self.metadata
+    }
+}
+
 impl DistroFixture for YarnFixture {
     fn server_path(&self) -> String {
         format!("/yarn/-/yarn-{}.tgz", self.metadata.version)
@@ -208,6 +256,23 @@ impl DistroFixture for YarnFixture {
     }
 }
 
+impl DistroFixture for YarnBerryFixture {
+    fn server_path(&self) -> String {
+        format!(
+            "/@yarnpkg/cli-dist/-/cli-dist-{}.tgz",
+            self.metadata.version
+        )
+    }
+
+    fn fixture_path(&self) -> String {
+        format!("tests/fixtures/cli-dist-{}.tgz", self.metadata.version)
+    }
+
+    fn metadata(&self) -> &DistroMetadata {
+        &self.metadata
+    }
+}
+
 impl SandboxBuilder {
     /// Root of the project, ex: `/path/to/cargo/target/integration_test/t0/foo`
     pub fn root(&self) -> PathBuf {
@@ -226,6 +291,7 @@ impl SandboxBuilder {
             files: vec![],
             caches: vec![],
             path_dirs: vec![volta_bin_dir()],
+            executables: vec![],
         }
     }
 
@@ -296,6 +362,17 @@ impl SandboxBuilder {
         self
     }
 
+    /// Setup mock to return the available Yarn 2+ versions (chainable)
+    pub fn yarn_berry_available_versions(mut self, body: &str) -> Self {
+        let mock = mock("GET", "/@yarnpkg/cli-dist")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(body)
+            .create();
+        self.root.mocks.push(mock);
+        self
+    }
+
     /// Setup mock to return the available npm versions (chainable)
     pub fn npm_available_versions(mut self, body: &str) -> Self {
         let mock = mock("GET", "/npm")
@@ -308,6 +385,30 @@ impl SandboxBuilder {
         self
     }
 
+    /// Setup mock to return the available pnpm versions (chainable)
+    pub fn pnpm_available_versions(mut self, body: &str) -> Self {
+        let mock = mock("GET", "/pnpm")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(body)
+            .create();
+        self.root.mocks.push(mock);
+
+        self
+    }
+
+    /// Setup mock to return the available versions of a package (chainable)
+    pub fn package_available_versions(mut self, name: &str, body: &str) -> Self {
+        let mock = mock("GET", format!("/{}", name).as_str())
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(body)
+            .create();
+        self.root.mocks.push(mock);
+
+        self
+    }
+
     /// Setup mock to return a 404 for any GET request
     /// Note: Mocks are matched in reverse order, so any created _after_ this will work
     ///       While those created before will not
@@ -348,6 +449,13 @@ impl SandboxBuilder {
             .create();
         self.root.mocks.push(file_mock);
 
+        if let Some((checksum_server_path, checksum_fixture_path)) = fx.checksum_paths() {
+            let checksum_mock = mock("GET", &checksum_server_path[..])
+                .with_body_from_file(&checksum_fixture_path)
+                .create();
+            self.root.mocks.push(checksum_mock);
+        }
+
         self
     }
 
@@ -366,6 +474,19 @@ impl SandboxBuilder {
         self
     }
 
+    /// Add an executable in a directory that comes before the Volta shims in `PATH` (chainable)
+    pub fn path_executable(mut self, dir: &str, name: &str) -> Self {
+        let dir = sandbox_path(dir);
+        let file_name = dir.join(name);
+        self.files
+            .push(FileBuilder::new(file_name.clone(), "#!/bin/sh\n"));
+        self.executables.push(file_name);
+        if !self.path_dirs.contains(&dir) {
+            self.path_dirs.insert(0, dir);
+        }
+        self
+    }
+
     /// Add an arbitrary file to the test project within the sandbox (chainable)
     pub fn project_file(mut self, path: &str, contents: &str) -> Self {
         let file_name = self.root().join(path);
@@ -407,6 +528,16 @@ impl SandboxBuilder {
         self
     }
 
+    /// Set up an unpacked Node image, with a script standing in for its bundled npm (chainable)
+    #[cfg(unix)]
+    pub fn node_image(mut self, node_version: &str, npm_version: &str, npm_script: &str) -> Self {
+        let npm_file = node_image_dir(node_version).join("bin").join("npm");
+        self.files
+            .push(FileBuilder::new(npm_file.clone(), npm_script));
+        self.executables.push(npm_file);
+        self.node_npm_version_file(node_version, npm_version)
+    }
+
     /// Write the "default npm" file for a node version (chainable)
     pub fn node_npm_version_file(mut self, node_version: &str, npm_version: &str) -> Self {
         let npm_file = node_npm_version_file(node_version);
@@ -430,8 +561,9 @@ impl SandboxBuilder {
         ok_or_panic! { fs::create_dir_all(yarn_inventory_dir()) };
         ok_or_panic! { fs::create_dir_all(volta_tmp_dir()) };
 
-        // Make sure the shims to npm and yarn exist
+        // Make sure the shims to npm, pnpm, and yarn exist
         ok_or_panic! { symlink_file(shim_exe(), shim_file("npm")) };
+        ok_or_panic! { symlink_file(shim_exe(), shim_file("pnpm")) };
         ok_or_panic! { symlink_file(shim_exe(), shim_file("yarn")) };
 
         // write node and yarn caches
@@ -444,6 +576,10 @@ impl SandboxBuilder {
             file_builder.build();
         }
 
+        for executable in self.executables.iter() {
+            ok_or_panic! { set_executable(executable) };
+        }
+
         // join dirs for the path (volta bin path is already first)
         self.root.path = env::join_paths(self.path_dirs.iter()).unwrap();
 
@@ -524,8 +660,14 @@ fn binary_config_file(name: &str) -> PathBuf {
 fn shim_file(name: &str) -> PathBuf {
     volta_bin_dir().join(format!("{}{}", name, env::consts::EXE_SUFFIX))
 }
+#[cfg(unix)]
+fn node_image_dir(version: &str) -> PathBuf {
+    image_dir().join("node").join(version)
+}
 fn package_image_dir(name: &str, version: &str) -> PathBuf {
-    image_dir().join("packages").join(name).join(version)
+    image_dir()
+        .join("packages")
+        .join(format!("{}@{}", name, version))
 }
 fn default_platform_file() -> PathBuf {
     user_dir().join("platform.json")
@@ -607,6 +749,16 @@ impl Sandbox {
         p
     }
 
+    /// Create a `ProcessBuilder` to run the volta pnpm shim.
+    /// Arguments can be separated by spaces.
+    /// Example:
+    ///     assert_that(p.pnpm("add ember-cli"), execs());
+    pub fn pnpm(&self, cmd: &str) -> ProcessBuilder {
+        let mut p = self.process(shim_file("pnpm"));
+        split_and_add_args(&mut p, cmd);
+        p
+    }
+
     /// Create a `ProcessBuilder` to run the volta yarn shim.
     /// Arguments can be separated by spaces.
     /// Example:
@@ -617,6 +769,16 @@ impl Sandbox {
         p
     }
 
+    /// Create a `ProcessBuilder` to run the volta shim for a package binary.
+    /// Arguments can be separated by spaces.
+    /// Example:
+    ///     assert_that(p.shim("tsc", "--version"), execs());
+    pub fn shim(&self, bin: &str, cmd: &str) -> ProcessBuilder {
+        let mut p = self.process(shim_file(bin));
+        split_and_add_args(&mut p, cmd);
+        p
+    }
+
     pub fn read_package_json(&self) -> String {
         let package_file = package_json_file(self.root());
         read_file_to_string(package_file)
@@ -630,6 +792,25 @@ impl Sandbox {
         volta_home().rm_rf();
     }
 
+    pub fn remove_path(&self, path: &str) {
+        let path = sandbox_path(path);
+        if path.is_file() {
+            fs::remove_file(&path)
+                .unwrap_or_else(|e| panic!("could not remove file {}: {}", path.display(), e));
+        } else {
+            path.rm_rf();
+        }
+    }
+
+    pub fn append_to_file(&self, path: &str, contents: &str) {
+        let mut file = fs::OpenOptions::new()
+            .append(true)
+            .open(sandbox_path(path))
+            .unwrap_or_else(|e| panic!("could not open file {}: {}", path, e));
+        file.write_all(contents.as_bytes())
+            .unwrap_or_else(|e| panic!("could not append to file {}: {}", path, e));
+    }
+
     // check that files in the sandbox exist
 
     pub fn node_inventory_archive_exists(&self, version: &Version) -> bool {
@@ -660,6 +841,9 @@ impl Sandbox {
         let package_img_dir = package_image_dir(name, version);
         package_img_dir.join("package.json").exists()
     }
+    pub fn read_package_config(name: &str) -> String {
+        read_file_to_string(package_config_file(name))
+    }
     pub fn read_default_platform() -> String {
         read_file_to_string(default_platform_file())
     }
@@ -671,6 +855,24 @@ impl Drop for Sandbox {
     }
 }
 
+/// A script standing in for npm, which "installs" the given version of a package whatever was
+/// requested, with a single binary that prints the package name and version
+#[cfg(unix)]
+pub fn fake_npm_install(name: &str, version: &str, bin: &str) -> String {
+    format!(
+        r#"#!/bin/sh
+package_dir="$npm_config_prefix/lib/node_modules/{name}"
+/bin/mkdir -p "$package_dir" "$npm_config_prefix/bin"
+echo '{{"name":"{name}","version":"{version}","bin":{{"{bin}":"index.js"}}}}' > "$package_dir/package.json"
+printf '#!/bin/sh\necho {name}@{version}\n' > "$npm_config_prefix/bin/{bin}"
+/bin/chmod +x "$npm_config_prefix/bin/{bin}"
+"#,
+        name = name,
+        version = version,
+        bin = bin
+    )
+}
+
 // Generates a sandboxed environment
 pub fn sandbox() -> SandboxBuilder {
     SandboxBuilder::new(paths::root().join("sandbox"))
@@ -0,0 +1,132 @@
+use crate::support::sandbox::{sandbox, DistroMetadata, NodeFixture, Sandbox};
+use hamcrest2::assert_that;
+use test_support::matchers::execs;
+use volta_core::error::ExitCode;
+use volta_core::tool::{Node, Yarn};
+
+const NODE_VERSION_INFO: &str = r#"[
+{"version":"v10.99.1040","npm":"6.2.26","lts": "Dubnium","files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]}
+]
+"#;
+
+const NODE_VERSION_FIXTURES: [DistroMetadata; 1] = [DistroMetadata {
+    version: "10.99.1040",
+    compressed_size: 273,
+    uncompressed_size: Some(0x0028_0000),
+}];
+
+fn node_image_file(version: &str) -> String {
+    format!(".volta/tools/image/node/{}/README.md", version)
+}
+
+fn node_archive_file(version: &str) -> String {
+    format!(
+        ".volta/tools/inventory/node/{}",
+        Node::archive_filename(&version.parse().unwrap())
+    )
+}
+
+fn yarn_archive_file(version: &str) -> String {
+    format!(
+        ".volta/tools/inventory/yarn/{}",
+        Yarn::archive_filename(version)
+    )
+}
+
+#[test]
+fn cache_list_shows_archives() {
+    let s = sandbox()
+        .file(&node_archive_file("9.11.2"), "contents don't matter")
+        .node_npm_version_file("9.11.2", "5.6.17")
+        .file(&yarn_archive_file("1.12.99"), "contents don't matter")
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+
+    assert_that!(
+        s.volta("cache list"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("node [..]node-v9.11.2-[..] ([..])")
+            .with_stdout_contains("yarn [..]yarn-v1.12.99.tar.gz ([..])")
+            .with_stdout_contains("No cached Node index")
+    );
+
+    assert_that!(
+        s.volta("cache size"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("node: 1 archive ([..])")
+            .with_stdout_contains("yarn: 1 archive ([..])")
+    );
+}
+
+#[test]
+fn cache_clean_keeps_images() {
+    let s = sandbox()
+        .file(&node_image_file("9.11.2"), "contents don't matter")
+        .file(&node_archive_file("9.11.2"), "contents don't matter")
+        .node_npm_version_file("9.11.2", "5.6.17")
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+
+    assert_that!(
+        s.volta("cache clean"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]removed 1 cached archive ([..] freed)")
+    );
+
+    assert!(!Sandbox::path_exists(&node_archive_file("9.11.2")));
+    assert!(Sandbox::path_exists(&node_image_file("9.11.2")));
+    assert!(Sandbox::path_exists(
+        ".volta/tools/inventory/node/node-v9.11.2-npm"
+    ));
+}
+
+#[test]
+fn cache_verify_removes_corrupt_archives() {
+    let s = sandbox()
+        .node_available_versions(NODE_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .file(&yarn_archive_file("1.12.99"), "corrupted contents")
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+
+    assert_that!(
+        s.volta("fetch node@10.99.1040"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    assert_that!(
+        s.volta("cache verify"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("Verified 2 cached archives, 1 corrupt archive removed")
+    );
+
+    assert!(Sandbox::path_exists(&node_archive_file("10.99.1040")));
+    assert!(!Sandbox::path_exists(&yarn_archive_file("1.12.99")));
+}
+
+#[test]
+fn cache_verify_refreshes_index() {
+    let s = sandbox()
+        .node_cache("http://localhost/node-dist/index.json\n[]", false)
+        .node_available_versions(NODE_VERSION_INFO)
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+
+    assert_that!(
+        s.volta("cache verify --refresh-index"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]refreshed the Node index")
+    );
+
+    assert_that!(
+        s.volta("cache list"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("Node index [..] ([..], expires [..])")
+    );
+}
@@ -0,0 +1,127 @@
+use crate::support::sandbox::sandbox;
+use hamcrest2::assert_that;
+use test_support::matchers::execs;
+use volta_core::error::ExitCode;
+
+const PLATFORM_NODE_ONLY: &str = r#"{
+  "node": {
+    "runtime": "11.10.1",
+    "npm": null
+  },
+  "yarn": null
+}"#;
+
+const BIN_CONFIG_COWTHINK: &str = r#"{
+  "name": "cowthink",
+  "package": "cowsay",
+  "version": "1.4.0",
+  "platform": {
+    "node": "11.10.1",
+    "npm": null,
+    "yarn": null
+  },
+  "manager": "Npm"
+}"#;
+
+const PKG_CONFIG_VUE_CLI: &str = r#"{
+  "name": "@vue/cli",
+  "version": "5.0.8",
+  "platform": {
+    "node": "11.10.1",
+    "npm": null,
+    "yarn": null
+  },
+  "bins": [],
+  "manager": "Npm"
+}"#;
+
+#[test]
+fn doctor_finds_no_problems() {
+    let s = sandbox().env("VOLTA_LOGLEVEL", "info").build();
+
+    assert_that!(
+        s.volta("doctor"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]No problems found")
+    );
+}
+
+#[test]
+fn doctor_reports_problems_with_fixes() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .binary_config("cowthink", BIN_CONFIG_COWTHINK)
+        .default_hooks("{ not valid json")
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+
+    assert_that!(
+        s.volta("doctor"),
+        execs()
+            .with_status(ExitCode::UnknownError as i32)
+            .with_stderr_contains("[..]The default node@11.10.1 has not been fetched")
+            .with_stderr_contains(
+                "[..]The binary 'cowthink' refers to package 'cowsay', which doesn't provide it"
+            )
+            .with_stderr_contains("[..]Could not load the hooks configuration[..]")
+            .with_stdout_contains("[..]Run `volta install node@11.10.1`")
+            .with_stdout_contains("Found 3 problems")
+    );
+}
+
+#[test]
+fn doctor_checks_scoped_packages() {
+    let s = sandbox()
+        .package_config("@vue/cli", PKG_CONFIG_VUE_CLI)
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+
+    assert_that!(
+        s.volta("doctor"),
+        execs()
+            .with_status(ExitCode::UnknownError as i32)
+            .with_stderr_contains("[..]The image for package '@vue/cli@5.0.8' is missing")
+            .with_stdout_contains("[..]Run `volta install @vue/cli`")
+            .with_stdout_contains("Found 1 problem")
+    );
+}
+
+#[test]
+#[cfg(unix)]
+fn doctor_reports_tools_before_shims() {
+    let s = sandbox()
+        .path_executable("other/bin", "node")
+        .path_executable("other/bin", "yarn")
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+
+    assert_that!(
+        s.volta("doctor"),
+        execs()
+            .with_status(ExitCode::UnknownError as i32)
+            .with_stderr_contains(
+                "[..]other/bin/node' comes before the Volta shims in PATH, so it will be run instead of Volta's node"
+            )
+            .with_stderr_contains(
+                "[..]other/bin/yarn' comes before the Volta shims in PATH, so it will be run instead of Volta's yarn"
+            )
+            .with_stdout_contains("Found 2 problems")
+    );
+}
+
+#[test]
+fn doctor_reports_missing_shim_dir() {
+    let s = sandbox()
+        .env("PATH", "")
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+
+    assert_that!(
+        s.volta("doctor"),
+        execs()
+            .with_status(ExitCode::UnknownError as i32)
+            .with_stderr_contains("[..].volta[..]bin' is not in PATH")
+            .with_stdout_contains("[..]Run `volta setup`, then open a new terminal")
+    );
+}
@@ -1,5 +1,7 @@
+#[cfg(unix)]
+use crate::support::sandbox::fake_npm_install;
 use crate::support::sandbox::{
-    sandbox, DistroMetadata, NodeFixture, NpmFixture, Sandbox, YarnFixture,
+    sandbox, DistroMetadata, NodeFixture, NpmFixture, PnpmFixture, Sandbox, YarnFixture,
 };
 use hamcrest2::assert_that;
 use hamcrest2::prelude::*;
@@ -33,6 +35,20 @@ fn platform_with_node_npm(node: &str, npm: &str) -> String {
     )
 }
 
+fn platform_with_node_pnpm(node: &str, pnpm: &str) -> String {
+    format!(
+        r#"{{
+  "node": {{
+    "runtime": "{}",
+    "npm": null
+  }},
+  "pnpm": "{}",
+  "yarn": null
+}}"#,
+        node, pnpm
+    )
+}
+
 const NODE_VERSION_INFO: &str = r#"[
 {"version":"v10.99.1040","npm":"6.2.26","lts": "Dubnium","files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]},
 {"version":"v9.27.6","npm":"5.6.17","lts": false,"files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]},
@@ -116,13 +132,16 @@ cfg_if::cfg_if! {
     }
 }
 
-const YARN_VERSION_INFO: &str = r#"[
-{"tag_name":"v1.2.42","assets":[{"name":"yarn-v1.2.42.tar.gz"}]},
-{"tag_name":"v1.3.1","assets":[{"name":"yarn-v1.3.1.msi"}]},
-{"tag_name":"v1.4.159","assets":[{"name":"yarn-v1.4.159.tar.gz"}]},
-{"tag_name":"v1.7.71","assets":[{"name":"yarn-v1.7.71.tar.gz"}]},
-{"tag_name":"v1.12.99","assets":[{"name":"yarn-v1.12.99.tar.gz"}]}
-]"#;
+const YARN_VERSION_INFO: &str = r#"{
+    "name":"yarn",
+    "dist-tags": { "latest":"1.12.99" },
+    "versions": {
+        "1.2.42": { "version":"1.2.42", "dist": { "shasum":"a04472b4febb093141993a289b7253bfffba11db", "tarball":"" }},
+        "1.4.159": { "version":"1.4.159", "dist": { "shasum":"1b211aa31eaf27ab4c9a2c68c52d8d5f6bb9d48c", "tarball":"" }},
+        "1.7.71": { "version":"1.7.71", "dist": { "shasum":"090cc91fdeb93933518daf741acfb505106dc711", "tarball":"" }},
+        "1.12.99": { "version":"1.12.99", "dist": { "shasum":"cc1a5558d673700822f42bcadcc50b8898b3e26f", "tarball":"" }}
+    }
+}"#;
 
 const YARN_VERSION_FIXTURES: [DistroMetadata; 4] = [
     DistroMetadata {
@@ -147,14 +166,36 @@ const YARN_VERSION_FIXTURES: [DistroMetadata; 4] = [
     },
 ];
 
+const PNPM_VERSION_INFO: &str = r#"{
+    "name":"pnpm",
+    "dist-tags": { "latest":"8.6.12" },
+    "versions": {
+        "7.7.1": { "version":"7.7.1", "dist": { "shasum":"85cd6f772e9501c7f7865f9a8de8ccb273ffec16", "integrity":"sha512-1saveQpUyq8ASrVW1HgD6g8v+J0VGCzgaOeouHS34XPvuOft6klQAsgfKkkPoKGt/5tZsuNRXrELTYtnqsQCkw==", "tarball":"" }},
+        "8.6.12": { "version":"8.6.12", "dist": { "shasum":"4f4089acc760845fb62839e12017cadbfcb204e0", "integrity":"sha512-zMC3A8XTompoNUqjxcNJeSXlUQmKF/7XecffNcAjEuwaRIj4c7bvth7VYZ/BUtm4Bb2vPN7Hp8Fj96+Od14JIQ==", "tarball":"" }}
+    }
+}"#;
+
+const PNPM_VERSION_FIXTURES: [DistroMetadata; 2] = [
+    DistroMetadata {
+        version: "7.7.1",
+        compressed_size: 199,
+        uncompressed_size: Some(0x0028_0000),
+    },
+    DistroMetadata {
+        version: "8.6.12",
+        compressed_size: 200,
+        uncompressed_size: Some(0x0028_0000),
+    },
+];
+
 const NPM_VERSION_INFO: &str = r#"
 {
     "name":"npm",
     "dist-tags": { "latest":"8.1.5" },
     "versions": {
-        "1.2.3": { "version":"1.2.3", "dist": { "shasum":"", "tarball":"" }},
-        "4.5.6": { "version":"4.5.6", "dist": { "shasum":"", "tarball":"" }},
-        "8.1.5": { "version":"8.1.5", "dist": { "shasum":"", "tarball":"" }}
+        "1.2.3": { "version":"1.2.3", "dist": { "shasum":"10d224f266a6fda6cc0acfc60a7ee41e68c70f64", "tarball":"" }},
+        "4.5.6": { "version":"4.5.6", "dist": { "shasum":"459724a7681468c76bb3e45cb1ee3deb30462f6d", "tarball":"" }},
+        "8.1.5": { "version":"8.1.5", "dist": { "shasum":"90d46be79e04806f4ed7a7a4b8599ea02f2a20eb", "integrity":"sha512-gCG75wi/78E3AS3jI/R6t+fMwuchy9rR7ElgmfMgy8vbgI5z9TraQCOXd2ENhgINSpaWJ2spdQvSyBWav9+B3w==", "tarball":"" }}
     }
 }
 "#;
@@ -279,3 +320,350 @@ fn install_yarn_without_node_errors() {
             )
     );
 }
+
+#[test]
+fn install_pnpm() {
+    let s = sandbox()
+        .platform(&platform_with_node("8.9.10"))
+        .pnpm_available_versions(PNPM_VERSION_INFO)
+        .distro_mocks::<PnpmFixture>(&PNPM_VERSION_FIXTURES)
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+
+    assert_that!(
+        s.volta("install pnpm@7"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]installed and set pnpm@7.7.1 as default")
+    );
+
+    assert_eq!(
+        Sandbox::read_default_platform(),
+        platform_with_node_pnpm("8.9.10", "7.7.1")
+    );
+    assert!(Sandbox::path_exists(
+        ".volta/tools/image/pnpm/7.7.1/bin/pnpm"
+    ));
+    assert!(Sandbox::path_exists(
+        ".volta/tools/image/pnpm/7.7.1/bin/pnpx"
+    ));
+}
+
+#[test]
+fn install_pnpm_without_node_errors() {
+    let s = sandbox()
+        .pnpm_available_versions(PNPM_VERSION_INFO)
+        .distro_mocks::<PnpmFixture>(&PNPM_VERSION_FIXTURES)
+        .build();
+
+    assert_that!(
+        s.volta("install pnpm@8.6.12"),
+        execs()
+            .with_status(ExitCode::ConfigurationError as i32)
+            .with_stderr_contains(
+                "[..]Cannot install pnpm because the default Node version is not set."
+            )
+    );
+}
+
+#[test]
+fn install_node_offline_from_inventory() {
+    let s = sandbox()
+        .file(".volta/tools/image/node/10.99.1040/README", "")
+        .file(".volta/tools/image/node/9.27.6/README", "")
+        .node_npm_version_file("10.99.1040", "6.2.26")
+        .node_npm_version_file("9.27.6", "5.6.17")
+        .build();
+
+    assert_that!(
+        s.volta("install --offline node@9"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    assert_eq!(
+        Sandbox::read_default_platform(),
+        platform_with_node("9.27.6")
+    );
+}
+
+#[test]
+fn install_node_lts_offline_uses_stale_index() {
+    let s = sandbox()
+        .node_cache(
+            &format!("https://nodejs.org/dist/index.json\n{}", NODE_VERSION_INFO),
+            true,
+        )
+        .file(".volta/tools/image/node/10.99.1040/README", "")
+        .file(".volta/tools/image/node/9.27.6/README", "")
+        .node_npm_version_file("10.99.1040", "6.2.26")
+        .node_npm_version_file("9.27.6", "5.6.17")
+        .env("VOLTA_OFFLINE", "1")
+        .build();
+
+    assert_that!(
+        s.volta("install node@lts/dubnium"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    assert_eq!(
+        Sandbox::read_default_platform(),
+        platform_with_node("10.99.1040")
+    );
+}
+
+#[test]
+fn install_node_offline_no_local_match() {
+    let s = sandbox()
+        .file(".volta/tools/image/node/9.27.6/README", "")
+        .node_npm_version_file("9.27.6", "5.6.17")
+        .env("VOLTA_OFFLINE", "1")
+        .build();
+
+    assert_that!(
+        s.volta("install node@10"),
+        execs()
+            .with_status(ExitCode::NoVersionMatch as i32)
+            .with_stderr_contains(
+                "[..]Could not find a node version matching \"[..]10[..]\" in the local inventory while offline."
+            )
+    );
+}
+
+#[test]
+fn install_node_offline_requires_download() {
+    let s = sandbox()
+        .node_available_versions(NODE_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .build();
+
+    assert_that!(
+        s.volta("install --offline node@10.99.1040"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains("[..]Could not download node@10.99.1040 while offline.")
+    );
+}
+
+#[test]
+#[cfg(unix)]
+fn install_package_offline_requires_download() {
+    let s = sandbox()
+        .platform(&platform_with_node("10.99.1040"))
+        .node_image(
+            "10.99.1040",
+            "6.2.26",
+            &fake_npm_install("typescript", "4.9.5", "tsc"),
+        )
+        .build();
+
+    assert_that!(
+        s.volta("install --offline typescript@4.9.5"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains("[..]Could not download typescript@4.9.5 while offline.")
+    );
+
+    assert!(!Sandbox::path_exists(
+        ".volta/tools/image/packages/typescript@4.9.5"
+    ));
+}
+
+#[test]
+fn install_node_offline_uses_cached_archive() {
+    let s = sandbox()
+        .node_available_versions(NODE_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .build();
+
+    assert_that!(
+        s.volta("fetch node@10.99.1040"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+    s.remove_path(".volta/tools/image/node/10.99.1040");
+
+    assert_that!(
+        s.volta("install node@10.99.1040").env("VOLTA_OFFLINE", "1"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    assert!(Sandbox::path_exists(".volta/tools/image/node/10.99.1040"));
+    assert_eq!(
+        Sandbox::read_default_platform(),
+        platform_with_node("10.99.1040")
+    );
+}
+
+#[test]
+fn install_node_with_offline_disabled_downloads() {
+    let s = sandbox()
+        .node_available_versions(NODE_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .env("VOLTA_OFFLINE", "0")
+        .build();
+
+    assert_that!(
+        s.volta("install node@10.99.1040"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    assert!(Sandbox::path_exists(".volta/tools/image/node/10.99.1040"));
+}
+
+#[test]
+fn install_alias_requires_package() {
+    let s = sandbox().build();
+
+    assert_that!(
+        s.volta("install node@14 --as node14"),
+        execs()
+            .with_status(ExitCode::InvalidArguments as i32)
+            .with_stderr_contains(
+                "[..]The `--as` option can only be used when installing a single package."
+            )
+    );
+}
+
+#[test]
+fn install_package_with_manager_requires_default() {
+    let s = sandbox()
+        .platform(&platform_with_node("8.9.10"))
+        .file(".volta/tools/image/node/8.9.10/README", "")
+        .node_npm_version_file("8.9.10", "5.6.7")
+        .build();
+
+    assert_that!(
+        s.volta("install cowsay --manager pnpm"),
+        execs()
+            .with_status(ExitCode::ConfigurationError as i32)
+            .with_stderr_contains("[..]pnpm is not available.")
+    );
+}
+
+#[test]
+fn install_package_uses_manager_setting() {
+    let s = sandbox()
+        .platform(&platform_with_node("8.9.10"))
+        .file(".volta/tools/image/node/8.9.10/README", "")
+        .node_npm_version_file("8.9.10", "5.6.7")
+        .file(".volta/settings.json", r#"{"packageManager": "yarn"}"#)
+        .build();
+
+    assert_that!(
+        s.volta("install cowsay"),
+        execs()
+            .with_status(ExitCode::ConfigurationError as i32)
+            .with_stderr_contains("[..]Yarn is not available.")
+    );
+}
+
+#[test]
+#[cfg(unix)]
+fn install_package_invalid_manager_setting_uses_npm() {
+    let s = sandbox()
+        .platform(&platform_with_node("10.99.1040"))
+        .node_image(
+            "10.99.1040",
+            "6.2.26",
+            &fake_npm_install("cowsay", "1.4.0", "cowsay"),
+        )
+        .file(".volta/settings.json", r#"{"packageManager": "bun"}"#)
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+
+    assert_that!(
+        s.volta("install cowsay"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains(
+                "[..]unknown package manager 'bun' in '[..]settings.json', using npm"
+            )
+    );
+
+    assert!(Sandbox::path_exists(
+        ".volta/tools/image/packages/cowsay@1.4.0"
+    ));
+}
+
+#[test]
+fn install_missing_local_package() {
+    let s = sandbox().platform(&platform_with_node("8.9.10")).build();
+
+    assert_that!(
+        s.volta("install ./missing-cli"),
+        execs()
+            .with_status(ExitCode::InvalidArguments as i32)
+            .with_stderr_contains("[..]Could not find the package to install at './missing-cli'")
+    );
+}
+
+#[test]
+fn install_platform_requires_package() {
+    let s = sandbox().platform(&platform_with_node("8.9.10")).build();
+
+    assert_that!(
+        s.volta("install yarn@1.2.42 --npm bundled"),
+        execs()
+            .with_status(ExitCode::InvalidArguments as i32)
+            .with_stderr_contains(
+                "[..]The `--node` and `--npm` options can only be used when installing packages."
+            )
+    );
+}
+
+#[test]
+#[cfg(unix)]
+fn install_package_records_node_override() {
+    let s = sandbox()
+        .platform(&platform_with_node("8.9.10"))
+        .node_image(
+            "10.99.1040",
+            "6.2.26",
+            &fake_npm_install("typescript", "4.9.5", "tsc"),
+        )
+        .build();
+
+    assert_that!(
+        s.volta("install typescript@4.9.5 --node 10.99.1040"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    let config = Sandbox::read_package_config("typescript");
+    assert!(config.contains(r#""node": "10.99.1040""#));
+    assert!(config.contains(
+        r#""overrides": {
+    "node": true
+  }"#
+    ));
+}
+
+#[test]
+fn install_migrate_packages_requires_node() {
+    let s = sandbox().platform(&platform_with_node("8.9.10")).build();
+
+    assert_that!(
+        s.volta("install yarn@1.2.42 --migrate-packages"),
+        execs()
+            .with_status(ExitCode::InvalidArguments as i32)
+            .with_stderr_contains(
+                "[..]The `--migrate-packages` option can only be used when installing Node."
+            )
+    );
+}
+
+#[test]
+fn install_node_migrate_packages_without_packages() {
+    let s = sandbox()
+        .platform(&platform_with_node("8.9.10"))
+        .node_available_versions(NODE_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+
+    assert_that!(
+        s.volta("install node@10.99.1040 --migrate-packages"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]installed and set node@10.99.1040[..]as default")
+            .with_stdout_contains("No global packages are installed")
+    );
+}
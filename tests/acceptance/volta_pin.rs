@@ -1,4 +1,9 @@
-use crate::support::sandbox::{sandbox, DistroMetadata, NodeFixture, NpmFixture, YarnFixture};
+#[cfg(unix)]
+use crate::support::sandbox::fake_npm_install;
+use crate::support::sandbox::{
+    sandbox, DistroMetadata, NodeFixture, NpmFixture, PnpmFixture, Sandbox, YarnBerryFixture,
+    YarnFixture,
+};
 use hamcrest2::assert_that;
 use hamcrest2::prelude::*;
 use test_support::matchers::execs;
@@ -32,6 +37,20 @@ fn package_json_with_pinned_node(node: &str) -> String {
     )
 }
 
+#[cfg(unix)]
+fn package_json_with_pinned_node_package(node: &str, packages: &str) -> String {
+    format!(
+        r#"{{
+  "name": "test-package",
+  "volta": {{
+    "node": "{}",
+    "packages": {}
+  }}
+}}"#,
+        node, packages
+    )
+}
+
 fn package_json_with_pinned_node_npm(node: &str, npm: &str) -> String {
     format!(
         r#"{{
@@ -45,6 +64,19 @@ fn package_json_with_pinned_node_npm(node: &str, npm: &str) -> String {
     )
 }
 
+fn package_json_with_pinned_node_pnpm(node_version: &str, pnpm_version: &str) -> String {
+    format!(
+        r#"{{
+  "name": "test-package",
+  "volta": {{
+    "node": "{}",
+    "pnpm": "{}"
+  }}
+}}"#,
+        node_version, pnpm_version
+    )
+}
+
 fn package_json_with_pinned_node_yarn(node_version: &str, yarn_version: &str) -> String {
     format!(
         r#"{{
@@ -163,10 +195,10 @@ const YARN_VERSION_INFO: &str = r#"{
     "name":"yarn",
     "dist-tags": { "latest":"1.12.99" },
     "versions": {
-        "1.2.42": { "version":"1.2.42", "dist": { "shasum":"", "tarball":"" }},
-        "1.4.159": { "version":"1.4.159", "dist": { "shasum":"", "tarball":"" }},
-        "1.7.71": { "version":"1.7.71", "dist": { "shasum":"", "tarball":"" }},
-        "1.12.99": { "version":"1.12.99", "dist": { "shasum":"", "tarball":"" }}
+        "1.2.42": { "version":"1.2.42", "dist": { "shasum":"a04472b4febb093141993a289b7253bfffba11db", "tarball":"" }},
+        "1.4.159": { "version":"1.4.159", "dist": { "shasum":"1b211aa31eaf27ab4c9a2c68c52d8d5f6bb9d48c", "tarball":"" }},
+        "1.7.71": { "version":"1.7.71", "dist": { "shasum":"090cc91fdeb93933518daf741acfb505106dc711", "tarball":"" }},
+        "1.12.99": { "version":"1.12.99", "dist": { "shasum":"cc1a5558d673700822f42bcadcc50b8898b3e26f", "tarball":"" }}
     }
 }"#;
 
@@ -193,6 +225,20 @@ const YARN_VERSION_FIXTURES: [DistroMetadata; 4] = [
     },
 ];
 
+const YARN_BERRY_VERSION_INFO: &str = r#"{
+    "name":"@yarnpkg/cli-dist",
+    "dist-tags": { "latest":"3.12.56", "stable":"3.12.56" },
+    "versions": {
+        "3.12.56": { "version":"3.12.56", "dist": { "shasum":"edfd25d0c2c5c326831c0766c59563e089ba1a29", "integrity":"sha512-MYOblZiL0kn4AZhTm+A/GDrxDRNbPd3j4z0xyePgd5pqzfvUBCzyTe/bnzdv5QdBLfBc3pMImzodUdsDlfErIA==", "tarball":"" }}
+    }
+}"#;
+
+const YARN_BERRY_VERSION_FIXTURES: [DistroMetadata; 1] = [DistroMetadata {
+    version: "3.12.56",
+    compressed_size: 257,
+    uncompressed_size: Some(0x0028_0000),
+}];
+
 const NPM_VERSION_FIXTURES: [DistroMetadata; 3] = [
     DistroMetadata {
         version: "1.2.3",
@@ -216,13 +262,35 @@ const NPM_VERSION_INFO: &str = r#"
     "name":"npm",
     "dist-tags": { "latest":"8.1.5" },
     "versions": {
-        "1.2.3": { "version":"1.2.3", "dist": { "shasum":"", "tarball":"" }},
-        "4.5.6": { "version":"4.5.6", "dist": { "shasum":"", "tarball":"" }},
-        "8.1.5": { "version":"8.1.5", "dist": { "shasum":"", "tarball":"" }}
+        "1.2.3": { "version":"1.2.3", "dist": { "shasum":"10d224f266a6fda6cc0acfc60a7ee41e68c70f64", "tarball":"" }},
+        "4.5.6": { "version":"4.5.6", "dist": { "shasum":"459724a7681468c76bb3e45cb1ee3deb30462f6d", "tarball":"" }},
+        "8.1.5": { "version":"8.1.5", "dist": { "shasum":"90d46be79e04806f4ed7a7a4b8599ea02f2a20eb", "tarball":"" }}
     }
 }
 "#;
 
+const PNPM_VERSION_INFO: &str = r#"{
+    "name":"pnpm",
+    "dist-tags": { "latest":"8.6.12" },
+    "versions": {
+        "7.7.1": { "version":"7.7.1", "dist": { "shasum":"85cd6f772e9501c7f7865f9a8de8ccb273ffec16", "integrity":"sha512-1saveQpUyq8ASrVW1HgD6g8v+J0VGCzgaOeouHS34XPvuOft6klQAsgfKkkPoKGt/5tZsuNRXrELTYtnqsQCkw==", "tarball":"" }},
+        "8.6.12": { "version":"8.6.12", "dist": { "shasum":"4f4089acc760845fb62839e12017cadbfcb204e0", "integrity":"sha512-zMC3A8XTompoNUqjxcNJeSXlUQmKF/7XecffNcAjEuwaRIj4c7bvth7VYZ/BUtm4Bb2vPN7Hp8Fj96+Od14JIQ==", "tarball":"" }}
+    }
+}"#;
+
+const PNPM_VERSION_FIXTURES: [DistroMetadata; 2] = [
+    DistroMetadata {
+        version: "7.7.1",
+        compressed_size: 199,
+        uncompressed_size: Some(0x0028_0000),
+    },
+    DistroMetadata {
+        version: "8.6.12",
+        compressed_size: 200,
+        uncompressed_size: Some(0x0028_0000),
+    },
+];
+
 const VOLTA_LOGLEVEL: &str = "VOLTA_LOGLEVEL";
 
 #[test]
@@ -280,6 +348,61 @@ fn pin_node_latest() {
     )
 }
 
+#[test]
+fn pin_node_lts_codename() {
+    let s = sandbox()
+        .package_json(BASIC_PACKAGE_JSON)
+        .node_available_versions(NODE_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .build();
+
+    assert_that!(
+        s.volta("pin node@lts/dubnium"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    assert_eq!(
+        s.read_package_json(),
+        package_json_with_pinned_node("10.99.1040"),
+    )
+}
+
+#[test]
+fn pin_node_bare_lts_codename() {
+    let s = sandbox()
+        .package_json(BASIC_PACKAGE_JSON)
+        .node_available_versions(NODE_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .build();
+
+    assert_that!(
+        s.volta("pin node@Dubnium"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    assert_eq!(
+        s.read_package_json(),
+        package_json_with_pinned_node("10.99.1040"),
+    )
+}
+
+#[test]
+fn pin_node_relative_lts_missing() {
+    let s = sandbox()
+        .package_json(BASIC_PACKAGE_JSON)
+        .node_available_versions(NODE_VERSION_INFO)
+        .build();
+
+    assert_that!(
+        s.volta("pin node@lts/-1"),
+        execs()
+            .with_status(ExitCode::NoVersionMatch as i32)
+            .with_stderr_contains("[..]Could not find Node version matching \"lts/-1\"[..]")
+    );
+
+    assert_eq!(s.read_package_json(), BASIC_PACKAGE_JSON)
+}
+
 #[test]
 fn pin_node_no_version() {
     let s = sandbox()
@@ -334,6 +457,51 @@ fn pin_node_with_npm_hides_bundled_version() {
     );
 }
 
+#[test]
+fn pin_pnpm_no_node() {
+    let s = sandbox()
+        .package_json(BASIC_PACKAGE_JSON)
+        .pnpm_available_versions(PNPM_VERSION_INFO)
+        .distro_mocks::<PnpmFixture>(&PNPM_VERSION_FIXTURES)
+        .build();
+
+    assert_that!(
+        s.volta("pin pnpm@7.7.1"),
+        execs()
+            .with_status(ExitCode::ConfigurationError as i32)
+            .with_stderr_contains(
+                "[..]Cannot pin pnpm because the Node version is not pinned in this project."
+            )
+    );
+
+    assert_eq!(s.read_package_json(), BASIC_PACKAGE_JSON)
+}
+
+#[test]
+fn pin_pnpm() {
+    let s = sandbox()
+        .package_json(&package_json_with_pinned_node("1.2.3"))
+        .pnpm_available_versions(PNPM_VERSION_INFO)
+        .distro_mocks::<PnpmFixture>(&PNPM_VERSION_FIXTURES)
+        .env(VOLTA_LOGLEVEL, "info")
+        .build();
+
+    assert_that!(
+        s.volta("pin pnpm@7"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]pinned pnpm@7.7.1 in package.json")
+    );
+
+    assert_eq!(
+        s.read_package_json(),
+        package_json_with_pinned_node_pnpm("1.2.3", "7.7.1"),
+    );
+    assert!(Sandbox::path_exists(
+        ".volta/tools/image/pnpm/7.7.1/bin/pnpm"
+    ));
+}
+
 #[test]
 fn pin_yarn_no_node() {
     let s = sandbox()
@@ -373,6 +541,52 @@ fn pin_yarn() {
     )
 }
 
+#[test]
+fn pin_yarn_berry() {
+    let s = sandbox()
+        .package_json(&package_json_with_pinned_node("1.2.3"))
+        .yarn_available_versions(YARN_VERSION_INFO)
+        .yarn_berry_available_versions(YARN_BERRY_VERSION_INFO)
+        .distro_mocks::<YarnBerryFixture>(&YARN_BERRY_VERSION_FIXTURES)
+        .build();
+
+    assert_that!(
+        s.volta("pin yarn@3"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    assert_eq!(
+        s.read_package_json(),
+        package_json_with_pinned_node_yarn("1.2.3", "3.12.56"),
+    );
+    assert!(Sandbox::path_exists(
+        ".volta/tools/image/yarn/3.12.56/bin/yarn.js"
+    ));
+    assert!(Sandbox::path_exists(
+        ".volta/tools/image/yarn/3.12.56/bin/yarn"
+    ));
+}
+
+#[test]
+fn pin_yarn_berry_tag() {
+    let s = sandbox()
+        .package_json(&package_json_with_pinned_node("1.2.3"))
+        .yarn_available_versions(YARN_VERSION_INFO)
+        .yarn_berry_available_versions(YARN_BERRY_VERSION_INFO)
+        .distro_mocks::<YarnBerryFixture>(&YARN_BERRY_VERSION_FIXTURES)
+        .build();
+
+    assert_that!(
+        s.volta("pin yarn@stable"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    assert_eq!(
+        s.read_package_json(),
+        package_json_with_pinned_node_yarn("1.2.3", "3.12.56"),
+    );
+}
+
 #[test]
 fn pin_yarn_reports_info() {
     let s = sandbox()
@@ -428,6 +642,70 @@ fn pin_yarn_no_version() {
     )
 }
 
+fn package_json_with_package_manager(node_version: &str, package_manager: &str) -> String {
+    format!(
+        r#"{{
+  "name": "test-package",
+  "packageManager": "{}",
+  "volta": {{
+    "node": "{}"
+  }}
+}}"#,
+        package_manager, node_version
+    )
+}
+
+#[test]
+fn pin_yarn_updates_package_manager() {
+    let s = sandbox()
+        .package_json(&package_json_with_package_manager(
+            "1.2.3",
+            "yarn@1.2.42+sha1.a04472b4febb093141993a289b7253bfffba11db",
+        ))
+        .yarn_available_versions(YARN_VERSION_INFO)
+        .distro_mocks::<YarnFixture>(&YARN_VERSION_FIXTURES)
+        .build();
+
+    assert_that!(
+        s.volta("pin yarn@1.4"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    assert_eq!(
+        s.read_package_json(),
+        r#"{
+  "name": "test-package",
+  "packageManager": "yarn@1.4.159",
+  "volta": {
+    "node": "1.2.3",
+    "yarn": "1.4.159"
+  }
+}"#,
+    )
+}
+
+#[test]
+fn pin_yarn_with_mismatched_package_manager_hash() {
+    let package_json = package_json_with_package_manager(
+        "1.2.3",
+        "yarn@1.4.159+sha1.0000000000000000000000000000000000000000",
+    );
+    let s = sandbox()
+        .package_json(&package_json)
+        .yarn_available_versions(YARN_VERSION_INFO)
+        .distro_mocks::<YarnFixture>(&YARN_VERSION_FIXTURES)
+        .build();
+
+    assert_that!(
+        s.volta("pin yarn@1.4.159"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains("[..]Checksum verification failed for yarn@1.4.159")
+    );
+
+    assert_eq!(s.read_package_json(), package_json);
+}
+
 #[test]
 fn pin_yarn_missing_release() {
     let s = sandbox()
@@ -670,3 +948,177 @@ fn pin_node_does_not_overwrite_extends() {
         .read_package_json()
         .contains(r#""extends": "./basic.json""#));
 }
+
+#[test]
+fn pin_package_outside_project() {
+    let s = sandbox().build();
+
+    assert_that!(
+        s.volta("pin typescript@4.9"),
+        execs()
+            .with_status(ExitCode::ConfigurationError as i32)
+            .with_stderr_contains("[..]Not in a node package.")
+    );
+}
+
+#[test]
+fn pin_package_source() {
+    let s = sandbox().package_json(BASIC_PACKAGE_JSON).build();
+
+    assert_that!(
+        s.volta("pin github:org/tool"),
+        execs()
+            .with_status(ExitCode::InvalidArguments as i32)
+            .with_stderr_contains("[..]Cannot pin the package from 'github:org/tool'.")
+    );
+}
+
+#[test]
+#[cfg(unix)]
+fn pin_package() {
+    let s = sandbox()
+        .package_json(&package_json_with_pinned_node("10.99.1040"))
+        .node_image(
+            "10.99.1040",
+            "6.2.26",
+            &fake_npm_install("typescript", "4.9.5", "tsc"),
+        )
+        .env(VOLTA_LOGLEVEL, "info")
+        .build();
+
+    assert_that!(
+        s.volta("pin typescript@4.9"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]pinned typescript@4.9.5 in package.json")
+    );
+
+    assert_eq!(
+        s.read_package_json(),
+        package_json_with_pinned_node_package(
+            "10.99.1040",
+            r#"{
+      "typescript": "4.9.5"
+    }"#
+        ),
+    );
+    assert!(Sandbox::path_exists(
+        ".volta/tools/image/packages/typescript@4.9.5"
+    ));
+
+    assert_that!(
+        s.shim("tsc", "--version"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("typescript@4.9.5")
+    );
+}
+
+#[test]
+#[cfg(unix)]
+fn pinned_package_shim_installs_only_the_providing_package() {
+    let s = sandbox()
+        .package_json(&package_json_with_pinned_node("10.99.1040"))
+        .node_image(
+            "10.99.1040",
+            "6.2.26",
+            &fake_npm_install("typescript", "4.9.5", "tsc"),
+        )
+        .build();
+
+    assert_that!(
+        s.volta("pin typescript@4.9"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    // Pin another package that isn't installed, which doesn't provide the binary, and remove the
+    // image of the pinned package
+    std::fs::write(
+        s.root().join("package.json"),
+        package_json_with_pinned_node_package(
+            "10.99.1040",
+            r#"{
+      "cowsay": "1.5.0",
+      "typescript": "4.9.5"
+    }"#,
+        ),
+    )
+    .unwrap();
+    s.remove_path(".volta/tools/image/packages/typescript@4.9.5");
+
+    assert_that!(
+        s.shim("tsc", "--version"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("typescript@4.9.5")
+    );
+    assert!(Sandbox::path_exists(
+        ".volta/tools/image/packages/typescript@4.9.5"
+    ));
+    assert!(!Sandbox::path_exists(
+        ".volta/tools/image/packages/cowsay@1.5.0"
+    ));
+}
+
+#[test]
+#[cfg(unix)]
+fn pinned_package_shim_installs_package_not_pinned_on_this_machine() {
+    let s = sandbox()
+        .package_json(&package_json_with_pinned_node("10.99.1040"))
+        .node_image(
+            "10.99.1040",
+            "6.2.26",
+            &fake_npm_install("typescript", "4.9.5", "tsc"),
+        )
+        .build();
+
+    assert_that!(
+        s.volta("pin typescript@4.9"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    // Only keep the shim and the pin in the manifest, as in a fresh clone of the project
+    s.remove_path(".volta/tools/pinned/typescript.json");
+    s.remove_path(".volta/tools/image/packages/typescript@4.9.5");
+
+    assert_that!(
+        s.shim("tsc", "--version"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("typescript@4.9.5")
+    );
+    assert!(Sandbox::path_exists(".volta/tools/pinned/typescript.json"));
+}
+
+#[test]
+#[cfg(unix)]
+fn setup_installs_pinned_packages() {
+    let s = sandbox()
+        .package_json(&package_json_with_pinned_node_package(
+            "10.99.1040",
+            r#"{
+      "typescript": "4.9.5"
+    }"#,
+        ))
+        .node_image(
+            "10.99.1040",
+            "6.2.26",
+            &fake_npm_install("typescript", "4.9.5", "tsc"),
+        )
+        .build();
+
+    assert_that!(
+        s.volta("setup"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    assert!(Sandbox::path_exists(
+        ".volta/tools/image/packages/typescript@4.9.5"
+    ));
+    assert_that!(
+        s.shim("tsc", "--version"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("typescript@4.9.5")
+    );
+}
@@ -0,0 +1,215 @@
+use crate::support::sandbox::{sandbox, Sandbox};
+use hamcrest2::assert_that;
+use test_support::matchers::execs;
+
+use volta_core::error::ExitCode;
+
+const PLATFORM_NODE: &str = r#"{
+  "node": {
+    "runtime": "10.99.1040",
+    "npm": null
+  }
+}"#;
+
+const PKG_CONFIG_COWSAY: &str = r#"{
+  "name": "cowsay",
+  "version": "1.4.0",
+  "platform": {
+    "node": "10.99.1040",
+    "npm": null,
+    "yarn": null
+  },
+  "bins": [
+    "cowsay"
+  ],
+  "manager": "Npm"
+}"#;
+
+const BIN_CONFIG_COWSAY: &str = r#"{
+  "name": "cowsay",
+  "package": "cowsay",
+  "version": "1.4.0",
+  "platform": {
+    "node": "10.99.1040",
+    "npm": null,
+    "yarn": null
+  },
+  "manager": "Npm"
+}"#;
+
+#[test]
+fn create_and_list_profiles() {
+    let s = sandbox().env("VOLTA_LOGLEVEL", "info").build();
+
+    assert_that!(
+        s.volta("profile create work"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]created profile 'work'")
+    );
+    assert!(Sandbox::path_exists(
+        ".volta/tools/user/profiles/work/packages"
+    ));
+
+    assert_that!(
+        s.volta("profile list"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("default (active)")
+            .with_stdout_contains("work")
+    );
+}
+
+#[test]
+fn create_existing_profile() {
+    let s = sandbox().build();
+
+    assert_that!(
+        s.volta("profile create default"),
+        execs()
+            .with_status(ExitCode::InvalidArguments as i32)
+            .with_stderr_contains("[..]The profile 'default' already exists.")
+    );
+}
+
+#[test]
+fn create_invalid_profile_name() {
+    let s = sandbox().build();
+
+    assert_that!(
+        s.volta("profile create ../work"),
+        execs()
+            .with_status(ExitCode::InvalidArguments as i32)
+            .with_stderr_contains("[..]Invalid profile name '../work'")
+    );
+}
+
+#[test]
+fn use_profile_switches_tools_and_packages() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE)
+        .package_config("cowsay", PKG_CONFIG_COWSAY)
+        .binary_config("cowsay", BIN_CONFIG_COWSAY)
+        .shim("cowsay")
+        .package_image("cowsay", "1.4.0")
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+
+    assert_that!(
+        s.volta("profile create work"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+    assert_that!(
+        s.volta("profile use work"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]using profile 'work'")
+    );
+    assert!(!Sandbox::shim_exists("cowsay"));
+
+    assert_that!(
+        s.volta("export"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_does_not_contain("[..]10.99.1040[..]")
+            .with_stdout_does_not_contain("[..]cowsay[..]")
+    );
+
+    assert_that!(
+        s.volta("profile use default"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+    assert!(Sandbox::shim_exists("cowsay"));
+
+    assert_that!(
+        s.volta("export"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains(r#"  "node": "10.99.1040","#)
+            .with_stdout_contains(r#"    "cowsay": "1.4.0""#)
+    );
+}
+
+#[test]
+fn use_profile_unlinks_previous_shared_libs() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE)
+        .package_config("cowsay", PKG_CONFIG_COWSAY)
+        .binary_config("cowsay", BIN_CONFIG_COWSAY)
+        .shim("cowsay")
+        .package_image("cowsay", "1.4.0")
+        .file(".volta/tools/shared/cowsay/index.js", "contents don't matter")
+        .build();
+
+    assert_that!(
+        s.volta("profile create work"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+    assert_that!(
+        s.volta("profile use work"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    assert!(!Sandbox::path_exists(".volta/tools/shared/cowsay"));
+}
+
+#[test]
+fn use_missing_profile() {
+    let s = sandbox().build();
+
+    assert_that!(
+        s.volta("profile use work"),
+        execs()
+            .with_status(ExitCode::InvalidArguments as i32)
+            .with_stderr_contains("[..]Could not find the profile 'work'.")
+    );
+}
+
+#[test]
+fn delete_profile() {
+    let s = sandbox().env("VOLTA_LOGLEVEL", "info").build();
+
+    assert_that!(
+        s.volta("profile create work"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+    assert_that!(
+        s.volta("profile delete work"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]deleted profile 'work'")
+    );
+    assert!(!Sandbox::path_exists(".volta/tools/user/profiles/work"));
+}
+
+#[test]
+fn delete_active_profile() {
+    let s = sandbox().build();
+
+    assert_that!(
+        s.volta("profile create work"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+    assert_that!(
+        s.volta("profile use work"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+    assert_that!(
+        s.volta("profile delete work"),
+        execs()
+            .with_status(ExitCode::InvalidArguments as i32)
+            .with_stderr_contains("[..]Could not delete the profile 'work' because it is in use.")
+    );
+}
+
+#[test]
+fn delete_default_profile() {
+    let s = sandbox().build();
+
+    assert_that!(
+        s.volta("profile delete default"),
+        execs()
+            .with_status(ExitCode::InvalidArguments as i32)
+            .with_stderr_contains("[..]The default profile cannot be deleted.")
+    );
+}
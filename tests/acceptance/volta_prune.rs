@@ -0,0 +1,260 @@
+use crate::support::sandbox::{sandbox, Sandbox, SandboxBuilder};
+use hamcrest2::assert_that;
+use test_support::matchers::execs;
+use volta_core::error::ExitCode;
+use volta_core::tool::{Node, Yarn};
+
+const PKG_CONFIG_COWSAY: &str = r#"{
+  "name": "cowsay",
+  "version": "1.4.0",
+  "platform": {
+    "node": "10.99.1040",
+    "npm": null,
+    "yarn": null
+  },
+  "bins": [
+    "cowsay"
+  ],
+  "manager": "Npm"
+}"#;
+
+const PLATFORM_NODE_ONLY: &str = r#"{
+  "node": {
+    "runtime": "11.10.1",
+    "npm": null
+  },
+  "yarn": null
+}"#;
+
+const PACKAGE_JSON_WITH_NODE: &str = r#"{
+  "name": "test-package",
+  "volta": {
+    "node": "9.11.2"
+  }
+}"#;
+
+const PKG_CONFIG_WITH_YARN: &str = r#"{
+  "name": "typescript",
+  "version": "4.9.5",
+  "platform": {
+    "node": "11.10.1",
+    "npm": null,
+    "yarn": "1.12.99"
+  },
+  "bins": [
+    "tsc"
+  ],
+  "manager": "Yarn"
+}"#;
+
+const PLATFORM_OTHER_NODE: &str = r#"{
+  "node": {
+    "runtime": "9.11.2",
+    "npm": null
+  },
+  "yarn": null
+}"#;
+
+fn node_image_file(version: &str) -> String {
+    format!(".volta/tools/image/node/{}/README.md", version)
+}
+
+fn node_archive_file(version: &str) -> String {
+    format!(
+        ".volta/tools/inventory/node/{}",
+        Node::archive_filename(&version.parse().unwrap())
+    )
+}
+
+fn yarn_image_file(version: &str) -> String {
+    format!(".volta/tools/image/yarn/{}/README.md", version)
+}
+
+fn yarn_archive_file(version: &str) -> String {
+    format!(
+        ".volta/tools/inventory/yarn/{}",
+        Yarn::archive_filename(version)
+    )
+}
+
+/// A sandbox with Node 11.10.1 as the default, Node 10.99.1040 used by a package, and other
+/// fetched versions of Node and Yarn that nothing uses
+fn sandbox_with_inventory() -> SandboxBuilder {
+    sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .package_config("cowsay", PKG_CONFIG_COWSAY)
+        .file(&node_image_file("11.10.1"), "contents don't matter")
+        .file(&node_archive_file("11.10.1"), "contents don't matter")
+        .node_npm_version_file("11.10.1", "6.7.0")
+        .file(&node_image_file("10.99.1040"), "contents don't matter")
+        .node_npm_version_file("10.99.1040", "6.2.26")
+        .file(&node_image_file("9.11.2"), "contents don't matter")
+        .file(&node_archive_file("9.11.2"), "contents don't matter")
+        .node_npm_version_file("9.11.2", "5.6.17")
+        .file(&node_archive_file("8.9.4"), "contents don't matter")
+        .file(&yarn_image_file("1.12.99"), "contents don't matter")
+        .file(&yarn_archive_file("1.12.99"), "contents don't matter")
+        .env("VOLTA_LOGLEVEL", "info")
+}
+
+#[test]
+fn prune_removes_unreferenced_versions() {
+    let s = sandbox_with_inventory().build();
+
+    assert_that!(
+        s.volta("prune"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]pruned node: 9.11.2 ([..])")
+            .with_stdout_contains("[..]pruned yarn: 1.12.99 ([..])")
+            .with_stdout_contains("[..] freed")
+    );
+
+    assert!(Sandbox::path_exists(&node_image_file("11.10.1")));
+    assert!(Sandbox::path_exists(&node_archive_file("11.10.1")));
+    assert!(Sandbox::path_exists(
+        ".volta/tools/inventory/node/node-v11.10.1-npm"
+    ));
+    assert!(Sandbox::path_exists(&node_image_file("10.99.1040")));
+
+    assert!(!Sandbox::path_exists(".volta/tools/image/node/9.11.2"));
+    assert!(!Sandbox::path_exists(&node_archive_file("9.11.2")));
+    assert!(!Sandbox::path_exists(
+        ".volta/tools/inventory/node/node-v9.11.2-npm"
+    ));
+    assert!(!Sandbox::path_exists(&node_archive_file("8.9.4")));
+    assert!(!Sandbox::path_exists(".volta/tools/image/yarn/1.12.99"));
+    assert!(!Sandbox::path_exists(&yarn_archive_file("1.12.99")));
+}
+
+#[test]
+fn prune_dry_run_removes_nothing() {
+    let s = sandbox_with_inventory().build();
+
+    assert_that!(
+        s.volta("prune --dry-run"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("Would prune node: 9.11.2 ([..])")
+            .with_stdout_contains("Would prune yarn: 1.12.99 ([..])")
+            .with_stdout_contains("[..] would be freed")
+    );
+
+    assert!(Sandbox::path_exists(&node_image_file("9.11.2")));
+    assert!(Sandbox::path_exists(&node_archive_file("8.9.4")));
+    assert!(Sandbox::path_exists(&yarn_image_file("1.12.99")));
+}
+
+#[test]
+fn prune_keeps_project_versions() {
+    let s = sandbox_with_inventory()
+        .package_json(PACKAGE_JSON_WITH_NODE)
+        .build();
+
+    assert_that!(
+        s.volta("prune --project ."),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]pruned node: cached archives only ([..])")
+    );
+
+    assert!(Sandbox::path_exists(&node_image_file("9.11.2")));
+    assert!(Sandbox::path_exists(&node_archive_file("9.11.2")));
+    assert!(!Sandbox::path_exists(&node_archive_file("8.9.4")));
+}
+
+#[test]
+fn prune_keeps_current_project_versions() {
+    let s = sandbox_with_inventory()
+        .package_json(PACKAGE_JSON_WITH_NODE)
+        .build();
+
+    assert_that!(
+        s.volta("prune"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]pruned node: cached archives only ([..])")
+    );
+
+    assert!(Sandbox::path_exists(&node_image_file("9.11.2")));
+    assert!(Sandbox::path_exists(&node_archive_file("9.11.2")));
+}
+
+#[test]
+fn prune_skip_current_project_removes_its_versions() {
+    let s = sandbox_with_inventory()
+        .package_json(PACKAGE_JSON_WITH_NODE)
+        .build();
+
+    assert_that!(
+        s.volta("prune --skip-current-project"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]pruned node: 9.11.2 ([..])")
+    );
+
+    assert!(!Sandbox::path_exists(".volta/tools/image/node/9.11.2"));
+}
+
+#[test]
+fn prune_unused_for_keeps_recently_used_versions() {
+    // Yarn 1.12.99 was last used at the Unix epoch, while Node 9.11.2 has no recorded usage, so
+    // the time its image was unpacked is used instead
+    let s = sandbox_with_inventory()
+        .file(".volta/tools/usage/yarn/1.12.99", "0")
+        .build();
+
+    assert_that!(
+        s.volta("prune --unused-for 90"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]pruned node: cached archives only ([..])")
+            .with_stdout_contains("[..]pruned yarn: 1.12.99 ([..])")
+    );
+
+    assert!(Sandbox::path_exists(&node_image_file("9.11.2")));
+    assert!(Sandbox::path_exists(&node_archive_file("9.11.2")));
+    assert!(!Sandbox::path_exists(&node_archive_file("8.9.4")));
+    assert!(!Sandbox::path_exists(".volta/tools/image/yarn/1.12.99"));
+}
+
+#[test]
+fn prune_missing_project_fails() {
+    let s = sandbox_with_inventory().build();
+
+    assert_that!(
+        s.volta("prune --project no-such-project"),
+        execs()
+            .with_status(ExitCode::ConfigurationError as i32)
+            .with_stderr_contains("[..]Could not find a project in 'no-such-project'.")
+    );
+
+    assert!(Sandbox::path_exists(&node_image_file("9.11.2")));
+}
+
+#[test]
+fn prune_keeps_versions_used_by_other_profiles() {
+    let s = sandbox_with_inventory()
+        .file(
+            ".volta/tools/user/profiles/work/platform.json",
+            PLATFORM_OTHER_NODE,
+        )
+        .file(
+            ".volta/tools/user/profiles/work/packages/typescript.json",
+            PKG_CONFIG_WITH_YARN,
+        )
+        .build();
+
+    assert_that!(
+        s.volta("prune"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]pruned node: cached archives only ([..])")
+            .with_stdout_does_not_contain("[..]pruned yarn[..]")
+    );
+
+    assert!(Sandbox::path_exists(&node_image_file("9.11.2")));
+    assert!(Sandbox::path_exists(&node_archive_file("9.11.2")));
+    assert!(!Sandbox::path_exists(&node_archive_file("8.9.4")));
+    assert!(Sandbox::path_exists(&yarn_image_file("1.12.99")));
+}
@@ -0,0 +1,128 @@
+use crate::support::sandbox::sandbox;
+#[cfg(unix)]
+use crate::support::sandbox::{fake_npm_install, Sandbox};
+use hamcrest2::assert_that;
+use test_support::matchers::execs;
+
+use volta_core::error::ExitCode;
+
+const PLATFORM_WITH_NODE: &str = r#"{
+  "node": {
+    "runtime": "10.99.1040",
+    "npm": null
+  },
+  "yarn": null
+}"#;
+
+#[cfg(unix)]
+const PLATFORM_WITH_OLD_NODE: &str = r#"{
+  "node": {
+    "runtime": "9.27.6",
+    "npm": null
+  },
+  "yarn": null
+}"#;
+
+#[cfg(unix)]
+fn package_config(node: &str, overrides: &str) -> String {
+    format!(
+        r#"{{
+  "name": "typescript",
+  "version": "4.9.5",
+  "platform": {{
+    "node": "{}",
+    "npm": null,
+    "yarn": null
+  }},
+  "bins": [
+    "tsc"
+  ],
+  "manager": "Npm"{}
+}}"#,
+        node, overrides
+    )
+}
+
+#[test]
+fn reinstall_unknown_package() {
+    let s = sandbox().platform(PLATFORM_WITH_NODE).build();
+
+    assert_that!(
+        s.volta("reinstall cowsay"),
+        execs()
+            .with_status(ExitCode::InvalidArguments as i32)
+            .with_stderr_contains(
+                "[..]Could not find the installed package 'cowsay' to reinstall."
+            )
+    );
+}
+
+#[test]
+fn reinstall_all_without_packages() {
+    let s = sandbox()
+        .platform(PLATFORM_WITH_NODE)
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+
+    assert_that!(
+        s.volta("reinstall --all"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("No global packages are installed")
+    );
+}
+
+#[test]
+#[cfg(unix)]
+fn reinstall_package_with_default_node() {
+    let s = sandbox()
+        .platform(PLATFORM_WITH_NODE)
+        .package_config("typescript", &package_config("9.27.6", ""))
+        .node_image(
+            "10.99.1040",
+            "6.2.26",
+            &fake_npm_install("typescript", "4.9.5", "tsc"),
+        )
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+
+    assert_that!(
+        s.volta("reinstall typescript"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]reinstalled 1 package with node@10.99.1040")
+    );
+
+    assert_eq!(
+        Sandbox::read_package_config("typescript"),
+        package_config("10.99.1040", "")
+    );
+}
+
+#[test]
+#[cfg(unix)]
+fn reinstall_package_keeps_node_override() {
+    let overrides = r#",
+  "overrides": {
+    "node": true
+  }"#;
+    let s = sandbox()
+        .platform(PLATFORM_WITH_OLD_NODE)
+        .package_config("typescript", &package_config("10.99.1040", overrides))
+        .node_image(
+            "10.99.1040",
+            "6.2.26",
+            &fake_npm_install("typescript", "4.9.5", "tsc"),
+        )
+        .build();
+
+    assert_that!(
+        s.volta("reinstall typescript"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    assert_eq!(
+        Sandbox::read_package_config("typescript"),
+        package_config("10.99.1040", overrides)
+    );
+}
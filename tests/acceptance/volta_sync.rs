@@ -0,0 +1,253 @@
+use crate::support::sandbox::{sandbox, Sandbox};
+#[cfg(unix)]
+use crate::support::sandbox::fake_npm_install;
+use hamcrest2::assert_that;
+use test_support::matchers::execs;
+
+use volta_core::error::ExitCode;
+
+const PLATFORM_WITH_YARN: &str = r#"{
+  "node": {
+    "runtime": "10.99.1040",
+    "npm": null
+  },
+  "yarn": "1.12.99"
+}"#;
+
+const PKG_CONFIG_COWSAY: &str = r#"{
+  "name": "cowsay",
+  "version": "1.4.0",
+  "platform": {
+    "node": "10.99.1040",
+    "npm": null,
+    "yarn": null
+  },
+  "bins": [
+    "cowsay"
+  ],
+  "manager": "Npm"
+}"#;
+
+const BIN_CONFIG_COWSAY: &str = r#"{
+  "name": "cowsay",
+  "package": "cowsay",
+  "version": "1.4.0",
+  "platform": {
+    "node": "10.99.1040",
+    "npm": null,
+    "yarn": null
+  },
+  "manager": "Npm"
+}"#;
+
+const PKG_CONFIG_COWSAY_WITH_NODE: &str = r#"{
+  "name": "cowsay",
+  "version": "1.4.0",
+  "platform": {
+    "node": "9.27.6",
+    "npm": null,
+    "yarn": null
+  },
+  "bins": [
+    "cowsay"
+  ],
+  "manager": "Npm",
+  "overrides": {
+    "node": true,
+    "npm": true
+  }
+}"#;
+
+#[cfg(unix)]
+const PLATFORM_WITH_OLD_NODE: &str = r#"{
+  "node": {
+    "runtime": "9.27.6",
+    "npm": null
+  },
+  "yarn": null
+}"#;
+
+#[cfg(unix)]
+const PKG_CONFIG_TYPESCRIPT: &str = r#"{
+  "name": "typescript",
+  "version": "4.9.5",
+  "platform": {
+    "node": "9.27.6",
+    "npm": null,
+    "yarn": null
+  },
+  "bins": [
+    "tsc"
+  ],
+  "manager": "Npm"
+}"#;
+
+#[cfg(unix)]
+const PKG_CONFIG_TYPESCRIPT_WITH_NODE: &str = r#"{
+  "name": "typescript",
+  "version": "4.9.5",
+  "platform": {
+    "node": "10.99.1040",
+    "npm": null,
+    "yarn": null
+  },
+  "bins": [
+    "tsc"
+  ],
+  "manager": "Npm",
+  "overrides": {
+    "node": true
+  }
+}"#;
+
+const TOOLFILE_NODE_ONLY: &str = r#"{
+  "node": "10"
+}"#;
+
+const TOOLFILE_WITH_PACKAGES: &str = r#"{
+  "node": "10.99.1040",
+  "yarn": "^1.12.0",
+  "packages": {
+    "cowsay": "1"
+  }
+}"#;
+
+#[test]
+fn sync_removes_undeclared_tools_and_packages() {
+    let s = sandbox()
+        .platform(PLATFORM_WITH_YARN)
+        .package_config("cowsay", PKG_CONFIG_COWSAY)
+        .binary_config("cowsay", BIN_CONFIG_COWSAY)
+        .shim("cowsay")
+        .package_image("cowsay", "1.4.0")
+        .project_file("tools.json", TOOLFILE_NODE_ONLY)
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+
+    assert_that!(
+        s.volta("sync --file tools.json"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]removed yarn from the default toolchain")
+            .with_stdout_contains("[..]package 'cowsay' uninstalled")
+            .with_stdout_contains("[..]made 2 changes to match the toolfile")
+    );
+
+    assert!(!Sandbox::read_default_platform().contains("1.12.99"));
+    assert!(!Sandbox::package_config_exists("cowsay"));
+    assert!(!Sandbox::shim_exists("cowsay"));
+}
+
+#[test]
+fn sync_keeps_matching_tools_and_packages() {
+    let s = sandbox()
+        .platform(PLATFORM_WITH_YARN)
+        .package_config("cowsay", PKG_CONFIG_COWSAY)
+        .binary_config("cowsay", BIN_CONFIG_COWSAY)
+        .shim("cowsay")
+        .package_image("cowsay", "1.4.0")
+        .project_file("tools.json", TOOLFILE_WITH_PACKAGES)
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+
+    assert_that!(
+        s.volta("sync --file tools.json"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]Everything already matches the toolfile")
+    );
+
+    assert!(Sandbox::package_config_exists("cowsay"));
+}
+
+#[test]
+fn sync_invalid_toolfile() {
+    let s = sandbox()
+        .platform(PLATFORM_WITH_YARN)
+        .project_file(
+            "tools.json",
+            r#"{"packages": {"cowsay": {"manager": "bun"}}}"#,
+        )
+        .build();
+
+    assert_that!(
+        s.volta("sync --file tools.json"),
+        execs()
+            .with_status(ExitCode::ConfigurationError as i32)
+            .with_stderr_contains("[..]Could not parse toolfile")
+    );
+}
+
+#[test]
+fn export_prints_toolfile() {
+    let s = sandbox()
+        .platform(PLATFORM_WITH_YARN)
+        .package_config("cowsay", PKG_CONFIG_COWSAY)
+        .build();
+
+    assert_that!(
+        s.volta("export"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains(r#"  "node": "10.99.1040","#)
+            .with_stdout_contains(r#"  "yarn": "1.12.99","#)
+            .with_stdout_contains(r#"    "cowsay": "1.4.0""#)
+    );
+}
+
+#[test]
+fn export_prints_package_node_and_npm() {
+    let s = sandbox()
+        .platform(PLATFORM_WITH_YARN)
+        .package_config("cowsay", PKG_CONFIG_COWSAY_WITH_NODE)
+        .build();
+
+    assert_that!(
+        s.volta("export"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains(r#"    "cowsay": {"#)
+            .with_stdout_contains(r#"      "version": "1.4.0","#)
+            .with_stdout_contains(r#"      "node": "9.27.6","#)
+            .with_stdout_contains(r#"      "npm": "bundled""#)
+    );
+}
+
+#[test]
+#[cfg(unix)]
+fn sync_reinstalls_package_with_declared_node() {
+    let s = sandbox()
+        .platform(PLATFORM_WITH_OLD_NODE)
+        .package_config("typescript", PKG_CONFIG_TYPESCRIPT)
+        .package_image("typescript", "4.9.5")
+        .node_image(
+            "10.99.1040",
+            "6.2.26",
+            &fake_npm_install("typescript", "4.9.5", "tsc"),
+        )
+        .project_file(
+            "tools.json",
+            r#"{"packages": {"typescript": {"version": "4.9.5", "node": "10.99.1040"}}}"#,
+        )
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+
+    assert_that!(
+        s.volta("sync --file tools.json"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]made 1 change to match the toolfile")
+    );
+
+    assert_eq!(
+        Sandbox::read_package_config("typescript"),
+        PKG_CONFIG_TYPESCRIPT_WITH_NODE
+    );
+
+    assert_that!(
+        s.volta("sync --file tools.json"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]Everything already matches the toolfile")
+    );
+}
@@ -2,6 +2,8 @@ use crate::support::sandbox::{sandbox, Sandbox};
 use hamcrest2::assert_that;
 use hamcrest2::prelude::*;
 use test_support::matchers::execs;
+use volta_core::error::ExitCode;
+use volta_core::tool::{Node, Yarn};
 
 const PKG_CONFIG_BASIC: &str = r#"{
   "name": "cowsay",
@@ -47,6 +49,30 @@ fn bin_config(name: &str) -> String {
     )
 }
 
+fn platform_with_node(node: &str) -> String {
+    format!(
+        r#"{{
+  "node": {{
+    "runtime": "{}",
+    "npm": null
+  }},
+  "yarn": null
+}}"#,
+        node
+    )
+}
+
+fn node_image_file(version: &str) -> String {
+    format!(".volta/tools/image/node/{}/README.md", version)
+}
+
+fn node_archive_file(version: &str) -> String {
+    format!(
+        ".volta/tools/inventory/node/{}",
+        Node::archive_filename(&version.parse().unwrap())
+    )
+}
+
 const VOLTA_LOGLEVEL: &str = "VOLTA_LOGLEVEL";
 
 #[test]
@@ -94,6 +120,149 @@ fn uninstall_package_basic() {
     assert!(!Sandbox::package_image_exists("cowsay", "1.4.0"));
 }
 
+const PKG_CONFIG_ALIAS: &str = r#"{
+  "name": "cowsay",
+  "alias": "cowsay1",
+  "version": "1.3.0",
+  "platform": {
+    "node": "11.10.1",
+    "npm": "6.7.0",
+    "yarn": null
+  },
+  "bins": [
+    "cowsay1"
+  ],
+  "manager": "Npm"
+}"#;
+
+const BIN_CONFIG_ALIAS: &str = r#"{
+  "name": "cowsay1",
+  "package": "cowsay",
+  "alias": "cowsay1",
+  "target": "cowsay",
+  "version": "1.3.0",
+  "platform": {
+    "node": "11.10.1",
+    "npm": "6.7.0",
+    "yarn": null
+  },
+  "manager": "Npm"
+}"#;
+
+const PKG_CONFIG_SOURCE: &str = r#"{
+  "name": "cowsay",
+  "source": "github:piuccio/cowsay",
+  "version": "1.4.0",
+  "platform": {
+    "node": "11.10.1",
+    "npm": "6.7.0",
+    "yarn": null
+  },
+  "bins": [
+    "cowsay",
+    "cowthink"
+  ],
+  "manager": "Npm"
+}"#;
+
+#[test]
+fn uninstall_package_source() {
+    let s = sandbox()
+        .package_config("cowsay", PKG_CONFIG_SOURCE)
+        .binary_config("cowsay", &bin_config("cowsay"))
+        .binary_config("cowthink", &bin_config("cowthink"))
+        .shim("cowsay")
+        .shim("cowthink")
+        .package_image("cowsay", "1.4.0")
+        .env(VOLTA_LOGLEVEL, "info")
+        .build();
+
+    assert_that!(
+        s.volta("uninstall github:piuccio/cowsay"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("[..]package 'cowsay' uninstalled")
+    );
+
+    assert!(!Sandbox::package_config_exists("cowsay"));
+    assert!(!Sandbox::bin_config_exists("cowsay"));
+    assert!(!Sandbox::shim_exists("cowsay"));
+    assert!(!Sandbox::package_image_exists("cowsay", "1.4.0"));
+}
+
+#[test]
+fn uninstall_package_alias() {
+    // uninstalling an alias should leave the default install of the package in place
+    let s = sandbox()
+        .package_config("cowsay", PKG_CONFIG_BASIC)
+        .package_config("cowsay1", PKG_CONFIG_ALIAS)
+        .binary_config("cowsay", &bin_config("cowsay"))
+        .binary_config("cowthink", &bin_config("cowthink"))
+        .binary_config("cowsay1", BIN_CONFIG_ALIAS)
+        .shim("cowsay")
+        .shim("cowthink")
+        .shim("cowsay1")
+        .package_image("cowsay", "1.4.0")
+        .package_image("cowsay", "1.3.0")
+        .env(VOLTA_LOGLEVEL, "info")
+        .build();
+
+    assert_that!(
+        s.volta("uninstall cowsay1"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("Removed executable 'cowsay1' installed by 'cowsay1'")
+            .with_stdout_contains("[..]package 'cowsay1' uninstalled")
+    );
+
+    assert!(!Sandbox::package_config_exists("cowsay1"));
+    assert!(!Sandbox::bin_config_exists("cowsay1"));
+    assert!(!Sandbox::shim_exists("cowsay1"));
+    assert!(!Sandbox::package_image_exists("cowsay", "1.3.0"));
+
+    assert!(Sandbox::package_config_exists("cowsay"));
+    assert!(Sandbox::bin_config_exists("cowsay"));
+    assert!(Sandbox::shim_exists("cowsay"));
+    assert!(Sandbox::package_image_exists("cowsay", "1.4.0"));
+}
+
+const PINNED_CONFIG: &str = r#"{
+  "name": "cowsay",
+  "versions": [
+    "1.4.0"
+  ],
+  "bins": [
+    "cowsay",
+    "cowthink"
+  ]
+}"#;
+
+#[test]
+fn uninstall_package_keeps_pinned_image() {
+    // a project still pins the same version of the package, so its image should be kept
+    let s = sandbox()
+        .package_config("cowsay", PKG_CONFIG_BASIC)
+        .binary_config("cowsay", &bin_config("cowsay"))
+        .binary_config("cowthink", &bin_config("cowthink"))
+        .shim("cowsay")
+        .shim("cowthink")
+        .package_image("cowsay", "1.4.0")
+        .file(".volta/tools/pinned/cowsay.json", PINNED_CONFIG)
+        .env(VOLTA_LOGLEVEL, "info")
+        .build();
+
+    assert_that!(
+        s.volta("uninstall cowsay"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("[..]package 'cowsay' uninstalled")
+    );
+
+    assert!(!Sandbox::package_config_exists("cowsay"));
+    assert!(!Sandbox::bin_config_exists("cowsay"));
+    assert!(Sandbox::package_image_exists("cowsay", "1.4.0"));
+}
+
 #[test]
 fn uninstall_package_no_bins() {
     // the package doesn't contain any executables, it should uninstall without error
@@ -178,3 +347,169 @@ fn uninstall_package_orphaned_bins() {
     assert!(!Sandbox::shim_exists("cowsay"));
     assert!(!Sandbox::shim_exists("cowthink"));
 }
+
+#[test]
+fn uninstall_node_basic() {
+    let s = sandbox()
+        .file(&node_image_file("11.10.1"), "contents don't matter")
+        .file(&node_archive_file("11.10.1"), "contents don't matter")
+        .node_npm_version_file("11.10.1", "6.7.0")
+        .env(VOLTA_LOGLEVEL, "info")
+        .build();
+
+    assert_that!(
+        s.volta("uninstall node@11.10.1"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]node@11.10.1 uninstalled")
+    );
+
+    assert!(!Sandbox::path_exists(".volta/tools/image/node/11.10.1"));
+    assert!(!Sandbox::path_exists(&node_archive_file("11.10.1")));
+    assert!(!Sandbox::path_exists(
+        ".volta/tools/inventory/node/node-v11.10.1-npm"
+    ));
+}
+
+#[test]
+fn uninstall_nonexistent_node() {
+    let s = sandbox().env(VOLTA_LOGLEVEL, "info").build();
+
+    assert_that!(
+        s.volta("uninstall node@11.10.1"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains("[..]No node@11.10.1 found to uninstall")
+    );
+}
+
+#[test]
+fn uninstall_node_requires_exact_version() {
+    let s = sandbox()
+        .file(&node_image_file("11.10.1"), "contents don't matter")
+        .node_npm_version_file("11.10.1", "6.7.0")
+        .build();
+
+    assert_that!(
+        s.volta("uninstall node@11"),
+        execs()
+            .with_status(ExitCode::InvalidArguments as i32)
+            .with_stderr_contains("[..]Could not determine which version of node to uninstall.")
+    );
+
+    assert!(Sandbox::path_exists(&node_image_file("11.10.1")));
+}
+
+#[test]
+fn uninstall_default_node_refused() {
+    let s = sandbox()
+        .platform(&platform_with_node("11.10.1"))
+        .file(&node_image_file("11.10.1"), "contents don't matter")
+        .node_npm_version_file("11.10.1", "6.7.0")
+        .build();
+
+    assert_that!(
+        s.volta("uninstall node@11.10.1"),
+        execs()
+            .with_status(ExitCode::ConfigurationError as i32)
+            .with_stderr_contains(
+                "[..]Could not uninstall node@11.10.1 because it is still used by:"
+            )
+            .with_stderr_contains("[..]the default platform")
+    );
+
+    assert!(Sandbox::path_exists(&node_image_file("11.10.1")));
+}
+
+#[test]
+fn uninstall_package_node_refused() {
+    let s = sandbox()
+        .package_config("cowsay", PKG_CONFIG_BASIC)
+        .file(&node_image_file("11.10.1"), "contents don't matter")
+        .node_npm_version_file("11.10.1", "6.7.0")
+        .build();
+
+    assert_that!(
+        s.volta("uninstall node@11.10.1"),
+        execs()
+            .with_status(ExitCode::ConfigurationError as i32)
+            .with_stderr_contains("[..]package 'cowsay'")
+    );
+
+    assert!(Sandbox::path_exists(&node_image_file("11.10.1")));
+}
+
+#[test]
+fn uninstall_default_node_force() {
+    let s = sandbox()
+        .platform(&platform_with_node("11.10.1"))
+        .package_config("cowsay", PKG_CONFIG_BASIC)
+        .file(&node_image_file("11.10.1"), "contents don't matter")
+        .node_npm_version_file("11.10.1", "6.7.0")
+        .env(VOLTA_LOGLEVEL, "info")
+        .build();
+
+    assert_that!(
+        s.volta("uninstall --force node@11.10.1"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]node@11.10.1 uninstalled")
+    );
+
+    assert!(!Sandbox::path_exists(".volta/tools/image/node/11.10.1"));
+}
+
+#[test]
+fn uninstall_yarn_basic() {
+    let s = sandbox()
+        .file(
+            ".volta/tools/image/yarn/1.22.10/package.json",
+            "contents don't matter",
+        )
+        .file(
+            &format!(
+                ".volta/tools/inventory/yarn/{}",
+                Yarn::archive_filename("1.22.10")
+            ),
+            "contents don't matter",
+        )
+        .env(VOLTA_LOGLEVEL, "info")
+        .build();
+
+    assert_that!(
+        s.volta("uninstall yarn@1.22.10"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]yarn@1.22.10 uninstalled")
+    );
+
+    assert!(!Sandbox::path_exists(".volta/tools/image/yarn/1.22.10"));
+    assert!(!s.yarn_inventory_archive_exists("1.22.10"));
+}
+
+#[test]
+fn uninstall_node_used_by_other_profile_refused() {
+    let s = sandbox()
+        .platform(&platform_with_node("10.99.1040"))
+        .file(
+            ".volta/tools/user/profiles/work/platform.json",
+            &platform_with_node("11.10.1"),
+        )
+        .file(
+            ".volta/tools/user/profiles/oss/packages/cowsay.json",
+            PKG_CONFIG_BASIC,
+        )
+        .file(&node_image_file("11.10.1"), "contents don't matter")
+        .node_npm_version_file("11.10.1", "6.7.0")
+        .build();
+
+    assert_that!(
+        s.volta("uninstall node@11.10.1"),
+        execs()
+            .with_status(ExitCode::ConfigurationError as i32)
+            .with_stderr_contains("[..]the default platform of profile 'work'")
+            .with_stderr_contains("[..]package 'cowsay'")
+    );
+
+    assert!(Sandbox::path_exists(&node_image_file("11.10.1")));
+}
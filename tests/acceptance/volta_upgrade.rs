@@ -0,0 +1,196 @@
+#[cfg(unix)]
+use crate::support::sandbox::fake_npm_install;
+use crate::support::sandbox::{sandbox, DistroMetadata, NodeFixture, Sandbox};
+use hamcrest2::assert_that;
+use test_support::matchers::execs;
+
+use volta_core::error::ExitCode;
+
+fn platform_with_node(node: &str) -> String {
+    format!(
+        r#"{{
+  "node": {{
+    "runtime": "{}",
+    "npm": null
+  }},
+  "yarn": null
+}}"#,
+        node
+    )
+}
+
+const NODE_VERSION_INFO: &str = r#"[
+{"version":"v10.99.1040","npm":"6.2.26","lts": "Dubnium","files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]},
+{"version":"v9.27.6","npm":"5.6.17","lts": false,"files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]}
+]
+"#;
+
+const NODE_VERSION_FIXTURES: [DistroMetadata; 1] = [DistroMetadata {
+    version: "9.27.6",
+    compressed_size: 272,
+    uncompressed_size: Some(0x0028_0000),
+}];
+
+#[test]
+fn upgrade_node_within_major() {
+    let s = sandbox()
+        .platform(&platform_with_node("9.1.0"))
+        .node_available_versions(NODE_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+
+    assert_that!(
+        s.volta("upgrade"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]node 9.1.0 -> 9.27.6")
+            .with_stdout_contains("[..]installed and set node@9.27.6[..]as default")
+            .with_stdout_contains("[..]upgraded 1 tool")
+    );
+
+    assert_eq!(
+        Sandbox::read_default_platform(),
+        platform_with_node("9.27.6")
+    );
+}
+
+#[test]
+fn upgrade_reports_up_to_date() {
+    let s = sandbox()
+        .platform(&platform_with_node("10.99.1040"))
+        .node_available_versions(NODE_VERSION_INFO)
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+
+    assert_that!(
+        s.volta("upgrade --all"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]Everything is already up to date")
+    );
+}
+
+#[test]
+fn upgrade_unknown_tool() {
+    let s = sandbox()
+        .platform(&platform_with_node("10.99.1040"))
+        .node_available_versions(NODE_VERSION_INFO)
+        .build();
+
+    assert_that!(
+        s.volta("upgrade typescript"),
+        execs()
+            .with_status(ExitCode::InvalidArguments as i32)
+            .with_stderr_contains(
+                "[..]Could not find 'typescript' among your default tools or installed packages."
+            )
+    );
+}
+
+const TYPESCRIPT_VERSION_INFO: &str = r#"
+{
+    "name":"typescript",
+    "dist-tags": { "latest":"5.0.4" },
+    "versions": {
+        "4.9.3": { "version":"4.9.3", "dist": { "shasum":"", "tarball":"" }},
+        "4.9.5": { "version":"4.9.5", "dist": { "shasum":"", "tarball":"" }},
+        "4.10.1": { "version":"4.10.1", "dist": { "shasum":"", "tarball":"" }},
+        "5.0.4": { "version":"5.0.4", "dist": { "shasum":"", "tarball":"" }}
+    }
+}
+"#;
+
+#[cfg(unix)]
+const PKG_CONFIG_WITH_RANGE: &str = r#"{
+  "name": "typescript",
+  "range": "~4.9",
+  "version": "4.9.3",
+  "platform": {
+    "node": "10.99.1040",
+    "npm": null,
+    "yarn": null
+  },
+  "bins": [
+    "tsc"
+  ],
+  "manager": "Npm"
+}"#;
+
+const PKG_CONFIG_FROM_SOURCE: &str = r#"{
+  "name": "typescript",
+  "source": "github:microsoft/TypeScript",
+  "version": "4.9.3",
+  "platform": {
+    "node": "10.99.1040",
+    "npm": null,
+    "yarn": null
+  },
+  "bins": [
+    "tsc"
+  ],
+  "manager": "Npm"
+}"#;
+
+#[test]
+#[cfg(unix)]
+fn upgrade_package_within_range() {
+    let s = sandbox()
+        .platform(&platform_with_node("10.99.1040"))
+        .package_config("typescript", PKG_CONFIG_WITH_RANGE)
+        .package_image("typescript", "4.9.3")
+        .package_available_versions("typescript", TYPESCRIPT_VERSION_INFO)
+        .node_image(
+            "10.99.1040",
+            "6.2.26",
+            &fake_npm_install("typescript", "4.9.5", "tsc"),
+        )
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+
+    assert_that!(
+        s.volta("upgrade typescript --verbose"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]typescript 4.9.3 -> 4.9.5")
+            .with_stderr_contains("[..]Installing typescript@4.9.5 with command[..]")
+            .with_stdout_contains("[..]upgraded 1 tool")
+    );
+
+    assert!(Sandbox::path_exists(
+        ".volta/tools/image/packages/typescript@4.9.5"
+    ));
+    assert!(!Sandbox::package_image_exists("typescript", "4.9.3"));
+
+    // The platform was taken from the default, so it isn't recorded as an override
+    let config = Sandbox::read_package_config("typescript");
+    assert!(config.contains(r#""node": "10.99.1040""#));
+    assert!(!config.contains("overrides"));
+
+    // The range is kept, so there is nothing newer to upgrade to
+    assert_that!(
+        s.volta("upgrade typescript"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]Everything is already up to date")
+    );
+}
+
+#[test]
+fn upgrade_skips_package_from_source() {
+    let s = sandbox()
+        .package_config("typescript", PKG_CONFIG_FROM_SOURCE)
+        .package_available_versions("typescript", TYPESCRIPT_VERSION_INFO)
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+
+    assert_that!(
+        s.volta("upgrade --all"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains(
+                "[..]Skipping typescript, which was installed from github:microsoft/TypeScript"
+            )
+            .with_stdout_contains("[..]Everything is already up to date")
+    );
+}
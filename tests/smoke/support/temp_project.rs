@@ -223,8 +223,15 @@ fn package_json_file(mut root: PathBuf) -> PathBuf {
 fn shim_file(name: &str, root: PathBuf) -> PathBuf {
     volta_bin_dir(root).join(format!("{}{}", name, env::consts::EXE_SUFFIX))
 }
-fn package_image_dir(name: &str, root: PathBuf) -> PathBuf {
-    image_dir(root).join("packages").join(name)
+fn package_image_dir(name: &str, version: &str, root: PathBuf) -> PathBuf {
+    image_dir(root)
+        .join("packages")
+        .join(format!("{}@{}", name, version))
+}
+fn package_config_file(name: &str, root: PathBuf) -> PathBuf {
+    default_toolchain_dir(root)
+        .join("packages")
+        .join(format!("{}.json", name))
 }
 fn default_platform_file(root: PathBuf) -> PathBuf {
     default_toolchain_dir(root).join("platform.json")
@@ -394,8 +401,14 @@ impl TempProject {
 
     /// Verify that the input package has been installed
     pub fn package_is_installed(&self, name: &str) -> bool {
-        let install_dir = package_image_dir(name, self.root());
-        install_dir.exists()
+        let config_file = package_config_file(name, self.root());
+        let config: serde_json::Value = match File::open(config_file) {
+            Ok(file) => serde_json::from_reader(file).expect("could not parse package config"),
+            Err(_) => return false,
+        };
+
+        let version = config["version"].as_str().unwrap_or_default();
+        package_image_dir(name, version, self.root()).exists()
     }
 
     /// Verify that the input package version has been fetched.